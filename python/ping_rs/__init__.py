@@ -6,22 +6,71 @@ and asynchronous interfaces, leveraging Rust's performance and safety.
 """
 
 from ping_rs._ping_rs import (
+    AsyncPathStream,
     AsyncPinger,
     AsyncPingStream,
+    DnsPinger,
+    DnsPingResult,
+    DnsResolver,
+    HostResult,
+    HttpPinger,
+    HttpPingResult,
+    HttpPingStream,
+    InfluxSink,
+    MetricsServer,
+    Monitor,
+    PathHopStats,
+    PathMtuResult,
+    PathReport,
     Pinger,
     PingResult,
+    PingResultBatch,
+    PingStats,
     PingStream,
+    Recorder,
+    StatsdSink,
+    SweepResult,
+    TlsPinger,
+    TlsPingResult,
+    TracerouteHop,
+    UdpPinger,
+    UdpPingStream,
     __version__,
+    clear_dns_cache,
     create_ping_stream,
+    discover,
+    discover_async,
+    discover_path_mtu,
+    discover_path_mtu_async,
+    get_dns_cache_stats,
+    get_inflight_pings,
+    get_max_concurrent_pings,
+    get_rate_limit_stats,
     ping_multiple,
     ping_multiple_async,
     ping_once,
     ping_once_async,
+    set_dns_cache,
+    set_global_rate_limit,
+    set_max_concurrent_pings,
+    start_metrics_server,
+    sweep,
+    sweep_async,
+    traceroute,
+    traceroute_async,
 )
+
+try:
+    # 仅在启用 `otlp` feature 构建时可用
+    from ping_rs._ping_rs import OtlpJsonSink
+except ImportError:  # pragma: no cover
+    OtlpJsonSink = None  # type: ignore[assignment,misc]
+
 from ping_rs.core_schema import (
     PingExitedResult,
     PingResultDict,
     PongResult,
+    PortUnreachableResult,
     TargetType,
     TimeoutResult,
     UnknownResult,
@@ -31,20 +80,66 @@ __all__ = [
     # 从 Rust 核心导出的类和函数
     "AsyncPinger",
     "AsyncPingStream",
+    "AsyncPathStream",
+    "DnsPinger",
+    "DnsPingResult",
+    "DnsResolver",
+    "HostResult",
+    "HttpPinger",
+    "HttpPingResult",
+    "HttpPingStream",
+    "InfluxSink",
+    "MetricsServer",
+    "Monitor",
+    "PathHopStats",
+    "PathMtuResult",
+    "PathReport",
     "Pinger",
     "PingStream",
     "PingResult",
+    "PingResultBatch",
+    "PingStats",
+    "Recorder",
+    "StatsdSink",
+    "SweepResult",
+    "TlsPinger",
+    "TlsPingResult",
+    "TracerouteHop",
+    "UdpPinger",
+    "UdpPingStream",
     "__version__",
     "create_ping_stream",
     "ping_once",
     "ping_once_async",
     "ping_multiple",
     "ping_multiple_async",
+    "set_global_rate_limit",
+    "get_rate_limit_stats",
+    "set_max_concurrent_pings",
+    "get_max_concurrent_pings",
+    "get_inflight_pings",
+    "set_dns_cache",
+    "clear_dns_cache",
+    "get_dns_cache_stats",
+    "start_metrics_server",
+    "sweep",
+    "sweep_async",
+    "discover",
+    "discover_async",
+    "traceroute",
+    "traceroute_async",
+    "discover_path_mtu",
+    "discover_path_mtu_async",
     # 从 core_schema 导出的类型定义，便于静态类型检查
     "PongResult",
     "TimeoutResult",
     "UnknownResult",
     "PingExitedResult",
+    "PortUnreachableResult",
     "PingResultDict",
     "TargetType",
 ]
+
+# 只有启用 `otlp` feature 构建时才导出
+if OtlpJsonSink is not None:
+    __all__ += ["OtlpJsonSink"]
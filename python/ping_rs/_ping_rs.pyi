@@ -1,8 +1,14 @@
 """Type stubs for ping_rs Rust extension module."""
 
-from typing import final
+from array import array
+from collections.abc import Iterator
+from ipaddress import IPv4Network, IPv6Network
+from os import PathLike
+from pathlib import Path
+from types import TracebackType
+from typing import Any, Literal, final, overload
 
-from typing_extensions import disjoint_base, override
+from typing_extensions import Self, disjoint_base, override
 
 from ping_rs.core_schema import PingResultDict, TargetType
 
@@ -10,16 +16,58 @@ __version__: str
 
 __all__ = [
     "PingResult",
+    "PingResultBatch",
     "Pinger",
     "AsyncPinger",
     "PingStream",
     "AsyncPingStream",
+    "Recorder",
+    "MetricsServer",
+    "OtlpJsonSink",
+    "InfluxSink",
+    "StatsdSink",
+    "Monitor",
+    "PingStats",
+    "SweepResult",
+    "HostResult",
+    "TracerouteHop",
+    "AsyncPathStream",
+    "PathReport",
+    "PathHopStats",
+    "PathMtuResult",
+    "UdpPinger",
+    "UdpPingStream",
+    "HttpPinger",
+    "HttpPingResult",
+    "HttpPingStream",
+    "TlsPinger",
+    "TlsPingResult",
+    "DnsPinger",
+    "DnsPingResult",
+    "DnsResolver",
     "__version__",
     "create_ping_stream",
     "ping_once",
     "ping_once_async",
     "ping_multiple",
     "ping_multiple_async",
+    "start_metrics_server",
+    "set_global_rate_limit",
+    "get_rate_limit_stats",
+    "set_max_concurrent_pings",
+    "get_max_concurrent_pings",
+    "get_inflight_pings",
+    "sweep",
+    "sweep_async",
+    "discover",
+    "discover_async",
+    "traceroute",
+    "traceroute_async",
+    "discover_path_mtu",
+    "discover_path_mtu_async",
+    "set_dns_cache",
+    "clear_dns_cache",
+    "get_dns_cache_stats",
 ]
 
 @disjoint_base
@@ -60,13 +108,32 @@ class PingResult:
         stderr: str
         def __new__(cls, exit_code: int, stderr: str) -> PingResult.PingExited: ...
 
+    @final
+    class PortUnreachable:
+        """The target answered with ICMP port unreachable (UDP probes only)."""
+
+        __match_args__ = ("duration_ms", "line")
+        duration_ms: float
+        line: str
+        def __new__(cls, duration_ms: float, line: str) -> PingResult.PortUnreachable: ...
+
     @override
     def __repr__(self) -> str: ...
     @override
     def __str__(self) -> str: ...
+    @override
+    def __eq__(self, other: object) -> bool: ...
+    @override
+    def __ne__(self, other: object) -> bool: ...
+    def __lt__(self, other: PingResult) -> bool: ...
+    def __le__(self, other: PingResult) -> bool: ...
+    def __gt__(self, other: PingResult) -> bool: ...
+    def __ge__(self, other: PingResult) -> bool: ...
+    @override
+    def __hash__(self) -> int: ...
     @property
     def duration_ms(self) -> float | None:
-        """Get the ping duration in milliseconds, or None if not a successful ping."""
+        """Get the ping duration in milliseconds, or None if not a Pong or PortUnreachable result."""
         ...
 
     @property
@@ -86,7 +153,12 @@ class PingResult:
 
     @property
     def type_name(self) -> str:
-        """Get the type name of this PingResult (Pong, Timeout, Unknown, or PingExited)."""
+        """Get the type name of this PingResult (Pong, Timeout, Unknown, PingExited, or PortUnreachable)."""
+        ...
+
+    @property
+    def type_code(self) -> int:
+        """Get the numeric type code (Pong=0, Timeout=1, Unknown=2, PingExited=3, PortUnreachable=4)."""
         ...
 
     def is_success(self) -> bool:
@@ -105,10 +177,70 @@ class PingResult:
         """Check if this is a ping process exit result."""
         ...
 
+    def is_port_unreachable(self) -> bool:
+        """Check if this is a port unreachable result."""
+        ...
+
     def to_dict(self) -> PingResultDict:
         """Convert this PingResult to a dictionary."""
         ...
 
+    @staticmethod
+    def parse_line(line: str) -> PingResult:
+        """Parse a single line of ping output (C locale first, then localized keywords and comma decimals)."""
+        ...
+
+    def to_json(self) -> str:
+        """Serialize this PingResult to a JSON string (same shape as `to_dict`)."""
+        ...
+
+    @classmethod
+    def from_json(cls, data: str) -> Self:
+        """Deserialize a PingResult from a JSON string produced by `to_json`."""
+        ...
+
+    @classmethod
+    def from_dict(cls, data: PingResultDict) -> Self:
+        """Build a PingResult from a dictionary produced by `to_dict`."""
+        ...
+
+    def __reduce__(self) -> tuple[Any, tuple[str]]: ...
+    def __getnewargs__(self) -> tuple[Any, ...]: ...
+
+@final
+class PingResultBatch:
+    """Columnar batch of ping results, kept in Rust until accessed."""
+
+    def __new__(cls, results: list[PingResult] = ..., timestamps: list[float] | None = None) -> PingResultBatch: ...
+    @property
+    def timestamps(self) -> array[float]:
+        """Unix timestamps (seconds) of each result as `array('d')`."""
+        ...
+
+    @property
+    def rtts_ms(self) -> array[float]:
+        """Round-trip times in milliseconds as `array('d')`, NaN for results other than Pong and PortUnreachable."""
+        ...
+
+    @property
+    def type_codes(self) -> array[int]:
+        """Result type codes as `array('B')`, see `PingResult.type_code`."""
+        ...
+
+    def success_count(self) -> int:
+        """Number of successful (Pong) results."""
+        ...
+
+    def to_list(self) -> list[PingResult]:
+        """Convert to a list of PingResult objects."""
+        ...
+
+    def __len__(self) -> int: ...
+    def __getitem__(self, index: int) -> PingResult: ...
+    def __iter__(self) -> Iterator[PingResult]: ...
+    @override
+    def __repr__(self) -> str: ...
+
 @final
 class Pinger:
     """High-level ping interface."""
@@ -122,6 +254,10 @@ class Pinger:
         ipv6: bool = False,
         dns_pre_resolve: bool = True,
         dns_resolve_timeout_ms: int | None = None,
+        dns_resolver: DnsResolver | None = None,
+        resolve_mode: Literal["first", "happy_eyeballs"] = "first",
+        size: int | None = None,
+        dont_fragment: bool = False,
     ) -> Pinger: ...
     def ping_once(self) -> PingResult:
         """Execute a single ping synchronously."""
@@ -131,6 +267,379 @@ class Pinger:
         """Execute multiple pings synchronously."""
         ...
 
+    def ping_multiple_batch(self, count: int = 4, timeout_ms: int | None = None) -> PingResultBatch:
+        """Execute multiple pings synchronously and return a columnar batch."""
+        ...
+
+    @override
+    def __repr__(self) -> str: ...
+
+@final
+class InfluxSink:
+    """Sends stream results as InfluxDB line protocol over UDP, HTTP or HTTPS."""
+
+    def __new__(
+        cls,
+        url: str,
+        measurement: str = "ping",
+        token: str | None = None,
+        flush_interval_ms: int = 1000,
+        timeout_ms: int = 5000,
+    ) -> InfluxSink: ...
+    def write(
+        self, result: PingResult | HttpPingResult | TlsPingResult | DnsPingResult, target: str = ""
+    ) -> None:
+        """Write a single result manually; probe results are converted with `to_ping_result()`."""
+        ...
+
+    def flush(self) -> None:
+        """Send buffered lines now and report earlier send errors."""
+        ...
+
+    def close(self) -> None:
+        """Stop periodic flushing after sending the remaining lines."""
+        ...
+
+    @property
+    def url(self) -> str:
+        """The destination URL."""
+        ...
+
+    def __enter__(self) -> InfluxSink: ...
+    def __exit__(
+        self,
+        exc_type: type[BaseException] | None,
+        exc_value: BaseException | None,
+        traceback: TracebackType | None,
+    ) -> bool: ...
+    @override
+    def __repr__(self) -> str: ...
+
+@final
+class StatsdSink:
+    """Sends stream results as StatsD timings and counters over UDP."""
+
+    def __new__(
+        cls,
+        host: str = "127.0.0.1",
+        port: int = 8125,
+        prefix: str = "ping",
+        tags: bool = False,
+    ) -> StatsdSink: ...
+    def write(
+        self, result: PingResult | HttpPingResult | TlsPingResult | DnsPingResult, target: str = ""
+    ) -> None:
+        """Write a single result manually; probe results are converted with `to_ping_result()`."""
+        ...
+
+    def flush(self) -> None:
+        """Report earlier send errors; metrics are sent immediately."""
+        ...
+
+    @override
+    def __repr__(self) -> str: ...
+
+@final
+class OtlpJsonSink:
+    """Pushes stream results as OTLP/JSON metrics to a collector's `/v1/metrics` over HTTP or HTTPS.
+
+    This is a push sink, not the OpenTelemetry SDK: metrics are encoded directly and cannot be
+    attached to an existing `MeterProvider`. Only available when built with the `otlp` cargo
+    feature, which the published wheels enable.
+    """
+
+    def __new__(
+        cls,
+        endpoint: str = "http://localhost:4318",
+        export_interval_ms: int = 10000,
+        service_name: str = "ping-rs",
+        timeout_ms: int = 10000,
+    ) -> OtlpJsonSink: ...
+    def write(
+        self, result: PingResult | HttpPingResult | TlsPingResult | DnsPingResult, target: str = ""
+    ) -> None:
+        """Record a single result manually; probe results are converted with `to_ping_result()`."""
+        ...
+
+    def force_flush(self) -> None:
+        """Export the current metrics immediately."""
+        ...
+
+    def shutdown(self) -> None:
+        """Stop periodic export after a final export."""
+        ...
+
+    @property
+    def endpoint(self) -> str:
+        """The collector URL metrics are posted to."""
+        ...
+
+    def __enter__(self) -> OtlpJsonSink: ...
+    def __exit__(
+        self,
+        exc_type: type[BaseException] | None,
+        exc_value: BaseException | None,
+        traceback: TracebackType | None,
+    ) -> bool: ...
+    @override
+    def __repr__(self) -> str: ...
+
+@final
+class PingStats:
+    """Snapshot of the latest state and rolling statistics of a monitored target."""
+
+    @property
+    def target(self) -> str: ...
+    @property
+    def state(self) -> Literal["up", "down", "unknown"]: ...
+    @property
+    def packets_sent(self) -> int: ...
+    @property
+    def packets_received(self) -> int: ...
+    @property
+    def packets_lost(self) -> int: ...
+    @property
+    def loss_pct(self) -> float | None:
+        """Packet loss within the rolling window, in percent."""
+        ...
+
+    @property
+    def min_ms(self) -> float | None: ...
+    @property
+    def avg_ms(self) -> float | None: ...
+    @property
+    def max_ms(self) -> float | None: ...
+    @property
+    def stddev_ms(self) -> float | None: ...
+    @property
+    def jitter_ms(self) -> float | None:
+        """Mean absolute difference between consecutive RTTs within the rolling window."""
+        ...
+
+    @property
+    def last_rtt_ms(self) -> float | None: ...
+    @property
+    def latest(self) -> PingResult | None: ...
+    @property
+    def last_update(self) -> float | None:
+        """Unix timestamp (seconds) of the latest result."""
+        ...
+
+    @property
+    def restarts(self) -> int: ...
+    @property
+    def dns_failures(self) -> int: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    @override
+    def __repr__(self) -> str: ...
+
+@final
+class HostResult:
+    """Result of probing one address during a sweep."""
+
+    @property
+    def target(self) -> str: ...
+    @property
+    def alive(self) -> bool:
+        """Whether any probe received a reply."""
+        ...
+
+    @property
+    def rtt_ms(self) -> float | None:
+        """Round-trip time of the first reply, in milliseconds."""
+        ...
+
+    @property
+    def results(self) -> list[PingResult]: ...
+    @override
+    def __repr__(self) -> str: ...
+
+@final
+class SweepResult:
+    """Result of a multi-target sweep, in the order the targets were given."""
+
+    @property
+    def hosts(self) -> list[HostResult]: ...
+    @property
+    def alive(self) -> list[str]:
+        """Addresses that replied."""
+        ...
+
+    @property
+    def dead(self) -> list[str]:
+        """Addresses that did not reply."""
+        ...
+
+    def __len__(self) -> int: ...
+    @override
+    def __repr__(self) -> str: ...
+
+@final
+class TracerouteHop:
+    """One hop on the path to a traceroute target."""
+
+    @property
+    def hop(self) -> int:
+        """Hop number, starting at 1."""
+        ...
+
+    @property
+    def address(self) -> str | None:
+        """Address that answered at this hop, or None if every probe timed out."""
+        ...
+
+    @property
+    def rtts_ms(self) -> list[float]:
+        """Round-trip times of the probes that got an answer, in milliseconds.
+
+        In `icmp` mode the system ping does not report the latency of "time to live exceeded"
+        messages, so for intermediate hops these are measured around the ping process and
+        include its start-up overhead.
+        """
+        ...
+
+    @property
+    def timeouts(self) -> int:
+        """Number of probes that got no answer."""
+        ...
+
+    @property
+    def reached(self) -> bool:
+        """Whether the target itself answered at this hop."""
+        ...
+
+    @override
+    def __repr__(self) -> str: ...
+
+@final
+class PathMtuResult:
+    """Result of a path MTU discovery."""
+
+    @property
+    def target(self) -> str: ...
+    @property
+    def address(self) -> str:
+        """Resolved address of the target."""
+        ...
+
+    @property
+    def mtu(self) -> int | None:
+        """Largest packet size (IP and ICMP headers included) that got through with don't-fragment set,
+        or None if even `min` did not."""
+        ...
+
+    @property
+    def payload_size(self) -> int | None:
+        """ICMP payload size matching `mtu` (what `ping -s` takes)."""
+        ...
+
+    @property
+    def mtu_hint(self) -> int | None:
+        """MTU reported by the last "fragmentation needed" message, if one was seen."""
+        ...
+
+    @property
+    def probes(self) -> int: ...
+    @override
+    def __repr__(self) -> str: ...
+
+@final
+class Monitor:
+    """Continuously pings many targets, restarting exited ping processes with exponential backoff."""
+
+    def __new__(
+        cls,
+        window: int = 100,
+        restart_backoff_ms: int = 1000,
+        max_backoff_ms: int = 60000,
+        backoff_multiplier: float = 2.0,
+        jitter_seed: int | None = None,
+    ) -> Monitor: ...
+    def add_target(
+        self,
+        target: TargetType,
+        interval_ms: int = 1000,
+        interface: str | None = None,
+        ipv4: bool = False,
+        ipv6: bool = False,
+        dns_resolve_timeout_ms: int | None = None,
+        dns_resolver: DnsResolver | None = None,
+        resolve_mode: Literal["first", "happy_eyeballs"] = "first",
+        start_jitter_ms: int = 0,
+        interval_jitter_pct: float = 0.0,
+    ) -> None:
+        """Start monitoring a target.
+
+        `start_jitter_ms` delays the first probe by a random offset in `[0, start_jitter_ms]`;
+        `interval_jitter_pct` varies the interval by up to `±pct%` each time ping starts. The interval is
+        rounded to 100ms and fixed for the lifetime of each ping process, so a jitter smaller than 50ms has no effect.
+        """
+        ...
+
+    def remove_target(self, target: TargetType) -> bool:
+        """Stop monitoring a target. Returns whether it was monitored."""
+        ...
+
+    def targets(self) -> list[str]: ...
+    def get_stats(self, target: TargetType) -> PingStats:
+        """Get the latest state and statistics of a target. Raises KeyError if unknown."""
+        ...
+
+    def get_all_stats(self) -> dict[str, PingStats]: ...
+    def add_sink(self, sink: Recorder | InfluxSink | StatsdSink | OtlpJsonSink) -> None:
+        """Attach a sink that receives the results of every target."""
+        ...
+
+    def stop(self) -> None:
+        """Stop monitoring all targets."""
+        ...
+
+    def is_running(self) -> bool: ...
+    def __len__(self) -> int: ...
+    def __contains__(self, target: object) -> bool: ...
+    def __enter__(self) -> Monitor: ...
+    def __exit__(
+        self,
+        exc_type: type[BaseException] | None,
+        exc_value: BaseException | None,
+        traceback: TracebackType | None,
+    ) -> bool: ...
+    @override
+    def __repr__(self) -> str: ...
+
+@final
+class MetricsServer:
+    """Embedded Prometheus exporter created by `start_metrics_server()`."""
+
+    @property
+    def host(self) -> str:
+        """The address the server listens on."""
+        ...
+
+    @property
+    def port(self) -> int:
+        """The port the server listens on (the assigned port when started with port 0)."""
+        ...
+
+    def render(self) -> str:
+        """Return the current metrics in the Prometheus text exposition format."""
+        ...
+
+    def is_running(self) -> bool:
+        """Check whether the server is still running."""
+        ...
+
+    def stop(self) -> None:
+        """Stop the HTTP server and all ping processes."""
+        ...
+
+    def __enter__(self) -> MetricsServer: ...
+    def __exit__(
+        self,
+        exc_type: type[BaseException] | None,
+        exc_value: BaseException | None,
+        traceback: TracebackType | None,
+    ) -> bool: ...
     @override
     def __repr__(self) -> str: ...
 
@@ -147,6 +656,10 @@ class AsyncPinger:
         ipv6: bool = False,
         dns_pre_resolve: bool = True,
         dns_resolve_timeout_ms: int | None = None,
+        dns_resolver: DnsResolver | None = None,
+        resolve_mode: Literal["first", "happy_eyeballs"] = "first",
+        size: int | None = None,
+        dont_fragment: bool = False,
     ) -> AsyncPinger: ...
     async def ping_once(self) -> PingResult:
         """Execute a single ping asynchronously."""
@@ -156,6 +669,10 @@ class AsyncPinger:
         """Execute multiple pings asynchronously."""
         ...
 
+    async def ping_multiple_batch(self, count: int = 4, timeout_ms: int | None = None) -> PingResultBatch:
+        """Execute multiple pings asynchronously and return a columnar batch."""
+        ...
+
     @override
     def __repr__(self) -> str: ...
 
@@ -173,6 +690,16 @@ class PingStream:
         max_count: int | None = None,
         dns_pre_resolve: bool = True,
         dns_resolve_timeout_ms: int | None = None,
+        dns_resolver: DnsResolver | None = None,
+        resolve_mode: Literal["first", "happy_eyeballs"] = "first",
+        restart_on_exit: bool = False,
+        restart_backoff_ms: int = 1000,
+        max_restarts: int | None = None,
+        start_jitter_ms: int = 0,
+        interval_jitter_pct: float = 0.0,
+        jitter_seed: int | None = None,
+        size: int | None = None,
+        dont_fragment: bool = False,
     ) -> PingStream: ...
     def try_recv(self) -> PingResult | None:
         """Try to receive the next ping result without blocking."""
@@ -186,6 +713,14 @@ class PingStream:
         """Check if the stream is still active."""
         ...
 
+    def drain(self, max_items: int | None = None) -> PingResultBatch:
+        """Take all results that have already arrived without blocking, as a columnar batch."""
+        ...
+
+    def add_sink(self, sink: Recorder | InfluxSink | StatsdSink | OtlpJsonSink) -> None:
+        """Attach a sink that receives every result produced by this stream."""
+        ...
+
     def __iter__(self) -> PingStream:
         """Return self as an sync iterator."""
         ...
@@ -194,7 +729,7 @@ class PingStream:
         """Get the next ping result synchronously.
 
         Raises:
-            StopIteration: When the stream is exhausted or the ping process exits
+            StopIteration: When the stream is exhausted or the ping process exits without restarting
         """
         ...
 
@@ -212,7 +747,25 @@ class AsyncPingStream:
         max_count: int | None = None,
         dns_pre_resolve: bool = True,
         dns_resolve_timeout_ms: int | None = None,
+        dns_resolver: DnsResolver | None = None,
+        resolve_mode: Literal["first", "happy_eyeballs"] = "first",
+        restart_on_exit: bool = False,
+        restart_backoff_ms: int = 1000,
+        max_restarts: int | None = None,
+        start_jitter_ms: int = 0,
+        interval_jitter_pct: float = 0.0,
+        jitter_seed: int | None = None,
+        size: int | None = None,
+        dont_fragment: bool = False,
     ) -> AsyncPingStream: ...
+    def add_sink(self, sink: Recorder | InfluxSink | StatsdSink | OtlpJsonSink) -> None:
+        """Attach a sink that receives every result produced by this stream."""
+        ...
+
+    async def drain(self, max_items: int | None = None) -> PingResultBatch:
+        """Take all results that have already arrived without waiting for new ones, as a columnar batch."""
+        ...
+
     def __aiter__(self) -> AsyncPingStream:
         """Return self as an async iterator."""
         ...
@@ -221,37 +774,583 @@ class AsyncPingStream:
         """Get the next ping result asynchronously.
 
         Raises:
-            StopAsyncIteration: When the stream is exhausted or the ping process exits
+            StopAsyncIteration: When the stream is exhausted or the ping process exits without restarting
         """
         ...
 
-def ping_once(
-    target: TargetType,
-    timeout_ms: int = 1000,
-    interface: str | None = None,
-    ipv4: bool = False,
-    ipv6: bool = False,
-    dns_pre_resolve: bool = True,
-    dns_resolve_timeout_ms: int | None = None,
-) -> PingResult:
-    """Execute a single ping operation synchronously."""
-    ...
+@final
+class AsyncPathStream:
+    """Continuously probes every hop on the path to a target, like `mtr`.
 
-async def ping_once_async(
-    target: TargetType,
-    timeout_ms: int = 1000,
-    interface: str | None = None,
-    ipv4: bool = False,
-    ipv6: bool = False,
-    dns_pre_resolve: bool = True,
-    dns_resolve_timeout_ms: int | None = None,
-) -> PingResult:
-    """Execute a single ping operation asynchronously."""
-    ...
+    Each iteration sends one probe to every hop (pings with increasing TTL) and returns the
+    updated `PathReport`. Hop statistics use a rolling window of the last `window` probes.
+    """
 
-def ping_multiple(
-    target: TargetType,
-    count: int = 4,
+    def __new__(
+        cls,
+        target: TargetType,
+        max_hops: int = 30,
+        interval_ms: int = 1000,
+        timeout_ms: int = 1000,
+        max_cycles: int | None = None,
+        window: int = 100,
+        interface: str | None = None,
+        ipv4: bool = False,
+        ipv6: bool = False,
+        dns_resolve_timeout_ms: int | None = None,
+    ) -> AsyncPathStream: ...
+    def report(self) -> PathReport:
+        """Current report; after the stream ends this is the final report."""
+        ...
+
+    def __aiter__(self) -> AsyncPathStream:
+        """Return self as an async iterator."""
+        ...
+
+    async def __anext__(self) -> PathReport:
+        """Run one probing cycle and return the updated report.
+
+        Raises:
+            StopAsyncIteration: When `max_cycles` cycles have been run
+            RuntimeError: If the target cannot be resolved or the ping process fails to start
+        """
+        ...
+
+@final
+class PathHopStats:
+    """Rolling loss and latency statistics of one hop on a monitored path."""
+
+    @property
+    def hop(self) -> int: ...
+    @property
+    def address(self) -> str | None:
+        """Address that answered most recently, or None if the hop never answered."""
+        ...
+
+    @property
+    def packets_sent(self) -> int: ...
+    @property
+    def packets_received(self) -> int: ...
+    @property
+    def packets_lost(self) -> int: ...
+    @property
+    def loss_pct(self) -> float | None: ...
+    @property
+    def min_ms(self) -> float | None: ...
+    @property
+    def avg_ms(self) -> float | None: ...
+    @property
+    def max_ms(self) -> float | None: ...
+    @property
+    def stddev_ms(self) -> float | None: ...
+    @property
+    def jitter_ms(self) -> float | None: ...
+    @property
+    def last_rtt_ms(self) -> float | None: ...
+    @override
+    def __repr__(self) -> str: ...
+
+@final
+class PathReport:
+    """Per-hop statistics of an `AsyncPathStream`. `str(report)` renders an `mtr --report` style table."""
+
+    @property
+    def target(self) -> str: ...
+    @property
+    def address(self) -> str | None:
+        """Resolved address of the target, or None before the first cycle."""
+        ...
+
+    @property
+    def cycles(self) -> int: ...
+    @property
+    def reached(self) -> bool:
+        """Whether the target itself has answered."""
+        ...
+
+    @property
+    def hops(self) -> list[PathHopStats]: ...
+    @override
+    def __repr__(self) -> str: ...
+    @override
+    def __str__(self) -> str: ...
+
+@final
+class UdpPinger:
+    """Probes `host:port` with UDP datagrams.
+
+    A reply datagram gives `Pong`, an ICMP port unreachable gives `PortUnreachable`, and no answer
+    within `timeout_ms` gives `Timeout`. Useful where ICMP is filtered (DNS, NTP, game servers).
+    """
+
+    def __new__(
+        cls,
+        target: TargetType,
+        port: int,
+        interval_ms: int = 1000,
+        timeout_ms: int = 1000,
+        payload: bytes | None = None,
+        ipv4: bool = False,
+        ipv6: bool = False,
+        dns_resolve_timeout_ms: int | None = None,
+    ) -> UdpPinger: ...
+    def ping_once(self) -> PingResult:
+        """Send one probe."""
+        ...
+
+    def ping_multiple(self, count: int = 4) -> list[PingResult]:
+        """Send `count` probes, `interval_ms` apart."""
+        ...
+
+    async def ping_once_async(self) -> PingResult:
+        """Asynchronous version of `ping_once`."""
+        ...
+
+    async def ping_multiple_async(self, count: int = 4) -> list[PingResult]:
+        """Asynchronous version of `ping_multiple`."""
+        ...
+
+    @override
+    def __repr__(self) -> str: ...
+
+@final
+class UdpPingStream:
+    """Continuous UDP probing of `host:port`; supports both `for` and `async for`."""
+
+    def __new__(
+        cls,
+        target: TargetType,
+        port: int,
+        interval_ms: int = 1000,
+        timeout_ms: int = 1000,
+        payload: bytes | None = None,
+        max_count: int | None = None,
+        ipv4: bool = False,
+        ipv6: bool = False,
+        dns_resolve_timeout_ms: int | None = None,
+    ) -> UdpPingStream: ...
+    def is_active(self) -> bool:
+        """Whether the stream will produce more results."""
+        ...
+
+    def __iter__(self) -> Iterator[PingResult]: ...
+    def __next__(self) -> PingResult: ...
+    def __aiter__(self) -> UdpPingStream: ...
+    async def __anext__(self) -> PingResult: ...
+
+HttpMethod = Literal["HEAD", "GET"]
+
+@final
+class HttpPingResult:
+    """Result of one HTTP(S) request with a per-phase timing breakdown.
+
+    Each `*_ms` phase is the duration of that phase alone (not cumulative); phases that were not
+    reached are None. `ttfb_ms` runs from sending the request to the first response byte.
+    """
+
+    url: str
+    method: str
+    address: str | None
+    status: int | None
+    dns_ms: float | None
+    connect_ms: float | None
+    tls_ms: float | None
+    ttfb_ms: float | None
+    total_ms: float
+    error: str | None
+    timed_out: bool
+    def is_success(self) -> bool:
+        """Whether a response with a status below 400 was received."""
+        ...
+
+    def to_ping_result(self) -> PingResult:
+        """Convert for use with stats and sinks.
+
+        Any response (including 4xx/5xx) is a `Pong` with `total_ms`, a timeout is a `Timeout`,
+        and other failures (DNS, connection refused, TLS) are `PingExited`.
+        """
+        ...
+
+    @override
+    def __repr__(self) -> str: ...
+
+@final
+class HttpPinger:
+    """Measures HTTP(S) request latency with DNS / connect / TLS / TTFB / total timings.
+
+    Every request uses a fresh connection (`Connection: close`), so each sample includes connection setup.
+    """
+
+    def __new__(
+        cls,
+        url: str,
+        method: HttpMethod = "HEAD",
+        interval_ms: int = 1000,
+        timeout_ms: int = 5000,
+        headers: dict[str, str] | None = None,
+        verify: bool = True,
+        ipv4: bool = False,
+        ipv6: bool = False,
+        dns_resolve_timeout_ms: int | None = None,
+    ) -> HttpPinger: ...
+    def ping_once(self) -> HttpPingResult:
+        """Send one request."""
+        ...
+
+    def ping_multiple(self, count: int = 4) -> list[HttpPingResult]:
+        """Send `count` requests, `interval_ms` apart."""
+        ...
+
+    async def ping_once_async(self) -> HttpPingResult:
+        """Asynchronous version of `ping_once`."""
+        ...
+
+    async def ping_multiple_async(self, count: int = 4) -> list[HttpPingResult]:
+        """Asynchronous version of `ping_multiple`."""
+        ...
+
+    @override
+    def __repr__(self) -> str: ...
+
+@final
+class HttpPingStream:
+    """Continuous HTTP(S) probing; supports both `for` and `async for`. Failed requests do not end the stream."""
+
+    def __new__(
+        cls,
+        url: str,
+        method: HttpMethod = "HEAD",
+        interval_ms: int = 1000,
+        timeout_ms: int = 5000,
+        headers: dict[str, str] | None = None,
+        verify: bool = True,
+        max_count: int | None = None,
+        ipv4: bool = False,
+        ipv6: bool = False,
+        dns_resolve_timeout_ms: int | None = None,
+    ) -> HttpPingStream: ...
+    def is_active(self) -> bool:
+        """Whether the stream will produce more results."""
+        ...
+
+    def __iter__(self) -> Iterator[HttpPingResult]: ...
+    def __next__(self) -> HttpPingResult: ...
+    def __aiter__(self) -> HttpPingStream: ...
+    async def __anext__(self) -> HttpPingResult: ...
+
+@final
+class TlsPingResult:
+    """Result of one TCP connect plus TLS handshake."""
+
+    target: str
+    port: int
+    server_name: str
+    address: str | None
+    connect_ms: float | None
+    handshake_ms: float | None
+    protocol_version: str | None
+    """Negotiated protocol version, e.g. `TLSv1.3`."""
+    cipher_suite: str | None
+    """Negotiated cipher suite, e.g. `TLS13_AES_256_GCM_SHA384`."""
+    cert_not_after: int | None
+    """Server certificate expiry as a Unix timestamp in seconds."""
+    cert_expires_in_days: float | None
+    """Days until the server certificate expires; negative if already expired."""
+    error: str | None
+    timed_out: bool
+    @property
+    def total_ms(self) -> float | None:
+        """Connect plus handshake time, or None if the handshake did not complete."""
+        ...
+
+    def is_success(self) -> bool:
+        """Whether the handshake completed."""
+        ...
+
+    def to_ping_result(self) -> PingResult:
+        """Convert for use with stats and sinks.
+
+        A completed handshake is a `Pong` with `total_ms`, a timeout is a `Timeout`,
+        and other failures (including certificate verification) are `PingExited`.
+        """
+        ...
+
+    @override
+    def __repr__(self) -> str: ...
+
+@final
+class TlsPinger:
+    """Measures TCP connect plus TLS handshake time to `host:port` with SNI.
+
+    Reports the negotiated protocol version, cipher suite and server certificate expiry.
+    DNS resolution is not included in the timings.
+    """
+
+    def __new__(
+        cls,
+        target: TargetType,
+        port: int = 443,
+        server_name: str | None = None,
+        interval_ms: int = 1000,
+        timeout_ms: int = 5000,
+        verify: bool = True,
+        ipv4: bool = False,
+        ipv6: bool = False,
+        dns_resolve_timeout_ms: int | None = None,
+    ) -> TlsPinger:
+        """Create a TLS pinger.
+
+        Args:
+            server_name: SNI name, defaults to `target`.
+            verify: Verify the certificate chain and name; set to False to still report expiry of invalid certificates.
+        """
+        ...
+
+    def ping_once(self) -> TlsPingResult:
+        """Perform one handshake."""
+        ...
+
+    def ping_multiple(self, count: int = 4) -> list[TlsPingResult]:
+        """Perform `count` handshakes, `interval_ms` apart."""
+        ...
+
+    async def ping_once_async(self) -> TlsPingResult:
+        """Asynchronous version of `ping_once`."""
+        ...
+
+    async def ping_multiple_async(self, count: int = 4) -> list[TlsPingResult]:
+        """Asynchronous version of `ping_multiple`."""
+        ...
+
+    @override
+    def __repr__(self) -> str: ...
+
+DnsProtocol = Literal["udp", "tcp"]
+
+@final
+class DnsPingResult:
+    """Result of one DNS query."""
+
+    server: str
+    """Resolver address as `IP:port`."""
+    name: str
+    record_type: str
+    protocol: str
+    rcode: str | None
+    """Response code such as `NOERROR` or `NXDOMAIN`; None if no response was received."""
+    answer_count: int | None
+    addresses: list[str]
+    """A / AAAA addresses in the answer section."""
+    duration_ms: float
+    error: str | None
+    timed_out: bool
+    def is_success(self) -> bool:
+        """Whether a `NOERROR` response was received."""
+        ...
+
+    def to_ping_result(self) -> PingResult:
+        """Convert for use with stats and sinks.
+
+        Any response (including `NXDOMAIN` / `SERVFAIL`) is a `Pong` with `duration_ms`, a timeout is a
+        `Timeout`, and other failures are `PingExited`.
+        """
+        ...
+
+    @override
+    def __repr__(self) -> str: ...
+
+@final
+class DnsPinger:
+    """Measures DNS query latency against a specific resolver."""
+
+    def __new__(
+        cls,
+        name: TargetType,
+        server: str,
+        record_type: str = "A",
+        protocol: DnsProtocol = "udp",
+        interval_ms: int = 1000,
+        timeout_ms: int = 2000,
+    ) -> DnsPinger:
+        """Create a DNS pinger.
+
+        Args:
+            server: Resolver as `IP`, `IP:port` or `[IPv6]:port` (port defaults to 53).
+            record_type: `A`, `AAAA`, `MX`, ... or `TYPE<n>`.
+            protocol: `udp` (falls back to TCP on truncation) or `tcp`.
+        """
+        ...
+
+    def ping_once(self) -> DnsPingResult:
+        """Send one query."""
+        ...
+
+    def ping_multiple(self, count: int = 4) -> list[DnsPingResult]:
+        """Send `count` queries, `interval_ms` apart."""
+        ...
+
+    async def ping_once_async(self) -> DnsPingResult:
+        """Asynchronous version of `ping_once`."""
+        ...
+
+    async def ping_multiple_async(self, count: int = 4) -> list[DnsPingResult]:
+        """Asynchronous version of `ping_multiple`."""
+        ...
+
+    @override
+    def __repr__(self) -> str: ...
+
+@final
+class DnsResolver:
+    """Resolver used for DNS pre-resolution instead of the system resolver."""
+
+    def __new__(
+        cls,
+        nameservers: list[str] | None = None,
+        protocol: DnsProtocol = "udp",
+        search_domains: list[str] | None = None,
+        hosts: dict[str, str | list[str]] | None = None,
+    ) -> DnsResolver:
+        """Create a resolver.
+
+        Args:
+            nameservers: Servers as `IP`, `IP:port` or `[IPv6]:port` (port defaults to 53), tried in order.
+                The system resolver is used when empty.
+            protocol: `udp` (falls back to TCP on truncation) or `tcp`.
+            search_domains: Appended to names without a dot before trying the name as-is;
+                names with a dot are tried as-is first. Names ending in `.` are never expanded.
+            hosts: Overrides mapping names to one or more addresses, checked before any query.
+        """
+        ...
+
+    @property
+    def nameservers(self) -> list[str]: ...
+    @property
+    def protocol(self) -> str: ...
+    @property
+    def search_domains(self) -> list[str]: ...
+    @property
+    def hosts(self) -> dict[str, list[str]]: ...
+    @override
+    def __repr__(self) -> str: ...
+
+@final
+class Recorder:
+    """Appends stream results to a JSON Lines or CSV file, with optional rotation."""
+
+    def __new__(
+        cls,
+        path: str | PathLike[str],
+        format: Literal["jsonl", "ndjson", "csv"] = "jsonl",
+        max_bytes: int | None = None,
+        rotate_interval_ms: int | None = None,
+    ) -> Recorder: ...
+    def write(
+        self, result: PingResult | HttpPingResult | TlsPingResult | DnsPingResult, target: str = ""
+    ) -> None:
+        """Write a single result manually; probe results are converted with `to_ping_result()`."""
+        ...
+
+    def flush(self) -> None:
+        """Flush buffered records, raising OSError if a previous write failed."""
+        ...
+
+    def close(self) -> None:
+        """Flush, sync and close the file; later results are ignored."""
+        ...
+
+    @property
+    def closed(self) -> bool:
+        """Whether the recorder has been closed."""
+        ...
+
+    @property
+    def path(self) -> Path:
+        """Path of the current record file."""
+        ...
+
+    def __enter__(self) -> Recorder: ...
+    def __exit__(
+        self,
+        exc_type: type[BaseException] | None,
+        exc_value: BaseException | None,
+        traceback: TracebackType | None,
+    ) -> bool: ...
+    @override
+    def __repr__(self) -> str: ...
+
+@overload
+def ping_once(
+    target: TargetType,
+    timeout_ms: int = 1000,
+    interface: str | None = None,
+    ipv4: bool = False,
+    ipv6: bool = False,
+    dns_pre_resolve: bool = True,
+    dns_resolve_timeout_ms: int | None = None,
+    dns_resolver: DnsResolver | None = None,
+    resolve_mode: Literal["first", "happy_eyeballs"] = "first",
+) -> PingResult:
+    """Execute a single ping operation synchronously.
+
+    `resolve_mode="happy_eyeballs"` prefers IPv6 with an IPv4 fallback (RFC 8305). With `resolve_mode="all"`,
+    every resolved address is pinged concurrently and a `SweepResult` with one entry per address is returned.
+    """
+    ...
+
+@overload
+def ping_once(
+    target: TargetType,
+    timeout_ms: int = 1000,
+    interface: str | None = None,
+    ipv4: bool = False,
+    ipv6: bool = False,
+    dns_pre_resolve: bool = True,
+    dns_resolve_timeout_ms: int | None = None,
+    dns_resolver: DnsResolver | None = None,
+    *,
+    resolve_mode: Literal["all"],
+) -> SweepResult:
+    ...
+
+@overload
+async def ping_once_async(
+    target: TargetType,
+    timeout_ms: int = 1000,
+    interface: str | None = None,
+    ipv4: bool = False,
+    ipv6: bool = False,
+    dns_pre_resolve: bool = True,
+    dns_resolve_timeout_ms: int | None = None,
+    dns_resolver: DnsResolver | None = None,
+    resolve_mode: Literal["first", "happy_eyeballs"] = "first",
+) -> PingResult:
+    """Execute a single ping operation asynchronously.
+
+    `resolve_mode="happy_eyeballs"` prefers IPv6 with an IPv4 fallback (RFC 8305). With `resolve_mode="all"`,
+    every resolved address is pinged concurrently and a `SweepResult` with one entry per address is returned.
+    """
+    ...
+
+@overload
+async def ping_once_async(
+    target: TargetType,
+    timeout_ms: int = 1000,
+    interface: str | None = None,
+    ipv4: bool = False,
+    ipv6: bool = False,
+    dns_pre_resolve: bool = True,
+    dns_resolve_timeout_ms: int | None = None,
+    dns_resolver: DnsResolver | None = None,
+    *,
+    resolve_mode: Literal["all"],
+) -> SweepResult:
+    ...
+
+@overload
+def ping_multiple(
+    target: TargetType,
+    count: int = 4,
     interval_ms: int = 1000,
     timeout_ms: int | None = None,
     interface: str | None = None,
@@ -259,10 +1358,34 @@ def ping_multiple(
     ipv6: bool = False,
     dns_pre_resolve: bool = True,
     dns_resolve_timeout_ms: int | None = None,
+    dns_resolver: DnsResolver | None = None,
+    resolve_mode: Literal["first", "happy_eyeballs"] = "first",
 ) -> list[PingResult]:
-    """Execute multiple ping operations synchronously."""
+    """Execute multiple ping operations synchronously.
+
+    `resolve_mode="happy_eyeballs"` prefers IPv6 with an IPv4 fallback (RFC 8305). With `resolve_mode="all"`,
+    every resolved address is pinged concurrently and a `SweepResult` with one entry per address is returned.
+    """
     ...
 
+@overload
+def ping_multiple(
+    target: TargetType,
+    count: int = 4,
+    interval_ms: int = 1000,
+    timeout_ms: int | None = None,
+    interface: str | None = None,
+    ipv4: bool = False,
+    ipv6: bool = False,
+    dns_pre_resolve: bool = True,
+    dns_resolve_timeout_ms: int | None = None,
+    dns_resolver: DnsResolver | None = None,
+    *,
+    resolve_mode: Literal["all"],
+) -> SweepResult:
+    ...
+
+@overload
 async def ping_multiple_async(
     target: TargetType,
     count: int = 4,
@@ -273,8 +1396,31 @@ async def ping_multiple_async(
     ipv6: bool = False,
     dns_pre_resolve: bool = True,
     dns_resolve_timeout_ms: int | None = None,
+    dns_resolver: DnsResolver | None = None,
+    resolve_mode: Literal["first", "happy_eyeballs"] = "first",
 ) -> list[PingResult]:
-    """Execute multiple ping operations asynchronously."""
+    """Execute multiple ping operations asynchronously.
+
+    `resolve_mode="happy_eyeballs"` prefers IPv6 with an IPv4 fallback (RFC 8305). With `resolve_mode="all"`,
+    every resolved address is pinged concurrently and a `SweepResult` with one entry per address is returned.
+    """
+    ...
+
+@overload
+async def ping_multiple_async(
+    target: TargetType,
+    count: int = 4,
+    interval_ms: int = 1000,
+    timeout_ms: int | None = None,
+    interface: str | None = None,
+    ipv4: bool = False,
+    ipv6: bool = False,
+    dns_pre_resolve: bool = True,
+    dns_resolve_timeout_ms: int | None = None,
+    dns_resolver: DnsResolver | None = None,
+    *,
+    resolve_mode: Literal["all"],
+) -> SweepResult:
     ...
 
 def create_ping_stream(
@@ -286,6 +1432,229 @@ def create_ping_stream(
     count: int | None = None,
     dns_pre_resolve: bool = True,
     dns_resolve_timeout_ms: int | None = None,
+    dns_resolver: DnsResolver | None = None,
+    resolve_mode: Literal["first", "happy_eyeballs"] = "first",
+    restart_on_exit: bool = False,
+    restart_backoff_ms: int = 1000,
+    max_restarts: int | None = None,
+    start_jitter_ms: int = 0,
+    interval_jitter_pct: float = 0.0,
+    jitter_seed: int | None = None,
+    size: int | None = None,
+    dont_fragment: bool = False,
 ) -> PingStream:
-    """Create a non-blocking ping stream."""
+    """Create a non-blocking ping stream.
+
+    `size` sets the ICMP payload size in bytes (16 to 65500) and `dont_fragment` sets the Don't Fragment
+    flag. On Windows either option runs `ping.exe` instead of the native API, with a fixed 1 second interval.
+    Probes that fail because of them (for example "Frag needed and DF set") are reported as `PingResult.Unknown`.
+    """
+    ...
+
+def start_metrics_server(
+    port: int,
+    targets: list[TargetType],
+    interval_ms: int = 1000,
+    host: str = "0.0.0.0",
+    interface: str | None = None,
+    ipv4: bool = False,
+    ipv6: bool = False,
+    dns_resolve_timeout_ms: int | None = None,
+) -> MetricsServer:
+    """Start an embedded Prometheus exporter that continuously pings the targets.
+
+    Metrics are served at `http://<host>:<port>/metrics`. Use port 0 to let the OS pick a free port.
+    """
+    ...
+
+def set_global_rate_limit(spawns_per_second: float | None, burst: int | None = None) -> None:
+    """Limit how many ping processes may be started per second across the whole process.
+
+    Every `Pinger`, `AsyncPinger`, stream, `Monitor` and metrics server takes one token from a
+    shared token bucket before starting a ping process and waits (without holding the GIL) when none
+    is available. `spawns_per_second` counts process spawns per second, not packets: once started, each ping process
+    keeps sending at its own interval. Pass `None` to remove the limit. `burst` defaults to `spawns_per_second` (at least 1).
+    Cancelling an async call while it waits gives its token back.
+    """
+    ...
+
+def get_rate_limit_stats(reset: bool = False) -> dict[str, Any]:
+    """Return the current rate limit and how often callers were throttled.
+
+    Keys: `spawns_per_second`, `burst`, `acquired`, `throttled`, `throttled_time_ms`.
+    Pass `reset=True` to zero the counters after reading them.
+    """
+    ...
+
+def set_max_concurrent_pings(max_concurrent: int | None, queue_timeout_ms: int | None = None) -> None:
+    """Cap how many ping sessions (child `ping` processes) may run at the same time.
+
+    Once the cap is reached, new pings wait in a queue until a session ends. With `queue_timeout_ms`,
+    a ping that waited longer raises `TimeoutError`. Pass `None` to remove the cap.
+    """
+    ...
+
+def get_max_concurrent_pings() -> int | None:
+    """Return the current cap on concurrent ping sessions, or `None` if there is none."""
+    ...
+
+def get_inflight_pings() -> int:
+    """Return how many ping sessions are currently running."""
+    ...
+
+def set_dns_cache(max_entries: int = 1024, max_age_ms: int = 30000, negative_max_age_ms: int = 5000) -> None:
+    """Enable or configure the DNS cache shared by all pings' hostname pre-resolution.
+
+    The cache is off until this is called; calling it with the defaults enables it.
+    Answers are cached for their record TTL, capped at `max_age_ms` (the system resolver reports no TTL,
+    so its answers are kept for `max_age_ms`). Negative answers (no such name, no addresses) are cached
+    for at most `negative_max_age_ms`. Timeouts and transient failures (server errors, network down)
+    are never cached.
+    `max_entries=0` disables the cache. Changing the settings clears the cache.
+    """
+    ...
+
+def clear_dns_cache() -> None:
+    """Drop every cached DNS answer so the next ping resolves its hostname again."""
+    ...
+
+def get_dns_cache_stats(reset: bool = False) -> dict[str, Any]:
+    """Return the DNS cache settings and how often lookups were served from it.
+
+    Keys: `max_entries`, `max_age_ms`, `negative_max_age_ms`, `entries`, `hits`, `misses`.
+    Pass `reset=True` to zero the counters after reading them.
+    """
+    ...
+
+SweepTarget = TargetType | IPv4Network | IPv6Network | list[TargetType | IPv4Network | IPv6Network]
+
+def sweep(
+    target: SweepTarget,
+    count: int = 1,
+    timeout_ms: int = 1000,
+    interface: str | None = None,
+    ipv4: bool = False,
+    ipv6: bool = False,
+    concurrency: int = 256,
+) -> SweepResult:
+    """Probe many addresses concurrently and report which ones are alive.
+
+    `target` may be a single address or hostname, an `IPv4Network`/`IPv6Network`, a CIDR string
+    such as `10.0.0.0/24`, a range such as `10.0.0.1-10.0.0.50` or `10.0.0.1-50`, or a list of these.
+    """
+    ...
+
+async def sweep_async(
+    target: SweepTarget,
+    count: int = 1,
+    timeout_ms: int = 1000,
+    interface: str | None = None,
+    ipv4: bool = False,
+    ipv6: bool = False,
+    concurrency: int = 256,
+) -> SweepResult:
+    """Asynchronous version of `sweep`."""
+    ...
+
+def discover(
+    network: SweepTarget,
+    timeout_ms: int = 1000,
+    retries: int = 1,
+    interface: str | None = None,
+    ipv4: bool = False,
+    ipv6: bool = False,
+    concurrency: int = 256,
+) -> list[HostResult]:
+    """Return only the addresses in `network` that reply, with the RTT of their first reply.
+
+    Each address gets one probe plus up to `retries` more if it does not answer, and probing
+    stops at the first reply (like `fping -a -g`). `network` accepts the same forms as `sweep`.
+    """
+    ...
+
+async def discover_async(
+    network: SweepTarget,
+    timeout_ms: int = 1000,
+    retries: int = 1,
+    interface: str | None = None,
+    ipv4: bool = False,
+    ipv6: bool = False,
+    concurrency: int = 256,
+) -> list[HostResult]:
+    """Asynchronous version of `discover`."""
+    ...
+
+def traceroute(
+    target: TargetType,
+    max_hops: int = 30,
+    probes_per_hop: int = 3,
+    timeout_ms: int = 1000,
+    interface: str | None = None,
+    ipv4: bool = False,
+    ipv6: bool = False,
+    dns_resolve_timeout_ms: int | None = None,
+) -> list[TracerouteHop]:
+    """Trace the route to `target`, stopping when it answers or after `max_hops` hops.
+
+    Sends ICMP echo requests with increasing TTL over native sockets, the probes of one hop in
+    parallel; RTTs are measured from when each packet is sent. Linux and macOS use unprivileged
+    ICMP sockets when allowed (on Linux, `net.ipv4.ping_group_range`) and fall back to raw
+    sockets, which need root or `CAP_NET_RAW`. `interface` is an interface name or a local
+    address (Windows only accepts an address). UDP and TCP probes are not supported.
+
+    Raises:
+        ValueError: If an argument is invalid.
+        RuntimeError: If the target cannot be resolved or the ICMP socket cannot be opened.
+        TimeoutError: If waiting for a free ping slot times out.
+    """
+    ...
+
+async def traceroute_async(
+    target: TargetType,
+    max_hops: int = 30,
+    probes_per_hop: int = 3,
+    timeout_ms: int = 1000,
+    interface: str | None = None,
+    ipv4: bool = False,
+    ipv6: bool = False,
+    dns_resolve_timeout_ms: int | None = None,
+) -> list[TracerouteHop]:
+    """Asynchronous version of `traceroute`."""
+    ...
+
+def discover_path_mtu(
+    target: TargetType,
+    min: int = 576,
+    max: int = 9000,
+    timeout_ms: int = 1000,
+    retries: int = 1,
+    interface: str | None = None,
+    ipv4: bool = False,
+    ipv6: bool = False,
+    dns_resolve_timeout_ms: int | None = None,
+) -> PathMtuResult:
+    """Find the path MTU to `target` by binary-searching the packet size with don't-fragment set.
+
+    `min` and `max` are packet sizes including the IP and ICMP headers. Sizes that get no answer are
+    retried up to `retries` times; an MTU reported in a "fragmentation needed" message narrows the search.
+
+    Raises:
+        ValueError: If an argument is invalid.
+        RuntimeError: If the target cannot be resolved or the ping process fails to start.
+        TimeoutError: If waiting for a free ping slot times out.
+    """
+    ...
+
+async def discover_path_mtu_async(
+    target: TargetType,
+    min: int = 576,
+    max: int = 9000,
+    timeout_ms: int = 1000,
+    retries: int = 1,
+    interface: str | None = None,
+    ipv4: bool = False,
+    ipv6: bool = False,
+    dns_resolve_timeout_ms: int | None = None,
+) -> PathMtuResult:
+    """Asynchronous version of `discover_path_mtu`."""
     ...
@@ -24,6 +24,12 @@ class PingExitedResult(TypedDict):
     stderr: str
 
 
-PingResultDict = PongResult | TimeoutResult | UnknownResult | PingExitedResult
+class PortUnreachableResult(TypedDict):
+    type: Literal["PortUnreachable"]
+    duration_ms: float
+    line: str
+
+
+PingResultDict = PongResult | TimeoutResult | UnknownResult | PingExitedResult | PortUnreachableResult
 # 定义 IP 地址类型
 TargetType = str | IPv4Address | IPv6Address
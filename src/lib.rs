@@ -6,28 +6,62 @@
 //! as well as streaming interfaces for continuous ping operations.
 
 #![allow(clippy::too_many_arguments)] // 添加允许多参数的属性
+#![allow(clippy::fn_params_excessive_bools)] // Python 接口的布尔关键字参数
 
+mod metrics;
+mod monitor;
 mod protocols;
+mod sinks;
 mod types;
 mod utils;
 
+use protocols::icmp::pmtu::{run_pmtu, PmtuConfig};
+use protocols::icmp::resolve::ping_all;
+use protocols::icmp::sweep::{run_sweep, SweepConfig};
+use protocols::icmp::traceroute::{run_traceroute, TracerouteConfig};
 use pyo3::prelude::*;
 use pyo3::wrap_pyfunction;
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use types::options::{DnsPreResolveOptions, ResolveMode};
 
 // 重新导出公共类型
+pub use metrics::prometheus::MetricsServer;
+pub use monitor::Monitor;
+pub use protocols::dns::ping::DnsPinger;
+pub use protocols::dns::resolver::DnsResolver;
+pub use protocols::dns::result::DnsPingResult;
+pub use protocols::http::ping::HttpPinger;
+pub use protocols::http::result::HttpPingResult;
+pub use protocols::http::stream::HttpPingStream;
 pub use protocols::icmp::ping::async_ping::AsyncPinger;
 pub use protocols::icmp::ping::sync::Pinger;
+pub use protocols::icmp::pmtu::PathMtuResult;
 pub use protocols::icmp::stream::async_stream::AsyncPingStream;
+pub use protocols::icmp::stream::path::{AsyncPathStream, PathHopStats, PathReport};
 pub use protocols::icmp::stream::sync::PingStream;
+pub use protocols::icmp::sweep::{HostResult, SweepResult};
+pub use protocols::icmp::traceroute::TracerouteHop;
+pub use protocols::tls::ping::TlsPinger;
+pub use protocols::tls::result::TlsPingResult;
+pub use protocols::udp::ping::UdpPinger;
+pub use protocols::udp::stream::UdpPingStream;
+pub use sinks::influx::InfluxSink;
+#[cfg(feature = "otlp")]
+pub use sinks::otlp::OtlpJsonSink;
+pub use sinks::recorder::Recorder;
+pub use sinks::statsd::StatsdSink;
+pub use types::batch::PingResultBatch;
 pub use types::result::PingResult;
+pub use types::stats::PingStats;
 
 // =================== 模块级函数 ===================
 
 /// 创建非阻塞 ping 流
 #[pyfunction]
-#[pyo3(signature = (target, interval_ms=1000, interface=None, ipv4=false, ipv6=false, count=None, dns_pre_resolve=true, dns_resolve_timeout_ms=None))]
+#[pyo3(signature = (target, interval_ms=1000, interface=None, ipv4=false, ipv6=false, count=None, dns_pre_resolve=true, dns_resolve_timeout_ms=None, dns_resolver=None, resolve_mode="first", restart_on_exit=false, restart_backoff_ms=1000, max_restarts=None, start_jitter_ms=0, interval_jitter_pct=0.0, jitter_seed=None, size=None, dont_fragment=false))]
 fn create_ping_stream(
+    py: Python<'_>,
     target: &Bound<PyAny>,
     interval_ms: i64,
     interface: Option<String>,
@@ -36,9 +70,20 @@ fn create_ping_stream(
     count: Option<usize>,
     dns_pre_resolve: bool,
     dns_resolve_timeout_ms: Option<i64>,
+    dns_resolver: Option<DnsResolver>,
+    resolve_mode: &str,
+    restart_on_exit: bool,
+    restart_backoff_ms: i64,
+    max_restarts: Option<u32>,
+    start_jitter_ms: i64,
+    interval_jitter_pct: f64,
+    jitter_seed: Option<u64>,
+    size: Option<i64>,
+    dont_fragment: bool,
 ) -> PyResult<PingStream> {
     // 直接使用 PingStream 的构造函数
     PingStream::new(
+        py,
         target,
         interval_ms,
         interface,
@@ -47,17 +92,81 @@ fn create_ping_stream(
         count,
         dns_pre_resolve,
         dns_resolve_timeout_ms,
+        dns_resolver,
+        resolve_mode,
+        restart_on_exit,
+        restart_backoff_ms,
+        max_restarts,
+        start_jitter_ms,
+        interval_jitter_pct,
+        jitter_seed,
+        size,
+        dont_fragment,
     )
 }
 
+/// `ping_once` / `ping_multiple` 的返回值，`resolve_mode="all"` 时为每个地址的结果
+#[derive(IntoPyObject)]
+enum PingOutcome<T> {
+    /// 目标（或选中的单个地址）的结果
+    Target(T),
+    /// 每个地址的结果
+    Addresses(SweepResult),
+}
+
+/// 校验 `resolve_mode="all"` 的参数，`timeout_ms` 限制每个地址的探测次数
+fn prepare_all_addresses(
+    target: &Bound<PyAny>,
+    count: i32,
+    interval_ms: i64,
+    timeout_ms: Option<i64>,
+    interface: Option<String>,
+    ipv4: bool,
+    ipv6: bool,
+    dns_pre_resolve: bool,
+    dns_resolve_timeout_ms: Option<i64>,
+    dns_resolver: Option<DnsResolver>,
+) -> PyResult<(String, SweepConfig, DnsPreResolveOptions)> {
+    let target = utils::conversion::extract_target(target)?;
+    let count = utils::validation::validate_count(count, "count")?;
+    let interval_ms = utils::validation::validate_interval_ms(interval_ms, "interval_ms")?;
+    let timeout = utils::validation::validate_timeout_ms(timeout_ms, interval_ms, "timeout_ms")?;
+    let count = timeout.map_or(count, |timeout| {
+        let probes = timeout.as_millis() / u128::from(interval_ms);
+        count.min(usize::try_from(probes).unwrap_or(usize::MAX).max(1))
+    });
+    let dns_timeout = dns_resolve_timeout_ms
+        .map(|value| utils::validation::i64_to_u64_positive(value, "dns_resolve_timeout_ms").map(Duration::from_millis))
+        .transpose()?;
+
+    let config = SweepConfig {
+        count,
+        timeout: Duration::from_millis(interval_ms),
+        interface,
+        ipv4,
+        ipv6,
+        stop_on_reply: false,
+        concurrency: 1,
+    };
+    let dns_options = DnsPreResolveOptions {
+        enable: dns_pre_resolve,
+        timeout: dns_timeout,
+        resolver: dns_resolver.map(Arc::new),
+        mode: ResolveMode::All,
+    };
+    Ok((target, config, dns_options))
+}
+
 /// 执行单次 ping（同步版本）
 ///
 /// # 参数
 /// - `timeout_ms`: 等待响应的超时时间（毫秒），默认 1000ms
 ///   注意：内部实现中，这个值会被用作 `interval_ms` 传递给底层 ping 命令
+/// - `resolve_mode`: `first`（默认）、`happy_eyeballs`，或 `all`（并发 ping 每个地址，返回 `SweepResult`）
 #[pyfunction]
-#[pyo3(signature = (target, timeout_ms=1000, interface=None, ipv4=false, ipv6=false, dns_pre_resolve=true, dns_resolve_timeout_ms=None))]
+#[pyo3(signature = (target, timeout_ms=1000, interface=None, ipv4=false, ipv6=false, dns_pre_resolve=true, dns_resolve_timeout_ms=None, dns_resolver=None, resolve_mode="first"))]
 fn ping_once(
+    py: Python<'_>,
     target: &Bound<PyAny>,
     timeout_ms: i64,
     interface: Option<String>,
@@ -65,7 +174,27 @@ fn ping_once(
     ipv6: bool,
     dns_pre_resolve: bool,
     dns_resolve_timeout_ms: Option<i64>,
-) -> PyResult<PingResult> {
+    dns_resolver: Option<DnsResolver>,
+    resolve_mode: &str,
+) -> PyResult<PingOutcome<PingResult>> {
+    if ResolveMode::from_name(resolve_mode)? == ResolveMode::All {
+        let (target, config, dns_options) = prepare_all_addresses(
+            target,
+            1,
+            timeout_ms,
+            None,
+            interface,
+            ipv4,
+            ipv6,
+            dns_pre_resolve,
+            dns_resolve_timeout_ms,
+            dns_resolver,
+        )?;
+        let result =
+            py.detach(|| pyo3_async_runtimes::tokio::get_runtime().block_on(ping_all(&target, config, dns_options)));
+        return Ok(PingOutcome::Addresses(result));
+    }
+
     // 创建 Pinger 实例
     // 注意：这里将 timeout_ms 作为 interval_ms 传递，因为 ping_once 中会将其用作超时时间
     let pinger = Pinger::new(
@@ -76,10 +205,14 @@ fn ping_once(
         ipv6,
         dns_pre_resolve,
         dns_resolve_timeout_ms,
+        dns_resolver,
+        resolve_mode,
+        None,
+        false,
     )?;
 
     // 执行 ping_once
-    pinger.ping_once()
+    pinger.ping_once(py).map(PingOutcome::Target)
 }
 
 /// 执行单次 ping（异步版本）
@@ -87,8 +220,9 @@ fn ping_once(
 /// # 参数
 /// - `timeout_ms`: 等待响应的超时时间（毫秒），默认 1000ms
 ///   注意：内部实现中，这个值会被用作 `interval_ms` 传递给底层 ping 命令
+/// - `resolve_mode`: `first`（默认）、`happy_eyeballs`，或 `all`（并发 ping 每个地址，返回 `SweepResult`）
 #[pyfunction]
-#[pyo3(signature = (target, timeout_ms=1000, interface=None, ipv4=false, ipv6=false, dns_pre_resolve=true, dns_resolve_timeout_ms=None))]
+#[pyo3(signature = (target, timeout_ms=1000, interface=None, ipv4=false, ipv6=false, dns_pre_resolve=true, dns_resolve_timeout_ms=None, dns_resolver=None, resolve_mode="first"))]
 fn ping_once_async<'py>(
     py: Python<'py>,
     target: &Bound<PyAny>,
@@ -98,7 +232,27 @@ fn ping_once_async<'py>(
     ipv6: bool,
     dns_pre_resolve: bool,
     dns_resolve_timeout_ms: Option<i64>,
+    dns_resolver: Option<DnsResolver>,
+    resolve_mode: &str,
 ) -> PyResult<Bound<'py, PyAny>> {
+    if ResolveMode::from_name(resolve_mode)? == ResolveMode::All {
+        let (target, config, dns_options) = prepare_all_addresses(
+            target,
+            1,
+            timeout_ms,
+            None,
+            interface,
+            ipv4,
+            ipv6,
+            dns_pre_resolve,
+            dns_resolve_timeout_ms,
+            dns_resolver,
+        )?;
+        return pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            Ok(ping_all(&target, config, dns_options).await)
+        });
+    }
+
     // 创建 AsyncPinger 实例
     // 注意：这里将 timeout_ms 作为 interval_ms 传递，因为 ping_once 中会将其用作超时时间
     let pinger = AsyncPinger::new(
@@ -109,6 +263,10 @@ fn ping_once_async<'py>(
         ipv6,
         dns_pre_resolve,
         dns_resolve_timeout_ms,
+        dns_resolver,
+        resolve_mode,
+        None,
+        false,
     )?;
 
     // 执行异步 ping_once
@@ -116,9 +274,13 @@ fn ping_once_async<'py>(
 }
 
 /// 执行多次 ping（同步版本）
+///
+/// `resolve_mode="all"` 时并发 ping 每个地址，每个地址发送 `count` 次（`timeout_ms` 内最多发送的次数），
+/// 返回 `SweepResult`。
 #[pyfunction]
-#[pyo3(signature = (target, count=4, interval_ms=1000, timeout_ms=None, interface=None, ipv4=false, ipv6=false, dns_pre_resolve=true, dns_resolve_timeout_ms=None))]
+#[pyo3(signature = (target, count=4, interval_ms=1000, timeout_ms=None, interface=None, ipv4=false, ipv6=false, dns_pre_resolve=true, dns_resolve_timeout_ms=None, dns_resolver=None, resolve_mode="first"))]
 fn ping_multiple(
+    py: Python<'_>,
     target: &Bound<PyAny>,
     count: i32,
     interval_ms: i64,
@@ -128,7 +290,27 @@ fn ping_multiple(
     ipv6: bool,
     dns_pre_resolve: bool,
     dns_resolve_timeout_ms: Option<i64>,
-) -> PyResult<Vec<PingResult>> {
+    dns_resolver: Option<DnsResolver>,
+    resolve_mode: &str,
+) -> PyResult<PingOutcome<Vec<PingResult>>> {
+    if ResolveMode::from_name(resolve_mode)? == ResolveMode::All {
+        let (target, config, dns_options) = prepare_all_addresses(
+            target,
+            count,
+            interval_ms,
+            timeout_ms,
+            interface,
+            ipv4,
+            ipv6,
+            dns_pre_resolve,
+            dns_resolve_timeout_ms,
+            dns_resolver,
+        )?;
+        let result =
+            py.detach(|| pyo3_async_runtimes::tokio::get_runtime().block_on(ping_all(&target, config, dns_options)));
+        return Ok(PingOutcome::Addresses(result));
+    }
+
     // 创建 Pinger 实例
     let pinger = Pinger::new(
         target,
@@ -138,15 +320,21 @@ fn ping_multiple(
         ipv6,
         dns_pre_resolve,
         dns_resolve_timeout_ms,
+        dns_resolver,
+        resolve_mode,
+        None,
+        false,
     )?;
 
     // 执行 ping_multiple
-    pinger.ping_multiple(count, timeout_ms)
+    pinger.ping_multiple(py, count, timeout_ms).map(PingOutcome::Target)
 }
 
 /// 执行多次 ping（异步版本）
+///
+/// `resolve_mode="all"` 时的行为与 `ping_multiple` 相同。
 #[pyfunction]
-#[pyo3(signature = (target, count=4, interval_ms=1000, timeout_ms=None, interface=None, ipv4=false, ipv6=false, dns_pre_resolve=true, dns_resolve_timeout_ms=None))]
+#[pyo3(signature = (target, count=4, interval_ms=1000, timeout_ms=None, interface=None, ipv4=false, ipv6=false, dns_pre_resolve=true, dns_resolve_timeout_ms=None, dns_resolver=None, resolve_mode="first"))]
 #[allow(clippy::too_many_arguments)] // 添加允许多参数的属性
 fn ping_multiple_async<'py>(
     py: Python<'py>,
@@ -159,7 +347,27 @@ fn ping_multiple_async<'py>(
     ipv6: bool,
     dns_pre_resolve: bool,
     dns_resolve_timeout_ms: Option<i64>,
+    dns_resolver: Option<DnsResolver>,
+    resolve_mode: &str,
 ) -> PyResult<Bound<'py, PyAny>> {
+    if ResolveMode::from_name(resolve_mode)? == ResolveMode::All {
+        let (target, config, dns_options) = prepare_all_addresses(
+            target,
+            count,
+            interval_ms,
+            timeout_ms,
+            interface,
+            ipv4,
+            ipv6,
+            dns_pre_resolve,
+            dns_resolve_timeout_ms,
+            dns_resolver,
+        )?;
+        return pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            Ok(ping_all(&target, config, dns_options).await)
+        });
+    }
+
     // 创建 AsyncPinger 实例
     let pinger = AsyncPinger::new(
         target,
@@ -169,12 +377,531 @@ fn ping_multiple_async<'py>(
         ipv6,
         dns_pre_resolve,
         dns_resolve_timeout_ms,
+        dns_resolver,
+        resolve_mode,
+        None,
+        false,
     )?;
 
     // 执行异步 ping_multiple
     pinger.ping_multiple(py, count, timeout_ms)
 }
 
+/// 校验扫描参数并展开目标
+fn prepare_sweep(
+    target: &Bound<PyAny>,
+    count: i32,
+    timeout_ms: i64,
+    interface: Option<String>,
+    ipv4: bool,
+    ipv6: bool,
+    concurrency: usize,
+    stop_on_reply: bool,
+) -> PyResult<(Vec<String>, SweepConfig)> {
+    let targets = utils::conversion::extract_targets(target)?;
+    let count = utils::validation::validate_count(count, "count")?;
+    let timeout_ms = utils::validation::validate_interval_ms(timeout_ms, "timeout_ms")?;
+    if concurrency == 0 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "concurrency must be a positive integer",
+        ));
+    }
+    let config = SweepConfig {
+        count,
+        timeout: Duration::from_millis(timeout_ms),
+        interface,
+        ipv4,
+        ipv6,
+        stop_on_reply,
+        concurrency,
+    };
+    Ok((targets, config))
+}
+
+/// 扫描多个目标（同步版本）
+///
+/// `target` 可以是单个地址或主机名、`IPv4Network` / `IPv6Network`、CIDR 字符串（`10.0.0.0/24`）、
+/// 地址范围（`10.0.0.1-10.0.0.50`），或由它们组成的列表。每个地址并发发送 `count` 次探测，
+/// 返回每个地址是否存活。
+///
+/// # Errors
+/// - `PyValueError`: If a target is invalid or expands to too many addresses, or `count` / `timeout_ms` /
+///   `concurrency` is invalid
+/// - `PyTypeError`: If a target cannot be converted to a string
+#[pyfunction]
+#[pyo3(signature = (target, count=1, timeout_ms=1000, interface=None, ipv4=false, ipv6=false, concurrency=256))]
+fn sweep(
+    py: Python<'_>,
+    target: &Bound<PyAny>,
+    count: i32,
+    timeout_ms: i64,
+    interface: Option<String>,
+    ipv4: bool,
+    ipv6: bool,
+    concurrency: usize,
+) -> PyResult<SweepResult> {
+    let (targets, config) = prepare_sweep(target, count, timeout_ms, interface, ipv4, ipv6, concurrency, false)?;
+    Ok(py.detach(|| pyo3_async_runtimes::tokio::get_runtime().block_on(run_sweep(targets, config))))
+}
+
+/// 扫描多个目标（异步版本）
+///
+/// 参数与 `sweep` 相同。
+///
+/// # Errors
+/// - `PyValueError`: If a target is invalid or expands to too many addresses, or `count` / `timeout_ms` /
+///   `concurrency` is invalid
+/// - `PyTypeError`: If a target cannot be converted to a string
+#[pyfunction]
+#[pyo3(signature = (target, count=1, timeout_ms=1000, interface=None, ipv4=false, ipv6=false, concurrency=256))]
+fn sweep_async<'py>(
+    py: Python<'py>,
+    target: &Bound<PyAny>,
+    count: i32,
+    timeout_ms: i64,
+    interface: Option<String>,
+    ipv4: bool,
+    ipv6: bool,
+    concurrency: usize,
+) -> PyResult<Bound<'py, PyAny>> {
+    let (targets, config) = prepare_sweep(target, count, timeout_ms, interface, ipv4, ipv6, concurrency, false)?;
+    pyo3_async_runtimes::tokio::future_into_py(py, async move { Ok(run_sweep(targets, config).await) })
+}
+
+/// 发现网段中的存活主机（同步版本）
+///
+/// 每个地址先发送一次探测，没有响应时最多重试 `retries` 次，收到第一个响应后立即停止该地址，
+/// 只返回存活的地址及其延迟（类似 `fping -a -g`）。`network` 的格式与 `sweep` 的 `target` 相同。
+///
+/// # Errors
+/// - `PyValueError`: If the network is invalid or expands to too many addresses, or `timeout_ms` /
+///   `retries` / `concurrency` is invalid
+/// - `PyTypeError`: If a target cannot be converted to a string
+#[pyfunction]
+#[pyo3(signature = (network, timeout_ms=1000, retries=1, interface=None, ipv4=false, ipv6=false, concurrency=256))]
+fn discover(
+    py: Python<'_>,
+    network: &Bound<PyAny>,
+    timeout_ms: i64,
+    retries: u16,
+    interface: Option<String>,
+    ipv4: bool,
+    ipv6: bool,
+    concurrency: usize,
+) -> PyResult<Vec<HostResult>> {
+    let count = i32::from(retries) + 1;
+    let (targets, config) = prepare_sweep(network, count, timeout_ms, interface, ipv4, ipv6, concurrency, true)?;
+    let result = py.detach(|| pyo3_async_runtimes::tokio::get_runtime().block_on(run_sweep(targets, config)));
+    Ok(result.into_alive())
+}
+
+/// 发现网段中的存活主机（异步版本）
+///
+/// 参数与 `discover` 相同。
+///
+/// # Errors
+/// - `PyValueError`: If the network is invalid or expands to too many addresses, or `timeout_ms` /
+///   `retries` / `concurrency` is invalid
+/// - `PyTypeError`: If a target cannot be converted to a string
+#[pyfunction]
+#[pyo3(signature = (network, timeout_ms=1000, retries=1, interface=None, ipv4=false, ipv6=false, concurrency=256))]
+fn discover_async<'py>(
+    py: Python<'py>,
+    network: &Bound<PyAny>,
+    timeout_ms: i64,
+    retries: u16,
+    interface: Option<String>,
+    ipv4: bool,
+    ipv6: bool,
+    concurrency: usize,
+) -> PyResult<Bound<'py, PyAny>> {
+    let count = i32::from(retries) + 1;
+    let (targets, config) = prepare_sweep(network, count, timeout_ms, interface, ipv4, ipv6, concurrency, true)?;
+    pyo3_async_runtimes::tokio::future_into_py(py, async move { Ok(run_sweep(targets, config).await.into_alive()) })
+}
+
+/// 校验路由追踪参数
+fn prepare_traceroute(
+    max_hops: u8,
+    probes_per_hop: i32,
+    timeout_ms: i64,
+    interface: Option<String>,
+    ipv4: bool,
+    ipv6: bool,
+    dns_resolve_timeout_ms: Option<i64>,
+) -> PyResult<TracerouteConfig> {
+    if max_hops == 0 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "max_hops must be between 1 and 255",
+        ));
+    }
+    let dns_timeout = dns_resolve_timeout_ms
+        .map(|timeout_ms| utils::validation::i64_to_u64_positive(timeout_ms, "dns_resolve_timeout_ms"))
+        .transpose()?
+        .map(Duration::from_millis);
+    Ok(TracerouteConfig {
+        max_hops,
+        probes_per_hop: utils::validation::validate_count(probes_per_hop, "probes_per_hop")?,
+        timeout: Duration::from_millis(utils::validation::i64_to_u64_positive(timeout_ms, "timeout_ms")?),
+        interface,
+        ipv4,
+        ipv6,
+        dns_timeout,
+    })
+}
+
+/// 追踪到目标的路由（同步版本）
+///
+/// 通过原生 ICMP 套接字逐跳发送 TTL 递增的 echo 请求，到达目标或达到 `max_hops` 时停止。
+/// Linux / macOS 优先使用无特权的 ICMP 套接字，不可用时回退到原始套接字（需要 root 权限）；
+/// Windows 使用 `IcmpSendEcho2`。
+///
+/// # Errors
+/// - `PyValueError`: If `max_hops`, `probes_per_hop`, `timeout_ms` or `dns_resolve_timeout_ms` is invalid
+/// - `PyTypeError`: If the target cannot be converted to a string
+/// - `PyRuntimeError`: If the target cannot be resolved, or the ICMP socket cannot be opened or fails to send or receive
+/// - `PyTimeoutError`: If waiting for a free ping slot times out (see `set_max_concurrent_pings`)
+#[pyfunction]
+#[pyo3(signature = (target, max_hops=30, probes_per_hop=3, timeout_ms=1000, interface=None, ipv4=false, ipv6=false, dns_resolve_timeout_ms=None))]
+fn traceroute(
+    py: Python<'_>,
+    target: &Bound<PyAny>,
+    max_hops: u8,
+    probes_per_hop: i32,
+    timeout_ms: i64,
+    interface: Option<String>,
+    ipv4: bool,
+    ipv6: bool,
+    dns_resolve_timeout_ms: Option<i64>,
+) -> PyResult<Vec<TracerouteHop>> {
+    let target = utils::conversion::extract_target(target)?;
+    let config = prepare_traceroute(
+        max_hops,
+        probes_per_hop,
+        timeout_ms,
+        interface,
+        ipv4,
+        ipv6,
+        dns_resolve_timeout_ms,
+    )?;
+    Ok(py.detach(|| pyo3_async_runtimes::tokio::get_runtime().block_on(run_traceroute(target, config)))?)
+}
+
+/// 追踪到目标的路由（异步版本）
+///
+/// 参数与 `traceroute` 相同。
+///
+/// # Errors
+/// - `PyValueError`: If `max_hops`, `probes_per_hop`, `timeout_ms` or `dns_resolve_timeout_ms` is invalid
+/// - `PyTypeError`: If the target cannot be converted to a string
+/// - `PyRuntimeError`: If the target cannot be resolved, or the ICMP socket cannot be opened or fails to send or receive
+/// - `PyTimeoutError`: If waiting for a free ping slot times out (see `set_max_concurrent_pings`)
+#[pyfunction]
+#[pyo3(signature = (target, max_hops=30, probes_per_hop=3, timeout_ms=1000, interface=None, ipv4=false, ipv6=false, dns_resolve_timeout_ms=None))]
+fn traceroute_async<'py>(
+    py: Python<'py>,
+    target: &Bound<PyAny>,
+    max_hops: u8,
+    probes_per_hop: i32,
+    timeout_ms: i64,
+    interface: Option<String>,
+    ipv4: bool,
+    ipv6: bool,
+    dns_resolve_timeout_ms: Option<i64>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let target = utils::conversion::extract_target(target)?;
+    let config = prepare_traceroute(
+        max_hops,
+        probes_per_hop,
+        timeout_ms,
+        interface,
+        ipv4,
+        ipv6,
+        dns_resolve_timeout_ms,
+    )?;
+    pyo3_async_runtimes::tokio::future_into_py(py, async move { Ok(run_traceroute(target, config).await?) })
+}
+
+/// 校验路径 MTU 探测参数
+fn prepare_pmtu(
+    min: u32,
+    max: u32,
+    timeout_ms: i64,
+    retries: u32,
+    interface: Option<String>,
+    ipv4: bool,
+    ipv6: bool,
+    dns_resolve_timeout_ms: Option<i64>,
+) -> PyResult<PmtuConfig> {
+    if !(68..=65_535).contains(&min) || !(68..=65_535).contains(&max) || min > max {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "min and max must satisfy 68 <= min <= max <= 65535, got min={min}, max={max}"
+        )));
+    }
+    let dns_timeout = dns_resolve_timeout_ms
+        .map(|timeout_ms| utils::validation::i64_to_u64_positive(timeout_ms, "dns_resolve_timeout_ms"))
+        .transpose()?
+        .map(Duration::from_millis);
+    Ok(PmtuConfig {
+        min_mtu: min,
+        max_mtu: max,
+        timeout: Duration::from_millis(utils::validation::i64_to_u64_positive(timeout_ms, "timeout_ms")?),
+        retries,
+        interface,
+        ipv4,
+        ipv6,
+        dns_timeout,
+    })
+}
+
+/// 探测到目标的路径 MTU（同步版本）
+///
+/// 设置禁止分片后在 `[min, max]` 内二分查找能收到响应的最大包长（包含 IP 和 ICMP 头部），
+/// 没有响应的包长最多重试 `retries` 次。结果同时包含"需要分片"消息报告的 MTU（如果收到）。
+///
+/// # Errors
+/// - `PyValueError`: If `min` / `max` are out of range, or `timeout_ms` / `dns_resolve_timeout_ms` is not positive
+/// - `PyTypeError`: If the target cannot be converted to a string
+/// - `PyRuntimeError`: If the target cannot be resolved or the ping process fails to start
+/// - `PyTimeoutError`: If waiting for a free ping slot times out (see `set_max_concurrent_pings`)
+#[pyfunction]
+#[pyo3(signature = (target, min=576, max=9000, timeout_ms=1000, retries=1, interface=None, ipv4=false, ipv6=false, dns_resolve_timeout_ms=None))]
+fn discover_path_mtu(
+    py: Python<'_>,
+    target: &Bound<PyAny>,
+    min: u32,
+    max: u32,
+    timeout_ms: i64,
+    retries: u32,
+    interface: Option<String>,
+    ipv4: bool,
+    ipv6: bool,
+    dns_resolve_timeout_ms: Option<i64>,
+) -> PyResult<PathMtuResult> {
+    let target = utils::conversion::extract_target(target)?;
+    let config = prepare_pmtu(
+        min,
+        max,
+        timeout_ms,
+        retries,
+        interface,
+        ipv4,
+        ipv6,
+        dns_resolve_timeout_ms,
+    )?;
+    Ok(py.detach(|| pyo3_async_runtimes::tokio::get_runtime().block_on(run_pmtu(target, config)))?)
+}
+
+/// 探测到目标的路径 MTU（异步版本）
+///
+/// 参数与 `discover_path_mtu` 相同。
+///
+/// # Errors
+/// - `PyValueError`: If `min` / `max` are out of range, or `timeout_ms` / `dns_resolve_timeout_ms` is not positive
+/// - `PyTypeError`: If the target cannot be converted to a string
+/// - `PyRuntimeError`: If the target cannot be resolved or the ping process fails to start
+/// - `PyTimeoutError`: If waiting for a free ping slot times out (see `set_max_concurrent_pings`)
+#[pyfunction]
+#[pyo3(signature = (target, min=576, max=9000, timeout_ms=1000, retries=1, interface=None, ipv4=false, ipv6=false, dns_resolve_timeout_ms=None))]
+fn discover_path_mtu_async<'py>(
+    py: Python<'py>,
+    target: &Bound<PyAny>,
+    min: u32,
+    max: u32,
+    timeout_ms: i64,
+    retries: u32,
+    interface: Option<String>,
+    ipv4: bool,
+    ipv6: bool,
+    dns_resolve_timeout_ms: Option<i64>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let target = utils::conversion::extract_target(target)?;
+    let config = prepare_pmtu(
+        min,
+        max,
+        timeout_ms,
+        retries,
+        interface,
+        ipv4,
+        ipv6,
+        dns_resolve_timeout_ms,
+    )?;
+    pyo3_async_runtimes::tokio::future_into_py(py, async move { Ok(run_pmtu(target, config).await?) })
+}
+
+/// 启动内置的 Prometheus 指标服务器
+///
+/// 在后台为每个目标持续 ping，并在 `http://<host>:<port>/metrics` 上暴露
+/// RTT 直方图、丢包计数、`up` 状态和 DNS 失败计数。`port` 为 0 时由系统分配端口。
+#[pyfunction]
+#[pyo3(signature = (port, targets, interval_ms=1000, host="0.0.0.0", interface=None, ipv4=false, ipv6=false, dns_resolve_timeout_ms=None))]
+fn start_metrics_server(
+    port: u16,
+    targets: Vec<Bound<PyAny>>,
+    interval_ms: i64,
+    host: &str,
+    interface: Option<&str>,
+    ipv4: bool,
+    ipv6: bool,
+    dns_resolve_timeout_ms: Option<i64>,
+) -> PyResult<MetricsServer> {
+    let interval_ms = utils::validation::validate_interval_ms(interval_ms, "interval_ms")?;
+    let dns_timeout = dns_resolve_timeout_ms
+        .map(|value| utils::validation::i64_to_u64_positive(value, "dns_resolve_timeout_ms").map(Duration::from_millis))
+        .transpose()?;
+
+    let targets = targets
+        .into_iter()
+        .map(|target| {
+            Ok(protocols::icmp::session::TargetConfig {
+                target: utils::conversion::extract_target(&target)?,
+                interval_ms,
+                interface: interface.map(str::to_string),
+                ipv4,
+                ipv6,
+                dns_timeout,
+                resolver: None,
+                resolve_mode: ResolveMode::First,
+                // 与 ping 间隔相同的固定重启间隔
+                backoff: utils::backoff::BackoffPolicy::fixed(Duration::from_millis(interval_ms)),
+                jitter: utils::jitter::Jitter::default(),
+            })
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+
+    MetricsServer::start(host, port, targets)
+}
+
+/// 设置所有 ping 共享的全局速率限制（令牌桶）
+///
+/// 所有 `Pinger`、`AsyncPinger`、流、`Monitor` 和指标服务器在启动 ping 进程前都要取得一个令牌，
+/// 令牌不足时等待（同步调用等待时释放 GIL）。`spawns_per_second` 是每秒允许启动的 ping 进程数，而不是数据包数：
+/// 进程启动后按自己的间隔持续发送。`spawns_per_second` 为 `None` 时取消限制；`burst` 为 `None` 时等于 `spawns_per_second`（至少为 1）。
+///
+/// # Errors
+/// - `PyValueError`: If `spawns_per_second` is not a positive finite number or `burst` is zero
+#[pyfunction]
+#[pyo3(signature = (spawns_per_second, burst=None))]
+fn set_global_rate_limit(spawns_per_second: Option<f64>, burst: Option<u32>) -> PyResult<()> {
+    if let Some(rate) = spawns_per_second {
+        if !(rate > 0.0 && rate.is_finite()) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "spawns_per_second must be a positive number, got {rate}"
+            )));
+        }
+    }
+    if burst == Some(0) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "burst must be a positive integer",
+        ));
+    }
+    utils::rate_limit::set_limit(spawns_per_second, burst);
+    Ok(())
+}
+
+/// 获取全局速率限制的当前配置和累计限速统计
+///
+/// # Errors
+/// - `PyErr`: If a value cannot be converted to a Python object
+#[pyfunction]
+#[pyo3(signature = (reset=false))]
+fn get_rate_limit_stats(py: Python<'_>, reset: bool) -> PyResult<Bound<'_, pyo3::types::PyDict>> {
+    let stats = utils::rate_limit::stats();
+    if reset {
+        utils::rate_limit::reset_stats();
+    }
+    let dict = pyo3::types::PyDict::new(py);
+    dict.set_item("spawns_per_second", stats.spawns_per_second)?;
+    dict.set_item("burst", stats.burst)?;
+    dict.set_item("acquired", stats.acquired)?;
+    dict.set_item("throttled", stats.throttled)?;
+    dict.set_item("throttled_time_ms", stats.throttled_time.as_secs_f64() * 1000.0)?;
+    Ok(dict)
+}
+
+/// 设置同时运行的 ping 会话（ping 子进程）的最大数量
+///
+/// 达到上限后新的 ping 排队等待，直到有会话结束；设置 `queue_timeout_ms` 后排队超时抛出 `TimeoutError`。
+/// `max_concurrent` 为 `None` 时取消上限。新的上限只约束之后启动的会话。
+///
+/// # Errors
+/// - `PyValueError`: If `max_concurrent` is zero or `queue_timeout_ms` is not positive
+#[pyfunction]
+#[pyo3(signature = (max_concurrent, queue_timeout_ms=None))]
+fn set_max_concurrent_pings(max_concurrent: Option<usize>, queue_timeout_ms: Option<i64>) -> PyResult<()> {
+    if max_concurrent == Some(0) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "max_concurrent must be a positive integer",
+        ));
+    }
+    let queue_timeout = queue_timeout_ms
+        .map(|value| utils::validation::i64_to_u64_positive(value, "queue_timeout_ms").map(Duration::from_millis))
+        .transpose()?;
+    utils::concurrency::set_max_concurrent(max_concurrent, queue_timeout);
+    Ok(())
+}
+
+/// 获取同时运行的 ping 会话的最大数量，未设置上限时返回 `None`
+#[pyfunction]
+fn get_max_concurrent_pings() -> Option<usize> {
+    utils::concurrency::max_concurrent()
+}
+
+/// 获取正在运行的 ping 会话数
+#[pyfunction]
+fn get_inflight_pings() -> usize {
+    utils::concurrency::in_flight()
+}
+
+/// 启用或设置所有 ping 共享的 DNS 预解析缓存
+///
+/// 缓存默认关闭，调用本函数（使用默认参数即可）后启用。
+/// 肯定结果按记录的 TTL 缓存，最多 `max_age_ms`（系统解析器不提供 TTL，按 `max_age_ms` 缓存）；
+/// 否定结果（域名不存在或没有地址）最多缓存 `negative_max_age_ms`。`max_entries` 为 0 时禁用缓存。
+/// 修改设置会清空缓存。
+///
+/// # Errors
+/// - `PyValueError`: If `max_age_ms` or `negative_max_age_ms` is not positive
+#[pyfunction]
+#[pyo3(signature = (max_entries=1024, max_age_ms=30000, negative_max_age_ms=5000))]
+fn set_dns_cache(max_entries: usize, max_age_ms: i64, negative_max_age_ms: i64) -> PyResult<()> {
+    let max_age = utils::validation::i64_to_u64_positive(max_age_ms, "max_age_ms")?;
+    let negative_max_age = utils::validation::i64_to_u64_positive(negative_max_age_ms, "negative_max_age_ms")?;
+    utils::dns_cache::configure(
+        max_entries,
+        Duration::from_millis(max_age),
+        Duration::from_millis(negative_max_age),
+    );
+    Ok(())
+}
+
+/// 清空 DNS 预解析缓存，之后的 ping 会重新解析主机名
+#[pyfunction]
+fn clear_dns_cache() {
+    utils::dns_cache::clear();
+}
+
+/// 获取 DNS 预解析缓存的当前配置和累计命中统计
+///
+/// # Errors
+/// - `PyErr`: If a value cannot be converted to a Python object
+#[pyfunction]
+#[pyo3(signature = (reset=false))]
+fn get_dns_cache_stats(py: Python<'_>, reset: bool) -> PyResult<Bound<'_, pyo3::types::PyDict>> {
+    let stats = utils::dns_cache::stats();
+    if reset {
+        utils::dns_cache::reset_stats();
+    }
+    let dict = pyo3::types::PyDict::new(py);
+    dict.set_item("max_entries", stats.max_entries)?;
+    dict.set_item("max_age_ms", stats.max_age.as_millis())?;
+    dict.set_item("negative_max_age_ms", stats.negative_max_age.as_millis())?;
+    dict.set_item("entries", stats.entries)?;
+    dict.set_item("hits", stats.hits)?;
+    dict.set_item("misses", stats.misses)?;
+    Ok(dict)
+}
+
 /// Get the version of the ping-rs library
 ///
 /// Returns the version string, converting Rust semver format to Python-compatible format
@@ -200,10 +927,36 @@ fn _ping_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
 
     // 添加类
     m.add_class::<PingResult>()?;
+    m.add_class::<PingResultBatch>()?;
     m.add_class::<Pinger>()?;
     m.add_class::<AsyncPinger>()?;
     m.add_class::<PingStream>()?;
     m.add_class::<AsyncPingStream>()?;
+    m.add_class::<AsyncPathStream>()?;
+    m.add_class::<PathHopStats>()?;
+    m.add_class::<PathReport>()?;
+    m.add_class::<UdpPinger>()?;
+    m.add_class::<UdpPingStream>()?;
+    m.add_class::<HttpPinger>()?;
+    m.add_class::<HttpPingResult>()?;
+    m.add_class::<HttpPingStream>()?;
+    m.add_class::<TlsPinger>()?;
+    m.add_class::<TlsPingResult>()?;
+    m.add_class::<DnsPinger>()?;
+    m.add_class::<DnsResolver>()?;
+    m.add_class::<DnsPingResult>()?;
+    m.add_class::<Recorder>()?;
+    m.add_class::<InfluxSink>()?;
+    m.add_class::<StatsdSink>()?;
+    #[cfg(feature = "otlp")]
+    m.add_class::<OtlpJsonSink>()?;
+    m.add_class::<MetricsServer>()?;
+    m.add_class::<Monitor>()?;
+    m.add_class::<PingStats>()?;
+    m.add_class::<HostResult>()?;
+    m.add_class::<SweepResult>()?;
+    m.add_class::<TracerouteHop>()?;
+    m.add_class::<PathMtuResult>()?;
 
     // 添加函数
     m.add_function(wrap_pyfunction!(ping_once, m)?)?;
@@ -211,6 +964,23 @@ fn _ping_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(ping_multiple, m)?)?;
     m.add_function(wrap_pyfunction!(ping_multiple_async, m)?)?;
     m.add_function(wrap_pyfunction!(create_ping_stream, m)?)?;
+    m.add_function(wrap_pyfunction!(start_metrics_server, m)?)?;
+    m.add_function(wrap_pyfunction!(sweep, m)?)?;
+    m.add_function(wrap_pyfunction!(sweep_async, m)?)?;
+    m.add_function(wrap_pyfunction!(discover, m)?)?;
+    m.add_function(wrap_pyfunction!(discover_async, m)?)?;
+    m.add_function(wrap_pyfunction!(traceroute, m)?)?;
+    m.add_function(wrap_pyfunction!(traceroute_async, m)?)?;
+    m.add_function(wrap_pyfunction!(discover_path_mtu, m)?)?;
+    m.add_function(wrap_pyfunction!(discover_path_mtu_async, m)?)?;
+    m.add_function(wrap_pyfunction!(set_global_rate_limit, m)?)?;
+    m.add_function(wrap_pyfunction!(get_rate_limit_stats, m)?)?;
+    m.add_function(wrap_pyfunction!(set_max_concurrent_pings, m)?)?;
+    m.add_function(wrap_pyfunction!(get_max_concurrent_pings, m)?)?;
+    m.add_function(wrap_pyfunction!(get_inflight_pings, m)?)?;
+    m.add_function(wrap_pyfunction!(set_dns_cache, m)?)?;
+    m.add_function(wrap_pyfunction!(clear_dns_cache, m)?)?;
+    m.add_function(wrap_pyfunction!(get_dns_cache_stats, m)?)?;
 
     // 添加版本信息
     m.add("__version__", get_ping_rs_version())?;
@@ -0,0 +1,110 @@
+//! 持续监控的指标统计
+//!
+//! `MetricsRegistry` 按目标汇总 ping 结果，供 Prometheus 等导出器使用。
+
+pub mod prometheus;
+
+use crate::sinks::ResultSink;
+use crate::types::result::PingResult;
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard};
+
+/// RTT 直方图的桶上界（秒）
+pub const RTT_BUCKETS_SECONDS: &[f64] = &[
+    0.000_5, 0.001, 0.002_5, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0,
+];
+
+/// 单个目标的指标
+#[derive(Debug, Clone, Default)]
+pub struct TargetMetrics {
+    /// 已发送（收到 `Pong` 或 `Timeout`）的探测数
+    pub packets_sent: u64,
+    /// 丢失（`Timeout`）的探测数
+    pub packets_lost: u64,
+    /// 无法识别的输出行数
+    pub unknown_lines: u64,
+    /// ping 进程退出次数
+    pub process_exits: u64,
+    /// DNS 解析失败次数
+    pub dns_failures: u64,
+    /// 最近一次探测是否成功
+    pub up: bool,
+    /// 各 RTT 桶的累计计数（与 `RTT_BUCKETS_SECONDS` 对应，不含 `+Inf`）
+    pub rtt_bucket_counts: Vec<u64>,
+    /// RTT 样本数
+    pub rtt_count: u64,
+    /// RTT 总和（秒）
+    pub rtt_sum_seconds: f64,
+}
+
+impl TargetMetrics {
+    fn observe_rtt(&mut self, seconds: f64) {
+        if self.rtt_bucket_counts.len() != RTT_BUCKETS_SECONDS.len() {
+            self.rtt_bucket_counts = vec![0; RTT_BUCKETS_SECONDS.len()];
+        }
+        for (count, bound) in self.rtt_bucket_counts.iter_mut().zip(RTT_BUCKETS_SECONDS) {
+            if seconds <= *bound {
+                *count += 1;
+            }
+        }
+        self.rtt_count += 1;
+        self.rtt_sum_seconds += seconds;
+    }
+}
+
+/// 按目标汇总 ping 结果的指标注册表
+///
+/// 实现了 `ResultSink`，也可以直接挂载到流上。
+#[derive(Debug, Default)]
+pub struct MetricsRegistry {
+    targets: Mutex<BTreeMap<String, TargetMetrics>>,
+}
+
+impl MetricsRegistry {
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<String, TargetMetrics>> {
+        // 更新过程中不会 panic，锁中毒时继续使用内部状态
+        self.targets.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// 注册目标，使其在收到第一个结果前也出现在导出结果中
+    pub fn register(&self, target: &str) {
+        self.lock().entry(target.to_string()).or_default();
+    }
+
+    /// 记录一次 DNS 解析失败
+    pub fn record_dns_failure(&self, target: &str) {
+        let mut targets = self.lock();
+        let metrics = targets.entry(target.to_string()).or_default();
+        metrics.dns_failures += 1;
+        metrics.up = false;
+    }
+
+    /// 获取所有目标指标的快照
+    pub fn snapshot(&self) -> BTreeMap<String, TargetMetrics> {
+        self.lock().clone()
+    }
+}
+
+impl ResultSink for MetricsRegistry {
+    fn record(&self, target: &str, result: &PingResult) {
+        let mut targets = self.lock();
+        let metrics = targets.entry(target.to_string()).or_default();
+        match result {
+            PingResult::Pong { duration_ms, .. } => {
+                metrics.packets_sent += 1;
+                metrics.up = true;
+                metrics.observe_rtt(duration_ms / 1000.0);
+            }
+            PingResult::Timeout { .. } | PingResult::PortUnreachable { .. } => {
+                metrics.packets_sent += 1;
+                metrics.packets_lost += 1;
+                metrics.up = false;
+            }
+            PingResult::Unknown { .. } => metrics.unknown_lines += 1,
+            PingResult::PingExited { .. } => {
+                metrics.process_exits += 1;
+                metrics.up = false;
+            }
+        }
+    }
+}
@@ -0,0 +1,310 @@
+//! Prometheus 文本格式导出
+//!
+//! 内置一个最小的 HTTP 服务器，在 tokio 运行时中为每个目标运行 ping 流，
+//! 并在 `/metrics` 上以 Prometheus 文本格式暴露指标。
+
+use super::{MetricsRegistry, TargetMetrics, RTT_BUCKETS_SECONDS};
+use crate::protocols::icmp::session::{run_session, SessionEvent, TargetConfig};
+use crate::sinks::ResultSink;
+use pyo3::exceptions::PyOSError;
+use pyo3::prelude::*;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+/// 请求头的最大长度
+const MAX_REQUEST_BYTES: usize = 8192;
+
+/// 读取请求的超时时间
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 持续 ping 单个目标并更新指标
+///
+/// ping 进程退出或 DNS 解析失败后按 `config.backoff` 等待再重启。
+pub async fn run_target(config: TargetConfig, registry: Arc<MetricsRegistry>) {
+    let target = config.target.clone();
+    registry.register(&target);
+    run_session(config, |event| match event {
+        SessionEvent::Result(result) => registry.record(&target, &result),
+        SessionEvent::DnsFailure => registry.record_dns_failure(&target),
+        SessionEvent::Restarting => {}
+    })
+    .await;
+}
+
+/// 转义标签值
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// 输出一个简单指标（counter / gauge）
+fn write_metric(
+    out: &mut String,
+    name: &str,
+    kind: &str,
+    help: &str,
+    targets: &BTreeMap<String, TargetMetrics>,
+    value: impl Fn(&TargetMetrics) -> u64,
+) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+    for (target, metrics) in targets {
+        let _ = writeln!(out, "{name}{{target=\"{}\"}} {}", escape_label(target), value(metrics));
+    }
+}
+
+/// 以 Prometheus 文本格式渲染所有目标的指标
+pub fn render(registry: &MetricsRegistry) -> String {
+    let targets = registry.snapshot();
+    let mut out = String::new();
+
+    write_metric(
+        &mut out,
+        "ping_up",
+        "gauge",
+        "Whether the last probe to the target succeeded.",
+        &targets,
+        |m| u64::from(m.up),
+    );
+    write_metric(
+        &mut out,
+        "ping_packets_sent_total",
+        "counter",
+        "Probes sent to the target.",
+        &targets,
+        |m| m.packets_sent,
+    );
+    write_metric(
+        &mut out,
+        "ping_packets_lost_total",
+        "counter",
+        "Probes to the target that timed out.",
+        &targets,
+        |m| m.packets_lost,
+    );
+    write_metric(
+        &mut out,
+        "ping_dns_failures_total",
+        "counter",
+        "Failed hostname resolutions for the target.",
+        &targets,
+        |m| m.dns_failures,
+    );
+    write_metric(
+        &mut out,
+        "ping_process_exits_total",
+        "counter",
+        "Times the ping process for the target exited.",
+        &targets,
+        |m| m.process_exits,
+    );
+
+    let _ = writeln!(out, "# HELP ping_rtt_seconds Round-trip time of successful probes.");
+    let _ = writeln!(out, "# TYPE ping_rtt_seconds histogram");
+    for (target, metrics) in &targets {
+        let target = escape_label(target);
+        for (i, bound) in RTT_BUCKETS_SECONDS.iter().enumerate() {
+            let count = metrics.rtt_bucket_counts.get(i).copied().unwrap_or(0);
+            let _ = writeln!(
+                out,
+                "ping_rtt_seconds_bucket{{target=\"{target}\",le=\"{bound}\"}} {count}"
+            );
+        }
+        let _ = writeln!(
+            out,
+            "ping_rtt_seconds_bucket{{target=\"{target}\",le=\"+Inf\"}} {}",
+            metrics.rtt_count
+        );
+        let _ = writeln!(
+            out,
+            "ping_rtt_seconds_sum{{target=\"{target}\"}} {}",
+            metrics.rtt_sum_seconds
+        );
+        let _ = writeln!(
+            out,
+            "ping_rtt_seconds_count{{target=\"{target}\"}} {}",
+            metrics.rtt_count
+        );
+    }
+
+    out
+}
+
+/// 处理单个 HTTP 连接
+async fn handle_connection(mut stream: TcpStream, registry: &MetricsRegistry) -> std::io::Result<()> {
+    let mut request = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 1024];
+    let read_headers = async {
+        loop {
+            let n = stream.read(&mut chunk).await?;
+            request.extend_from_slice(chunk.get(..n).unwrap_or_default());
+            if n == 0 || request.len() > MAX_REQUEST_BYTES || request.windows(4).any(|w| w == b"\r\n\r\n") {
+                return Ok::<_, std::io::Error>(());
+            }
+        }
+    };
+    match tokio::time::timeout(REQUEST_TIMEOUT, read_headers).await {
+        Ok(read) => read?,
+        // 客户端长时间未发送完整请求，直接关闭连接
+        Err(_) => return Ok(()),
+    }
+
+    let request = String::from_utf8_lossy(&request);
+    let mut request_line = request.lines().next().unwrap_or_default().split_whitespace();
+    let (status, content_type, body) = match (request_line.next(), request_line.next()) {
+        (Some("GET"), Some(path)) if path == "/metrics" || path.starts_with("/metrics?") => {
+            ("200 OK", "text/plain; version=0.0.4; charset=utf-8", render(registry))
+        }
+        (Some("GET"), Some("/")) => (
+            "200 OK",
+            "text/plain; charset=utf-8",
+            "ping-rs exporter, metrics at /metrics\n".to_string(),
+        ),
+        (Some("GET"), _) => ("404 Not Found", "text/plain; charset=utf-8", "Not Found\n".to_string()),
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain; charset=utf-8",
+            "Method Not Allowed\n".to_string(),
+        ),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// 接受 HTTP 连接
+async fn serve(listener: TcpListener, registry: Arc<MetricsRegistry>) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                let registry = registry.clone();
+                tokio::spawn(async move {
+                    let _ = handle_connection(stream, &registry).await;
+                });
+            }
+            Err(_) => {
+                // 例如文件描述符耗尽，稍后重试，避免空转
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        }
+    }
+}
+
+/// 内置的 Prometheus 指标服务器
+///
+/// 由 `start_metrics_server()` 创建，调用 `stop()` 或对象被回收时停止所有 ping 进程和 HTTP 服务。
+#[pyclass(module = "ping_rs._ping_rs")]
+pub struct MetricsServer {
+    host: String,
+    port: u16,
+    registry: Arc<MetricsRegistry>,
+    tasks: Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl MetricsServer {
+    /// 绑定端口并在 tokio 运行时中启动 HTTP 服务和所有目标的 ping 流
+    ///
+    /// # Errors
+    /// - `PyOSError`: If the address cannot be bound
+    pub fn start(host: &str, port: u16, targets: Vec<TargetConfig>) -> PyResult<Self> {
+        let listener = std::net::TcpListener::bind((host, port))
+            .and_then(|listener| listener.set_nonblocking(true).map(|()| listener))
+            .map_err(|e| PyErr::new::<PyOSError, _>(format!("Failed to bind {host}:{port}: {e}")))?;
+        let port = listener
+            .local_addr()
+            .map_err(|e| PyErr::new::<PyOSError, _>(e.to_string()))?
+            .port();
+
+        let runtime = pyo3_async_runtimes::tokio::get_runtime();
+        let _guard = runtime.enter();
+        let listener = TcpListener::from_std(listener).map_err(|e| PyErr::new::<PyOSError, _>(e.to_string()))?;
+
+        let registry = Arc::new(MetricsRegistry::default());
+        let mut tasks = Vec::with_capacity(targets.len() + 1);
+        for config in targets {
+            tasks.push(runtime.spawn(run_target(config, registry.clone())));
+        }
+        tasks.push(runtime.spawn(serve(listener, registry.clone())));
+
+        Ok(Self {
+            host: host.to_string(),
+            port,
+            registry,
+            tasks: Mutex::new(tasks),
+        })
+    }
+
+    fn abort_all(&self) {
+        let mut tasks = self.tasks.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        for task in tasks.drain(..) {
+            task.abort();
+        }
+    }
+}
+
+#[pymethods]
+impl MetricsServer {
+    /// 实际监听的端口（传入 0 时为系统分配的端口）
+    #[getter]
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// 监听地址
+    #[getter]
+    pub fn host(&self) -> String {
+        self.host.clone()
+    }
+
+    /// 以 Prometheus 文本格式返回当前指标
+    pub fn render(&self) -> String {
+        render(&self.registry)
+    }
+
+    /// 服务器是否仍在运行
+    pub fn is_running(&self) -> bool {
+        let tasks = self.tasks.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        !tasks.is_empty() && tasks.iter().any(|task| !task.is_finished())
+    }
+
+    /// 停止 HTTP 服务和所有 ping 进程
+    pub fn stop(&self) {
+        self.abort_all();
+    }
+
+    /// 上下文管理器协议：返回自身
+    pub fn __enter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    /// 上下文管理器协议：停止服务器
+    #[pyo3(signature = (*_args))]
+    pub fn __exit__(&self, _args: &Bound<'_, pyo3::types::PyTuple>) -> bool {
+        self.stop();
+        false
+    }
+
+    /// Python `__repr__` method for string representation
+    pub fn __repr__(&self) -> String {
+        format!(
+            "MetricsServer(host='{}', port={}, running={})",
+            self.host,
+            self.port,
+            self.is_running()
+        )
+    }
+}
+
+impl Drop for MetricsServer {
+    fn drop(&mut self) {
+        self.abort_all();
+    }
+}
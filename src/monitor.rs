@@ -0,0 +1,344 @@
+//! 多目标持续监控
+//!
+//! `Monitor` 在 tokio 运行时中为每个目标运行一个长期的 ping 会话，
+//! ping 进程退出时按指数退避自动重启，并维护每个目标的最新状态和滑动窗口统计。
+
+use crate::protocols::dns::resolver::DnsResolver;
+use crate::protocols::icmp::session::{run_session, SessionEvent, TargetConfig};
+use crate::sinks::{dispatch_async, extract_sink, flush_all, ResultSink, StreamInfo};
+use crate::types::options::ResolveMode;
+use crate::types::result::PingResult;
+use crate::types::stats::{PingStats, RollingStats};
+use crate::utils::backoff::BackoffPolicy;
+use crate::utils::conversion::{extract_target, unix_timestamp};
+use crate::utils::jitter::{Jitter, SplitMix64};
+use crate::utils::validation::{i64_to_u64_positive, validate_interval_ms};
+use pyo3::exceptions::{PyKeyError, PyValueError};
+use pyo3::prelude::*;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// 加锁，锁中毒时继续使用内部状态（持锁期间不会 panic）
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// 单个目标的运行状态
+struct TargetState {
+    stats: RollingStats,
+    latest: Option<PingResult>,
+    last_update: Option<f64>,
+    /// 最近一次探测是否成功，尚未收到结果时为 `None`
+    up: Option<bool>,
+    restarts: u64,
+    dns_failures: u64,
+}
+
+impl TargetState {
+    fn handle(&mut self, event: &SessionEvent) {
+        match event {
+            SessionEvent::Result(result) => {
+                self.stats.push(result);
+                match result {
+                    PingResult::Pong { .. } => self.up = Some(true),
+                    PingResult::Timeout { .. } | PingResult::PingExited { .. } | PingResult::PortUnreachable { .. } => {
+                        self.up = Some(false);
+                    }
+                    PingResult::Unknown { .. } => {}
+                }
+                self.latest = Some(result.clone());
+                self.last_update = Some(unix_timestamp());
+            }
+            SessionEvent::DnsFailure => {
+                self.dns_failures += 1;
+                self.up = Some(false);
+            }
+            SessionEvent::Restarting => self.restarts += 1,
+        }
+    }
+
+    fn snapshot(&self, target: &str) -> PingStats {
+        let mut stats = PingStats::from_rolling(target, &self.stats);
+        stats.state = match self.up {
+            Some(true) => "up",
+            Some(false) => "down",
+            None => "unknown",
+        }
+        .to_string();
+        stats.latest.clone_from(&self.latest);
+        stats.last_update = self.last_update;
+        stats.restarts = self.restarts;
+        stats.dns_failures = self.dns_failures;
+        stats
+    }
+}
+
+/// 正在监控的目标
+struct MonitoredTarget {
+    info: StreamInfo,
+    state: Arc<Mutex<TargetState>>,
+    task: JoinHandle<()>,
+}
+
+/// 多目标持续监控服务
+///
+/// 每个目标有独立的 ping 间隔，ping 进程退出或 DNS 解析失败时按指数退避自动重启，
+/// 单个目标的故障不会影响其他目标。通过 `get_stats()` / `get_all_stats()` 查询最新状态和统计。
+#[pyclass(module = "ping_rs._ping_rs")]
+pub struct Monitor {
+    targets: Mutex<BTreeMap<String, MonitoredTarget>>,
+    sinks: Arc<Mutex<Vec<Arc<dyn ResultSink>>>>,
+    backoff: BackoffPolicy,
+    window: usize,
+    /// 为每个目标生成抖动种子
+    jitter_seeds: Mutex<SplitMix64>,
+}
+
+impl Monitor {
+    fn stop_all(&self) {
+        let mut targets = lock(&self.targets);
+        for (_, monitored) in std::mem::take(&mut *targets) {
+            monitored.task.abort();
+        }
+    }
+}
+
+#[pymethods]
+impl Monitor {
+    /// 创建新的 `Monitor` 实例
+    ///
+    /// # Arguments
+    /// - `window`: 滑动窗口统计的探测数（默认 100）
+    /// - `restart_backoff_ms`: ping 进程退出后首次重启前的等待时间（默认 1000ms）
+    /// - `max_backoff_ms`: 重启等待时间上限（默认 60000ms）
+    /// - `backoff_multiplier`: 连续重启时等待时间的倍数（默认 2.0）
+    /// - `jitter_seed`: 抖动随机数种子，指定时各目标按添加顺序得到可复现的抖动
+    ///
+    /// # Errors
+    /// - `PyValueError`: If `window` is zero, a backoff duration is not positive,
+    ///   or `backoff_multiplier` is less than 1
+    #[new]
+    #[pyo3(signature = (window=100, restart_backoff_ms=1000, max_backoff_ms=60000, backoff_multiplier=2.0, jitter_seed=None))]
+    pub fn new(
+        window: usize,
+        restart_backoff_ms: i64,
+        max_backoff_ms: i64,
+        backoff_multiplier: f64,
+        jitter_seed: Option<u64>,
+    ) -> PyResult<Self> {
+        if window == 0 {
+            return Err(PyErr::new::<PyValueError, _>("window must be a positive integer"));
+        }
+        if !(backoff_multiplier >= 1.0 && backoff_multiplier.is_finite()) {
+            return Err(PyErr::new::<PyValueError, _>(format!(
+                "backoff_multiplier must be at least 1.0, got {backoff_multiplier}"
+            )));
+        }
+        let initial = Duration::from_millis(i64_to_u64_positive(restart_backoff_ms, "restart_backoff_ms")?);
+        let max = Duration::from_millis(i64_to_u64_positive(max_backoff_ms, "max_backoff_ms")?);
+
+        Ok(Self {
+            targets: Mutex::new(BTreeMap::new()),
+            sinks: Arc::new(Mutex::new(Vec::new())),
+            backoff: BackoffPolicy {
+                initial,
+                max: max.max(initial),
+                multiplier: backoff_multiplier,
+            },
+            window,
+            jitter_seeds: Mutex::new(jitter_seed.map_or_else(SplitMix64::from_entropy, SplitMix64::new)),
+        })
+    }
+
+    /// 添加目标并立即开始监控
+    ///
+    /// `start_jitter_ms` 大于 0 时首次探测在 `[0, start_jitter_ms]` 内的随机偏移后开始；
+    /// `interval_jitter_pct` 在每次启动 ping 进程时将间隔随机调整 `±pct%`（取整到 100ms）。
+    /// `dns_resolver` 指定每次启动前解析主机名使用的 `DnsResolver`，默认使用系统解析器；
+    /// `resolve_mode` 为 `happy_eyeballs` 时 IPv6 优先、IPv4 回退（RFC 8305）。
+    ///
+    /// # Errors
+    /// - `PyValueError`: If the target is already monitored, or `interval_ms` / `dns_resolve_timeout_ms` /
+    ///   `resolve_mode` / a jitter argument is invalid
+    /// - `PyTypeError`: If the target cannot be converted to a string
+    #[pyo3(signature = (target, interval_ms=1000, interface=None, ipv4=false, ipv6=false, dns_resolve_timeout_ms=None, dns_resolver=None, resolve_mode="first", start_jitter_ms=0, interval_jitter_pct=0.0))]
+    pub fn add_target(
+        &self,
+        target: &Bound<PyAny>,
+        interval_ms: i64,
+        interface: Option<String>,
+        ipv4: bool,
+        ipv6: bool,
+        dns_resolve_timeout_ms: Option<i64>,
+        dns_resolver: Option<DnsResolver>,
+        resolve_mode: &str,
+        start_jitter_ms: i64,
+        interval_jitter_pct: f64,
+    ) -> PyResult<()> {
+        let target = extract_target(target)?;
+        let interval_ms = validate_interval_ms(interval_ms, "interval_ms")?;
+        let dns_timeout = dns_resolve_timeout_ms
+            .map(|value| i64_to_u64_positive(value, "dns_resolve_timeout_ms").map(Duration::from_millis))
+            .transpose()?;
+        let seed = lock(&self.jitter_seeds).next_u64();
+        let jitter = Jitter::new(start_jitter_ms, interval_jitter_pct, Some(seed))?;
+
+        let mut targets = lock(&self.targets);
+        if targets.contains_key(&target) {
+            return Err(PyErr::new::<PyValueError, _>(format!(
+                "Target '{target}' is already monitored"
+            )));
+        }
+
+        let info = StreamInfo::new(&target, interface.clone(), ipv4, ipv6);
+        for sink in lock(&self.sinks).iter() {
+            sink.attach(&info);
+        }
+
+        let state = Arc::new(Mutex::new(TargetState {
+            stats: RollingStats::new(self.window),
+            latest: None,
+            last_update: None,
+            up: None,
+            restarts: 0,
+            dns_failures: 0,
+        }));
+        let config = TargetConfig {
+            target: target.clone(),
+            interval_ms,
+            interface,
+            ipv4,
+            ipv6,
+            dns_timeout,
+            resolver: dns_resolver.map(Arc::new),
+            resolve_mode: ResolveMode::single_from_name(resolve_mode)?,
+            backoff: self.backoff,
+            jitter,
+        };
+
+        let task_state = state.clone();
+        let sinks = self.sinks.clone();
+        let task_target = target.clone();
+        // 会话回调是同步的，结果经通道交给同一任务中的分发循环，按顺序在阻塞线程池中写入输出端
+        let (results_tx, mut results_rx) = tokio::sync::mpsc::unbounded_channel();
+        let session = run_session(config, move |event| {
+            lock(&task_state).handle(&event);
+            if let SessionEvent::Result(result) = event {
+                let _ = results_tx.send(result);
+            }
+        });
+        let forward = async move {
+            while let Some(result) = results_rx.recv().await {
+                let sinks = lock(&sinks).clone();
+                dispatch_async(&sinks, &task_target, &result).await;
+            }
+        };
+        let task = pyo3_async_runtimes::tokio::get_runtime().spawn(async move {
+            tokio::join!(session, forward);
+        });
+
+        targets.insert(target, MonitoredTarget { info, state, task });
+        Ok(())
+    }
+
+    /// 停止监控目标，返回目标之前是否在监控中
+    ///
+    /// # Errors
+    /// - `PyTypeError`: If the target cannot be converted to a string
+    pub fn remove_target(&self, target: &Bound<PyAny>) -> PyResult<bool> {
+        let target = extract_target(target)?;
+        Ok(lock(&self.targets).remove(&target).is_some_and(|monitored| {
+            monitored.task.abort();
+            true
+        }))
+    }
+
+    /// 正在监控的目标列表
+    pub fn targets(&self) -> Vec<String> {
+        lock(&self.targets).keys().cloned().collect()
+    }
+
+    /// 获取单个目标的最新状态和统计
+    ///
+    /// # Errors
+    /// - `PyKeyError`: If the target is not monitored
+    /// - `PyTypeError`: If the target cannot be converted to a string
+    pub fn get_stats(&self, target: &Bound<PyAny>) -> PyResult<PingStats> {
+        let target = extract_target(target)?;
+        let targets = lock(&self.targets);
+        let monitored = targets
+            .get(&target)
+            .ok_or_else(|| PyErr::new::<PyKeyError, _>(target.clone()))?;
+        let stats = lock(&monitored.state).snapshot(&target);
+        Ok(stats)
+    }
+
+    /// 获取所有目标的最新状态和统计
+    pub fn get_all_stats(&self) -> BTreeMap<String, PingStats> {
+        lock(&self.targets)
+            .iter()
+            .map(|(target, monitored)| (target.clone(), lock(&monitored.state).snapshot(target)))
+            .collect()
+    }
+
+    /// 添加结果输出端，所有目标的结果都会写入该输出端
+    ///
+    /// # Errors
+    /// - `PyTypeError`: If the object is not a supported sink type
+    pub fn add_sink(&self, sink: &Bound<PyAny>) -> PyResult<()> {
+        let sink = extract_sink(sink)?;
+        for monitored in lock(&self.targets).values() {
+            sink.attach(&monitored.info);
+        }
+        lock(&self.sinks).push(sink);
+        Ok(())
+    }
+
+    /// 停止监控所有目标
+    pub fn stop(&self) {
+        self.stop_all();
+        flush_all(&lock(&self.sinks));
+    }
+
+    /// 是否有目标在监控中
+    pub fn is_running(&self) -> bool {
+        lock(&self.targets)
+            .values()
+            .any(|monitored| !monitored.task.is_finished())
+    }
+
+    /// Python `__len__` method
+    pub fn __len__(&self) -> usize {
+        lock(&self.targets).len()
+    }
+
+    /// Python `__contains__` method
+    pub fn __contains__(&self, target: &Bound<PyAny>) -> bool {
+        extract_target(target).is_ok_and(|target| lock(&self.targets).contains_key(&target))
+    }
+
+    /// 上下文管理器协议：返回自身
+    pub fn __enter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    /// 上下文管理器协议：停止监控
+    #[pyo3(signature = (*_args))]
+    pub fn __exit__(&self, _args: &Bound<'_, pyo3::types::PyTuple>) -> bool {
+        self.stop();
+        false
+    }
+
+    /// Python `__repr__` method for string representation
+    pub fn __repr__(&self) -> String {
+        format!("Monitor(targets={})", self.__len__())
+    }
+}
+
+impl Drop for Monitor {
+    fn drop(&mut self) {
+        self.stop_all();
+    }
+}
@@ -0,0 +1,40 @@
+//! 应用层探测（HTTP / TLS / DNS / UDP）共用的参数解析、计时和调度
+
+use crate::utils::validation::i64_to_u64_positive;
+use pyo3::prelude::*;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+/// 解析探测的超时时间和可选的 DNS 解析超时
+///
+/// # Errors
+/// - `PyValueError`: If `timeout_ms` or `dns_resolve_timeout_ms` is not positive
+pub fn parse_timeouts(timeout_ms: i64, dns_resolve_timeout_ms: Option<i64>) -> PyResult<(Duration, Option<Duration>)> {
+    let timeout = Duration::from_millis(i64_to_u64_positive(timeout_ms, "timeout_ms")?);
+    let dns_timeout = dns_resolve_timeout_ms
+        .map(|timeout_ms| i64_to_u64_positive(timeout_ms, "dns_resolve_timeout_ms"))
+        .transpose()?
+        .map(Duration::from_millis);
+    Ok((timeout, dns_timeout))
+}
+
+/// 从 `since` 到现在的毫秒数
+pub fn elapsed_ms(since: Instant) -> f64 {
+    since.elapsed().as_secs_f64() * 1000.0
+}
+
+/// 以 `interval` 为间隔运行 `count` 次探测，间隔从上一次探测开始时计算
+pub async fn run_probes<T, F, Fut>(count: usize, interval: Duration, mut probe: F) -> Vec<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = T>,
+{
+    let mut results = Vec::with_capacity(count);
+    let mut next_at = Instant::now();
+    for _ in 0..count {
+        tokio::time::sleep_until(next_at.into()).await;
+        next_at = Instant::now() + interval;
+        results.push(probe().await);
+    }
+    results
+}
@@ -0,0 +1,140 @@
+//! DNS 查询客户端
+//!
+//! 通过 UDP 或 TCP 向指定服务器发送单个查询。UDP 响应被截断（TC 标志）时自动改用 TCP 重试。
+
+use super::wire::{build_query, parse_response, RecordType, Response};
+use crate::utils::jitter::SplitMix64;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::io::{Error, ErrorKind};
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpStream, UdpSocket};
+
+/// UDP 响应的接收缓冲区大小
+const UDP_BUFFER_LEN: usize = 4096;
+
+/// DNS 传输协议
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DnsProtocol {
+    /// UDP，响应被截断时改用 TCP
+    #[default]
+    Udp,
+    /// TCP
+    Tcp,
+}
+
+impl DnsProtocol {
+    /// 从协议名解析
+    ///
+    /// # Errors
+    /// - `PyValueError`: If `name` is not `udp` or `tcp`
+    pub fn from_name(name: &str) -> PyResult<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "udp" => Ok(Self::Udp),
+            "tcp" => Ok(Self::Tcp),
+            _ => Err(PyErr::new::<PyValueError, _>(format!(
+                "protocol must be 'udp' or 'tcp', got '{name}'"
+            ))),
+        }
+    }
+
+    /// 协议名
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Udp => "udp",
+            Self::Tcp => "tcp",
+        }
+    }
+}
+
+/// 解析 DNS 服务器地址：`IP`、`IP:port` 或 `[IPv6]:port`，未指定端口时使用 53
+///
+/// # Errors
+/// - `PyValueError`: If the address is not an IP address with an optional port
+pub fn parse_server(server: &str) -> PyResult<SocketAddr> {
+    server
+        .parse::<SocketAddr>()
+        .or_else(|_| server.parse().map(|ip| SocketAddr::new(ip, 53)))
+        .map_err(|_| {
+            PyErr::new::<PyValueError, _>(format!(
+                "Invalid DNS server '{server}': expected an IP address with an optional port"
+            ))
+        })
+}
+
+/// 发送一个查询并等待响应，整个过程（包括 TCP 重试）受 `timeout` 限制
+///
+/// # Errors
+/// - The name cannot be encoded, the server cannot be reached, the request times out
+///   or the response is malformed
+pub async fn query(
+    server: SocketAddr,
+    protocol: DnsProtocol,
+    name: &str,
+    record_type: RecordType,
+    timeout: Duration,
+) -> std::io::Result<Response> {
+    let id = u16::try_from(SplitMix64::from_entropy().next_u64() & 0xffff).unwrap_or_default();
+    let message = build_query(id, name, record_type).map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+    let exchange = async {
+        if protocol == DnsProtocol::Udp {
+            let response = query_udp(server, id, &message).await?;
+            if !response.truncated {
+                return Ok(response);
+            }
+        }
+        query_tcp(server, id, &message).await
+    };
+    tokio::time::timeout(timeout, exchange).await.map_err(|_| {
+        Error::new(
+            ErrorKind::TimedOut,
+            format!("DNS query to {server} timed out after {}ms", timeout.as_millis()),
+        )
+    })?
+}
+
+async fn query_udp(server: SocketAddr, id: u16, message: &[u8]) -> std::io::Result<Response> {
+    let local: SocketAddr = if server.is_ipv4() {
+        (Ipv4Addr::UNSPECIFIED, 0).into()
+    } else {
+        (Ipv6Addr::UNSPECIFIED, 0).into()
+    };
+    let socket = UdpSocket::bind(local).await?;
+    socket.connect(server).await?;
+    socket.send(message).await?;
+
+    let mut buffer = vec![0u8; UDP_BUFFER_LEN];
+    loop {
+        let len = socket.recv(&mut buffer).await?;
+        // 忽略 ID 不匹配的迟到响应
+        if let Ok(response) = parse_response(buffer.get(..len).unwrap_or_default()) {
+            if response.id == id {
+                return Ok(response);
+            }
+        }
+    }
+}
+
+async fn query_tcp(server: SocketAddr, id: u16, message: &[u8]) -> std::io::Result<Response> {
+    let mut stream = TcpStream::connect(server).await?;
+    let len = u16::try_from(message.len()).map_err(|_| Error::new(ErrorKind::InvalidInput, "query too long"))?;
+    // TCP 上的每个报文前有两字节的长度
+    let mut framed = Vec::with_capacity(message.len() + 2);
+    framed.extend_from_slice(&len.to_be_bytes());
+    framed.extend_from_slice(message);
+    stream.write_all(&framed).await?;
+
+    let len = stream.read_u16().await?;
+    let mut buffer = vec![0u8; usize::from(len)];
+    stream.read_exact(&mut buffer).await?;
+    let response = parse_response(&buffer).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+    if response.id != id {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "DNS response ID does not match the query",
+        ));
+    }
+    Ok(response)
+}
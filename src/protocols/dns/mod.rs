@@ -0,0 +1,97 @@
+//! DNS 查询探测协议
+//!
+//! 向指定的 DNS 服务器发送查询，测量响应时间并记录响应码和应答记录数，
+//! 用于区分"目标不可达"和"解析器慢或出错"。报文编解码和查询客户端也用于其他模块。
+
+pub mod client;
+pub mod ping;
+pub mod resolver;
+pub mod result;
+pub mod wire;
+
+use crate::utils::rate_limit;
+use crate::utils::validation::i64_to_u64_positive;
+use client::{query, DnsProtocol};
+use pyo3::prelude::*;
+use result::DnsPingResult;
+use std::io::ErrorKind;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use wire::{rcode_name, RecordType};
+
+/// DNS 探测目标
+#[derive(Clone, Debug)]
+pub struct DnsTarget {
+    /// 查询的域名
+    pub name: String,
+    /// DNS 服务器地址
+    pub server: SocketAddr,
+    /// 记录类型
+    pub record_type: RecordType,
+    /// 传输协议
+    pub protocol: DnsProtocol,
+    /// 查询超时时间
+    pub timeout: Duration,
+}
+
+impl DnsTarget {
+    /// 从 Python 参数创建
+    ///
+    /// # Errors
+    /// - `PyValueError`: If the server, record type, protocol or `timeout_ms` is invalid
+    pub fn from_args(name: String, server: &str, record_type: &str, protocol: &str, timeout_ms: i64) -> PyResult<Self> {
+        Ok(Self {
+            name,
+            server: client::parse_server(server)?,
+            record_type: RecordType::from_name(record_type)?,
+            protocol: DnsProtocol::from_name(protocol)?,
+            timeout: Duration::from_millis(i64_to_u64_positive(timeout_ms, "timeout_ms")?),
+        })
+    }
+}
+
+/// 发送一次查询
+///
+/// 发送前等待全局速率限制。
+pub async fn probe(target: &DnsTarget) -> DnsPingResult {
+    rate_limit::acquire().await;
+    let started = Instant::now();
+    let outcome = query(
+        target.server,
+        target.protocol,
+        &target.name,
+        target.record_type,
+        target.timeout,
+    )
+    .await;
+    let duration_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+    let mut result = DnsPingResult {
+        server: target.server.to_string(),
+        name: target.name.clone(),
+        record_type: target.record_type.name(),
+        protocol: target.protocol.name().to_string(),
+        rcode: None,
+        answer_count: None,
+        addresses: Vec::new(),
+        duration_ms,
+        error: None,
+        timed_out: false,
+    };
+    match outcome {
+        Ok(response) => {
+            result.rcode = Some(rcode_name(response.rcode));
+            result.answer_count = Some(response.answer_count);
+            result.addresses = response
+                .addresses
+                .iter()
+                .map(|record| record.address.to_string())
+                .collect();
+        }
+        Err(e) => {
+            result.timed_out = e.kind() == ErrorKind::TimedOut;
+            result.error = Some(e.to_string());
+        }
+    }
+    result
+}
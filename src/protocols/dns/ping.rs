@@ -0,0 +1,103 @@
+//! DNS 探测的 Python 接口
+
+use super::result::DnsPingResult;
+use super::{probe, DnsTarget};
+use crate::protocols::common::run_probes;
+use crate::utils::conversion::extract_target;
+use crate::utils::validation::{i64_to_u64_positive, validate_count};
+use pyo3::prelude::*;
+use pyo3_async_runtimes::tokio::future_into_py;
+use std::time::Duration;
+
+/// 测量 DNS 服务器响应时间的 Pinger
+#[pyclass(module = "ping_rs._ping_rs")]
+pub struct DnsPinger {
+    target: DnsTarget,
+    interval: Duration,
+}
+
+#[pymethods]
+impl DnsPinger {
+    /// 创建新的 `DnsPinger` 实例
+    ///
+    /// # Arguments
+    /// - `name`: 查询的域名
+    /// - `server`: DNS 服务器地址，`IP`、`IP:port` 或 `[IPv6]:port`（默认端口 53）
+    /// - `record_type`: 记录类型，例如 `A`（默认）、`AAAA`、`MX`，或 `TYPE<n>`
+    /// - `protocol`: `udp`（默认，响应被截断时改用 TCP）或 `tcp`
+    /// - `interval_ms`: `ping_multiple` 中两次查询的间隔（默认 1000ms）
+    /// - `timeout_ms`: 单次查询的超时时间（默认 2000ms）
+    ///
+    /// # Errors
+    /// - `PyValueError`: If the server, record type or protocol is invalid, or an interval / timeout is not positive
+    /// - `PyTypeError`: If the name cannot be converted to a string
+    #[new]
+    #[pyo3(signature = (name, server, record_type="A", protocol="udp", interval_ms=1000, timeout_ms=2000))]
+    pub fn new(
+        name: &Bound<PyAny>,
+        server: &str,
+        record_type: &str,
+        protocol: &str,
+        interval_ms: i64,
+        timeout_ms: i64,
+    ) -> PyResult<Self> {
+        let name = extract_target(name)?;
+        let interval = Duration::from_millis(i64_to_u64_positive(interval_ms, "interval_ms")?);
+        let target = DnsTarget::from_args(name, server, record_type, protocol, timeout_ms)?;
+        Ok(Self { target, interval })
+    }
+
+    /// 发送一次查询
+    pub fn ping_once(&self, py: Python<'_>) -> DnsPingResult {
+        py.detach(|| pyo3_async_runtimes::tokio::get_runtime().block_on(probe(&self.target)))
+    }
+
+    /// 发送 `count` 次查询
+    ///
+    /// # Errors
+    /// - `PyValueError`: If `count` is not positive
+    #[pyo3(signature = (count=4))]
+    pub fn ping_multiple(&self, py: Python<'_>, count: i32) -> PyResult<Vec<DnsPingResult>> {
+        let count = validate_count(count, "count")?;
+        Ok(py.detach(|| {
+            pyo3_async_runtimes::tokio::get_runtime().block_on(run_probes(count, self.interval, || probe(&self.target)))
+        }))
+    }
+
+    /// 异步发送一次查询
+    ///
+    /// # Errors
+    /// - `PyRuntimeError`: If the coroutine cannot be created
+    pub fn ping_once_async<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let target = self.target.clone();
+        future_into_py(py, async move { Ok(probe(&target).await) })
+    }
+
+    /// 异步发送 `count` 次查询
+    ///
+    /// # Errors
+    /// - `PyValueError`: If `count` is not positive
+    #[pyo3(signature = (count=4))]
+    pub fn ping_multiple_async<'py>(&self, py: Python<'py>, count: i32) -> PyResult<Bound<'py, PyAny>> {
+        let count = validate_count(count, "count")?;
+        let target = self.target.clone();
+        let interval = self.interval;
+        future_into_py(
+            py,
+            async move { Ok(run_probes(count, interval, || probe(&target)).await) },
+        )
+    }
+
+    /// Python `__repr__` method for string representation
+    pub fn __repr__(&self) -> String {
+        format!(
+            "DnsPinger(name='{}', server='{}', record_type='{}', protocol='{}', interval_ms={}, timeout_ms={})",
+            self.target.name,
+            self.target.server,
+            self.target.record_type.name(),
+            self.target.protocol.name(),
+            self.interval.as_millis(),
+            self.target.timeout.as_millis()
+        )
+    }
+}
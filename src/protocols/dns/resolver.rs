@@ -0,0 +1,301 @@
+//! 预解析使用的 DNS 解析器
+//!
+//! 可以指定 DNS 服务器、传输协议、搜索域和 hosts 覆盖表，让预解析绕过系统解析器，
+//! 例如测试分离视图（split-horizon）DNS。未指定 DNS 服务器时仍使用系统解析器，但搜索域和 hosts 覆盖表照常生效。
+
+use super::client::{parse_server, query, DnsProtocol};
+use super::wire::{rcode_name, RecordType, TYPE_A, TYPE_AAAA};
+use pinger::target::{IPVersion, Target};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::BTreeMap;
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+/// 单个 DNS 服务器查询的最短超时
+const MIN_ATTEMPT_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// hosts 覆盖表的值：单个地址或地址列表
+#[derive(FromPyObject)]
+pub enum HostsEntry {
+    /// 单个地址
+    One(String),
+    /// 多个地址
+    Many(Vec<String>),
+}
+
+/// 一次解析的结果
+#[derive(Clone, Debug, Default)]
+pub struct Lookup {
+    /// 解析到的地址，IPv4 在前
+    pub addresses: Vec<IpAddr>,
+    /// 应答记录中最小的 TTL，没有地址时为否定缓存 TTL（hosts 覆盖表和系统解析器的结果为 None）
+    pub ttl: Option<Duration>,
+}
+
+/// 预解析使用的 DNS 解析器配置
+#[pyclass(module = "ping_rs._ping_rs", frozen)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DnsResolver {
+    nameservers: Vec<SocketAddr>,
+    protocol: DnsProtocol,
+    search_domains: Vec<String>,
+    hosts: BTreeMap<String, Vec<IpAddr>>,
+}
+
+/// 统一域名的大小写并去掉结尾的点
+fn normalize(name: &str) -> String {
+    name.trim_end_matches('.').to_ascii_lowercase()
+}
+
+/// 地址是否符合要求的 IP 版本
+pub fn matches_version(address: IpAddr, version: IPVersion) -> bool {
+    match version {
+        IPVersion::V4 => address.is_ipv4(),
+        IPVersion::V6 => address.is_ipv6(),
+        IPVersion::Any => true,
+    }
+}
+
+#[pymethods]
+impl DnsResolver {
+    /// 创建新的 `DnsResolver` 实例
+    ///
+    /// # Arguments
+    /// - `nameservers`: DNS 服务器列表，`IP`、`IP:port` 或 `[IPv6]:port`（默认端口 53），按顺序尝试；为空时使用系统解析器
+    /// - `protocol`: `udp`（默认，响应被截断时改用 TCP）或 `tcp`
+    /// - `search_domains`: 搜索域，不带点的名称先加搜索域查询，带点的名称先按原样查询
+    /// - `hosts`: hosts 覆盖表，域名到地址（或地址列表）的映射，优先于 DNS 查询
+    ///
+    /// # Errors
+    /// - `PyValueError`: If a nameserver, the protocol, a search domain or a hosts address is invalid
+    #[new]
+    #[pyo3(signature = (nameservers=None, protocol="udp", search_domains=None, hosts=None))]
+    pub fn new(
+        nameservers: Option<Vec<String>>,
+        protocol: &str,
+        search_domains: Option<Vec<String>>,
+        hosts: Option<BTreeMap<String, HostsEntry>>,
+    ) -> PyResult<Self> {
+        let nameservers = nameservers
+            .unwrap_or_default()
+            .iter()
+            .map(|server| parse_server(server))
+            .collect::<PyResult<_>>()?;
+        let search_domains = search_domains
+            .unwrap_or_default()
+            .iter()
+            .map(|domain| {
+                let domain = normalize(domain);
+                if domain.is_empty() {
+                    Err(PyErr::new::<PyValueError, _>("search domains must not be empty"))
+                } else {
+                    Ok(domain)
+                }
+            })
+            .collect::<PyResult<_>>()?;
+        let hosts = hosts
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, entry)| {
+                let addresses = match entry {
+                    HostsEntry::One(address) => vec![address],
+                    HostsEntry::Many(addresses) => addresses,
+                };
+                let addresses = addresses
+                    .iter()
+                    .map(|address| {
+                        address.parse().map_err(|_| {
+                            PyErr::new::<PyValueError, _>(format!("Invalid address '{address}' for host '{name}'"))
+                        })
+                    })
+                    .collect::<PyResult<_>>()?;
+                Ok((normalize(&name), addresses))
+            })
+            .collect::<PyResult<_>>()?;
+
+        Ok(Self {
+            nameservers,
+            protocol: DnsProtocol::from_name(protocol)?,
+            search_domains,
+            hosts,
+        })
+    }
+
+    /// DNS 服务器列表（`IP:port`）
+    #[getter]
+    pub fn nameservers(&self) -> Vec<String> {
+        self.nameservers.iter().map(ToString::to_string).collect()
+    }
+
+    /// 传输协议
+    #[getter]
+    pub fn protocol(&self) -> &'static str {
+        self.protocol.name()
+    }
+
+    /// 搜索域
+    #[getter]
+    pub fn search_domains(&self) -> Vec<String> {
+        self.search_domains.clone()
+    }
+
+    /// hosts 覆盖表
+    #[getter]
+    pub fn hosts(&self) -> BTreeMap<String, Vec<String>> {
+        self.hosts
+            .iter()
+            .map(|(name, addresses)| (name.clone(), addresses.iter().map(ToString::to_string).collect()))
+            .collect()
+    }
+
+    /// Python `__repr__` method for string representation
+    pub fn __repr__(&self) -> String {
+        format!(
+            "DnsResolver(nameservers={:?}, protocol='{}', search_domains={:?}, hosts={})",
+            self.nameservers(),
+            self.protocol.name(),
+            self.search_domains,
+            self.hosts.len()
+        )
+    }
+}
+
+impl DnsResolver {
+    /// 按搜索域展开的候选名称，结尾带点的名称不使用搜索域
+    fn candidates(&self, name: &str) -> Vec<String> {
+        let absolute = name.ends_with('.');
+        let name = normalize(name);
+        if absolute || self.search_domains.is_empty() {
+            return vec![name];
+        }
+        let searched = self.search_domains.iter().map(|domain| format!("{name}.{domain}"));
+        if name.contains('.') {
+            std::iter::once(name.clone()).chain(searched).collect()
+        } else {
+            searched.chain(std::iter::once(name.clone())).collect()
+        }
+    }
+
+    /// 解析 `name`，返回所有符合 `version` 的地址
+    ///
+    /// 依次尝试 hosts 覆盖表和每个候选名称，返回第一个有地址的结果。
+    /// 所有候选名称都得到了 DNS 服务器的否定应答时返回没有地址的结果。
+    /// 每个 DNS 服务器的查询超时为 `timeout` 按服务器数量均分（至少 100ms）。
+    ///
+    /// # Errors
+    /// 某个候选名称解析失败（系统解析器出错或所有 DNS 服务器都无法查询）且没有候选名称有地址时返回错误信息
+    pub async fn lookup(&self, name: &str, version: IPVersion, timeout: Duration) -> Result<Lookup, String> {
+        let candidates = self.candidates(name);
+        for candidate in &candidates {
+            if let Some(addresses) = self.hosts.get(candidate) {
+                let addresses: Vec<_> = addresses
+                    .iter()
+                    .copied()
+                    .filter(|&address| matches_version(address, version))
+                    .collect();
+                if !addresses.is_empty() {
+                    return Ok(Lookup { addresses, ttl: None });
+                }
+            }
+        }
+
+        let mut last_error = None;
+        let mut negative_ttl: Option<Duration> = None;
+        for candidate in &candidates {
+            let result = if self.nameservers.is_empty() {
+                Self::lookup_system(candidate, version).await
+            } else {
+                self.lookup_nameservers(candidate, version, timeout).await
+            };
+            match result {
+                Ok(lookup) if !lookup.addresses.is_empty() => return Ok(lookup),
+                Ok(lookup) => {
+                    if let Some(ttl) = lookup.ttl {
+                        negative_ttl = Some(negative_ttl.map_or(ttl, |min| min.min(ttl)));
+                    }
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+        match last_error {
+            Some(e) => Err(e),
+            None => Ok(Lookup {
+                addresses: Vec::new(),
+                ttl: negative_ttl,
+            }),
+        }
+    }
+
+    /// 使用系统解析器解析单个名称
+    async fn lookup_system(name: &str, version: IPVersion) -> Result<Lookup, String> {
+        let target = Target::Hostname {
+            domain: name.to_string(),
+            version,
+        };
+        let address = pinger::utils::resolve_target_async(&target)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(Lookup {
+            addresses: vec![address],
+            ttl: None,
+        })
+    }
+
+    /// 按记录类型依次向每个 DNS 服务器查询，服务器返回 `NOERROR` 或 `NXDOMAIN` 时不再向后续服务器查询该类型
+    ///
+    /// `IPVersion::Any` 同时查询 A 和 AAAA，其中一种查询失败时仍返回另一种的记录。
+    ///
+    /// # Errors
+    /// 没有得到任何地址，且至少一种记录类型在所有 DNS 服务器上都查询失败时返回错误信息
+    async fn lookup_nameservers(&self, name: &str, version: IPVersion, timeout: Duration) -> Result<Lookup, String> {
+        let record_types: &[u16] = match version {
+            IPVersion::V4 => &[TYPE_A],
+            IPVersion::V6 => &[TYPE_AAAA],
+            IPVersion::Any => &[TYPE_A, TYPE_AAAA],
+        };
+        let servers = u32::try_from(self.nameservers.len()).unwrap_or(u32::MAX).max(1);
+        let attempt_timeout = (timeout / servers).max(MIN_ATTEMPT_TIMEOUT);
+
+        let mut lookup = Lookup::default();
+        let mut negative_ttl: Option<Duration> = None;
+        let mut last_error = None;
+        for &record_type in record_types {
+            let mut error = format!("no DNS server answered for '{name}'");
+            let mut answered = false;
+            for &server in &self.nameservers {
+                match query(server, self.protocol, name, RecordType(record_type), attempt_timeout).await {
+                    // NOERROR（可能没有该类型的记录）或 NXDOMAIN 都是权威的否定或肯定应答
+                    Ok(response) if response.rcode == 0 || response.rcode == 3 => {
+                        if let Some(ttl) = response.negative_ttl.map(|ttl| Duration::from_secs(ttl.into())) {
+                            negative_ttl = Some(negative_ttl.map_or(ttl, |min| min.min(ttl)));
+                        }
+                        for record in response.addresses {
+                            lookup.addresses.push(record.address);
+                            let ttl = Duration::from_secs(record.ttl.into());
+                            lookup.ttl = Some(lookup.ttl.map_or(ttl, |min| min.min(ttl)));
+                        }
+                        answered = true;
+                        break;
+                    }
+                    Ok(response) => error = format!("{server} answered {}", rcode_name(response.rcode)),
+                    Err(e) => error = format!("{server}: {e}"),
+                }
+            }
+            if !answered {
+                last_error = Some(error);
+            }
+        }
+
+        lookup.addresses.retain(|&address| matches_version(address, version));
+        match last_error {
+            Some(e) if lookup.addresses.is_empty() => Err(e),
+            _ => {
+                if lookup.addresses.is_empty() {
+                    lookup.ttl = negative_ttl;
+                }
+                Ok(lookup)
+            }
+        }
+    }
+}
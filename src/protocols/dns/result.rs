@@ -0,0 +1,91 @@
+//! DNS 探测结果
+
+use crate::types::result::PingResult;
+use pyo3::prelude::*;
+
+/// 一次 DNS 查询的结果
+#[pyclass(module = "ping_rs._ping_rs", frozen)]
+#[derive(Debug, Clone)]
+pub struct DnsPingResult {
+    /// DNS 服务器地址（`IP:port`）
+    #[pyo3(get)]
+    pub server: String,
+    /// 查询的域名
+    #[pyo3(get)]
+    pub name: String,
+    /// 记录类型，例如 `A`
+    #[pyo3(get)]
+    pub record_type: String,
+    /// 传输协议（`udp` 或 `tcp`）
+    #[pyo3(get)]
+    pub protocol: String,
+    /// 响应码，例如 `NOERROR`、`NXDOMAIN`，未收到响应时为 None
+    #[pyo3(get)]
+    pub rcode: Option<String>,
+    /// 应答记录数，未收到响应时为 None
+    #[pyo3(get)]
+    pub answer_count: Option<u16>,
+    /// 应答中的 A / AAAA 地址
+    #[pyo3(get)]
+    pub addresses: Vec<String>,
+    /// 从发送查询到收到响应（或失败）的耗时（毫秒）
+    #[pyo3(get)]
+    pub duration_ms: f64,
+    /// 失败原因
+    #[pyo3(get)]
+    pub error: Option<String>,
+    /// 是否因超时失败
+    #[pyo3(get)]
+    pub timed_out: bool,
+}
+
+#[pymethods]
+impl DnsPingResult {
+    /// 是否收到了 `NOERROR` 响应
+    pub fn is_success(&self) -> bool {
+        self.rcode.as_deref() == Some("NOERROR")
+    }
+
+    /// 转换为 `PingResult`，以便接入现有的统计和输出端
+    ///
+    /// 收到任意响应（包括 `NXDOMAIN`、`SERVFAIL`）时为 `Pong`；超时为 `Timeout`；其他失败为 `PingExited`。
+    pub fn to_ping_result(&self) -> PingResult {
+        if let (Some(rcode), Some(answer_count)) = (&self.rcode, self.answer_count) {
+            PingResult::Pong {
+                duration_ms: self.duration_ms,
+                line: format!(
+                    "{rcode} {} {} with {answer_count} answers from {}: time={:.3} ms",
+                    self.record_type, self.name, self.server, self.duration_ms
+                ),
+            }
+        } else if self.timed_out {
+            PingResult::Timeout {
+                line: format!("DNS query timeout for {} at {}", self.name, self.server),
+            }
+        } else {
+            PingResult::PingExited {
+                exit_code: -1,
+                stderr: self.error.clone().unwrap_or_default(),
+            }
+        }
+    }
+
+    /// Python `__repr__` method for string representation
+    pub fn __repr__(&self) -> String {
+        let rcode = self
+            .rcode
+            .as_ref()
+            .map_or_else(|| "None".to_string(), |rcode| format!("'{rcode}'"));
+        let answer_count = self
+            .answer_count
+            .map_or_else(|| "None".to_string(), |count| count.to_string());
+        let tail = self
+            .error
+            .as_ref()
+            .map_or_else(String::new, |error| format!(", error='{error}'"));
+        format!(
+            "DnsPingResult(name='{}', record_type='{}', server='{}', rcode={rcode}, answer_count={answer_count}, duration_ms={:.3}{tail})",
+            self.name, self.record_type, self.server, self.duration_ms
+        )
+    }
+}
@@ -0,0 +1,249 @@
+//! DNS 报文编解码（RFC 1035）
+//!
+//! 只实现探测和预解析需要的部分：构造单个问题的查询，解析响应头、应答记录中的 A / AAAA 地址和 TTL，
+//! 以及授权记录中 SOA 的最小 TTL（用于否定缓存，RFC 2308）。
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// 常用的记录类型
+const RECORD_TYPES: &[(&str, u16)] = &[
+    ("A", 1),
+    ("NS", 2),
+    ("CNAME", 5),
+    ("SOA", 6),
+    ("PTR", 12),
+    ("MX", 15),
+    ("TXT", 16),
+    ("AAAA", 28),
+    ("SRV", 33),
+    ("HTTPS", 65),
+    ("ANY", 255),
+    ("CAA", 257),
+];
+
+/// A 记录类型
+pub const TYPE_A: u16 = 1;
+/// AAAA 记录类型
+pub const TYPE_AAAA: u16 = 28;
+const TYPE_SOA: u16 = 6;
+const CLASS_IN: u16 = 1;
+
+/// DNS 记录类型
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecordType(pub u16);
+
+impl RecordType {
+    /// 从类型名（如 `AAAA`）或 `TYPE28` 形式解析
+    ///
+    /// # Errors
+    /// - `PyValueError`: If the name is not a known record type
+    pub fn from_name(name: &str) -> PyResult<Self> {
+        let upper = name.to_ascii_uppercase();
+        RECORD_TYPES
+            .iter()
+            .find(|(known, _)| *known == upper)
+            .map(|&(_, code)| Self(code))
+            .or_else(|| upper.strip_prefix("TYPE").and_then(|code| code.parse().ok()).map(Self))
+            .ok_or_else(|| PyErr::new::<PyValueError, _>(format!("Unknown DNS record type '{name}'")))
+    }
+
+    /// 类型名，未知类型为 `TYPE<n>`
+    pub fn name(self) -> String {
+        RECORD_TYPES
+            .iter()
+            .find(|(_, code)| *code == self.0)
+            .map_or_else(|| format!("TYPE{}", self.0), |(name, _)| (*name).to_string())
+    }
+}
+
+/// 响应码名称
+pub fn rcode_name(rcode: u8) -> String {
+    match rcode {
+        0 => "NOERROR".to_string(),
+        1 => "FORMERR".to_string(),
+        2 => "SERVFAIL".to_string(),
+        3 => "NXDOMAIN".to_string(),
+        4 => "NOTIMP".to_string(),
+        5 => "REFUSED".to_string(),
+        _ => format!("RCODE{rcode}"),
+    }
+}
+
+/// 构造只有一个问题、设置了递归查询标志的查询报文
+///
+/// # Errors
+/// 域名为空、标签长度超过 63 字节或总长度超过 255 字节时返回错误信息
+pub fn build_query(id: u16, name: &str, record_type: RecordType) -> Result<Vec<u8>, String> {
+    let mut message = Vec::with_capacity(18 + name.len());
+    message.extend_from_slice(&id.to_be_bytes());
+    // RD = 1
+    message.extend_from_slice(&[0x01, 0x00]);
+    // QDCOUNT = 1, ANCOUNT = NSCOUNT = ARCOUNT = 0
+    message.extend_from_slice(&[0, 1, 0, 0, 0, 0, 0, 0]);
+
+    let name = name.strip_suffix('.').unwrap_or(name);
+    if name.is_empty() {
+        return Err("empty domain name".to_string());
+    }
+    for label in name.split('.') {
+        let len = u8::try_from(label.len())
+            .ok()
+            .filter(|len| (1..=63).contains(len))
+            .ok_or_else(|| format!("invalid label '{label}' in '{name}'"))?;
+        message.push(len);
+        message.extend_from_slice(label.as_bytes());
+    }
+    message.push(0);
+    if message.len() - 12 > 255 {
+        return Err(format!("domain name '{name}' is too long"));
+    }
+    message.extend_from_slice(&record_type.0.to_be_bytes());
+    message.extend_from_slice(&CLASS_IN.to_be_bytes());
+    Ok(message)
+}
+
+/// 应答中的地址记录
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AddressRecord {
+    /// 地址
+    pub address: IpAddr,
+    /// 生存时间（秒）
+    pub ttl: u32,
+}
+
+/// 解析后的响应
+#[derive(Clone, Debug, Default)]
+pub struct Response {
+    /// 报文 ID
+    pub id: u16,
+    /// 响应码
+    pub rcode: u8,
+    /// 是否被截断（TC 标志）
+    pub truncated: bool,
+    /// 应答记录数
+    pub answer_count: u16,
+    /// 应答中的 A / AAAA 记录
+    pub addresses: Vec<AddressRecord>,
+    /// 授权记录中 SOA 的否定缓存 TTL（`min(TTL, MINIMUM)`）
+    pub negative_ttl: Option<u32>,
+}
+
+/// 按偏移读取报文的游标
+struct Reader<'a> {
+    message: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn bytes(&mut self, len: usize) -> Option<&[u8]> {
+        let bytes = self.message.get(self.pos..self.pos.checked_add(len)?)?;
+        self.pos += len;
+        Some(bytes)
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        let bytes = self.bytes(2)?;
+        Some(u16::from_be_bytes([*bytes.first()?, *bytes.get(1)?]))
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        let bytes = self.bytes(4)?;
+        Some(u32::from_be_bytes(bytes.try_into().ok()?))
+    }
+
+    /// 跳过一个（可能压缩的）域名
+    fn skip_name(&mut self) -> Option<()> {
+        loop {
+            let len = *self.bytes(1)?.first()?;
+            match len {
+                0 => return Some(()),
+                // 压缩指针占两个字节，指向的名称不需要读取
+                len if len & 0xc0 == 0xc0 => return self.bytes(1).map(|_| ()),
+                len if len & 0xc0 == 0 => {
+                    self.bytes(usize::from(len))?;
+                }
+                _ => return None,
+            }
+        }
+    }
+}
+
+/// 解析响应报文
+///
+/// # Errors
+/// 报文过短、格式错误或不是响应时返回错误信息
+pub fn parse_response(message: &[u8]) -> Result<Response, String> {
+    parse(message).ok_or_else(|| format!("malformed DNS response ({} bytes)", message.len()))
+}
+
+fn parse(message: &[u8]) -> Option<Response> {
+    let mut reader = Reader { message, pos: 0 };
+    let id = reader.u16()?;
+    let flags = reader.u16()?;
+    // QR 必须为 1
+    if flags & 0x8000 == 0 {
+        return None;
+    }
+    let question_count = reader.u16()?;
+    let answer_count = reader.u16()?;
+    let authority_count = reader.u16()?;
+    reader.u16()?;
+
+    let mut response = Response {
+        id,
+        rcode: u8::try_from(flags & 0x000f).ok()?,
+        truncated: flags & 0x0200 != 0,
+        answer_count,
+        ..Response::default()
+    };
+    // 截断的响应可能不完整，只解析头部
+    if response.truncated {
+        return Some(response);
+    }
+
+    for _ in 0..question_count {
+        reader.skip_name()?;
+        reader.bytes(4)?;
+    }
+    for index in 0..u32::from(answer_count) + u32::from(authority_count) {
+        reader.skip_name()?;
+        let record_type = reader.u16()?;
+        let class = reader.u16()?;
+        let ttl = reader.u32()?;
+        let len = usize::from(reader.u16()?);
+        let end = reader.pos.checked_add(len)?;
+        let in_answer = index < u32::from(answer_count);
+        match (record_type, class, len) {
+            (TYPE_A, CLASS_IN, 4) if in_answer => {
+                let bytes: [u8; 4] = reader.bytes(4)?.try_into().ok()?;
+                response.addresses.push(AddressRecord {
+                    address: IpAddr::V4(Ipv4Addr::from(bytes)),
+                    ttl,
+                });
+            }
+            (TYPE_AAAA, CLASS_IN, 16) if in_answer => {
+                let bytes: [u8; 16] = reader.bytes(16)?.try_into().ok()?;
+                response.addresses.push(AddressRecord {
+                    address: IpAddr::V6(Ipv6Addr::from(bytes)),
+                    ttl,
+                });
+            }
+            (TYPE_SOA, _, _) if !in_answer => {
+                // MNAME、RNAME 之后依次是 SERIAL、REFRESH、RETRY、EXPIRE、MINIMUM
+                reader.skip_name()?;
+                reader.skip_name()?;
+                reader.bytes(16)?;
+                let minimum = reader.u32()?;
+                response.negative_ttl = Some(ttl.min(minimum));
+            }
+            _ => {}
+        }
+        if reader.pos > end {
+            return None;
+        }
+        reader.pos = end;
+    }
+    Some(response)
+}
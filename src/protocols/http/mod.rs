@@ -0,0 +1,221 @@
+//! HTTP(S) 探测协议
+//!
+//! 向 URL 发送一次 HEAD / GET 请求，分别记录 DNS 解析、TCP 连接、TLS 握手、首字节和总耗时，
+//! 用于按 L7 口径衡量延迟。每次探测使用独立的连接（`Connection: close`），不复用连接，
+//! 因此每次都包含完整的建连开销。
+
+pub mod ping;
+pub mod result;
+pub mod stream;
+
+use crate::protocols::common::{elapsed_ms, parse_timeouts};
+use crate::protocols::icmp::resolve_target;
+use crate::utils::http::{parse_status, HttpEndpoint};
+use crate::utils::{rate_limit, tls};
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use result::HttpPingResult;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::{Error, ErrorKind};
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsConnector;
+
+/// 读取响应时的缓冲区大小，状态行只从第一块数据中解析
+const READ_BUFFER_LEN: usize = 8192;
+
+/// HTTP 探测目标
+#[derive(Clone)]
+pub struct HttpTarget {
+    /// 解析后的 URL
+    pub endpoint: HttpEndpoint,
+    /// 请求方法（`HEAD` 或 `GET`）
+    pub method: &'static str,
+    /// 完整的请求头，构造时生成
+    request: String,
+    /// `https://` 的 TLS 连接器
+    tls: Option<TlsConnector>,
+    /// 整个请求的超时时间
+    pub timeout: Duration,
+    /// 是否强制使用 IPv4
+    pub ipv4: bool,
+    /// 是否强制使用 IPv6
+    pub ipv6: bool,
+    /// DNS 解析超时（None 表示使用 `timeout`）
+    pub dns_timeout: Option<Duration>,
+}
+
+impl HttpTarget {
+    /// 从 Python 参数创建
+    ///
+    /// # Errors
+    /// - `PyValueError`: If the URL or method is invalid, a header contains a line break,
+    ///   or `timeout_ms` / `dns_resolve_timeout_ms` is not positive
+    /// - `PyRuntimeError`: If the TLS configuration cannot be created
+    pub fn from_args(
+        url: &str,
+        method: &str,
+        timeout_ms: i64,
+        headers: Option<BTreeMap<String, String>>,
+        verify: bool,
+        ipv4: bool,
+        ipv6: bool,
+        dns_resolve_timeout_ms: Option<i64>,
+    ) -> PyResult<Self> {
+        let endpoint = HttpEndpoint::parse(url, "/")?;
+        let method = match method.to_ascii_uppercase().as_str() {
+            "HEAD" => "HEAD",
+            "GET" => "GET",
+            _ => {
+                return Err(PyErr::new::<PyValueError, _>(format!(
+                    "method must be 'HEAD' or 'GET', got '{method}'"
+                )))
+            }
+        };
+        let (timeout, dns_timeout) = parse_timeouts(timeout_ms, dns_resolve_timeout_ms)?;
+
+        let mut request = format!(
+            "{method} {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: ping-rs/{}\r\nAccept: */*\r\nConnection: close\r\n",
+            endpoint.path,
+            endpoint.authority(),
+            env!("CARGO_PKG_VERSION")
+        );
+        for (name, value) in headers.unwrap_or_default() {
+            if [&name, &value].iter().any(|s| s.contains(['\r', '\n'])) || name.is_empty() {
+                return Err(PyErr::new::<PyValueError, _>(format!("Invalid header '{name}'")));
+            }
+            let _ = write!(request, "{name}: {value}\r\n");
+        }
+        request.push_str("\r\n");
+
+        let tls = if endpoint.tls {
+            let connector = tls::connector(verify, &[b"http/1.1"])
+                .map_err(|e| PyErr::new::<PyRuntimeError, _>(format!("Failed to configure TLS: {e}")))?;
+            Some(connector)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            endpoint,
+            method,
+            request,
+            tls,
+            timeout,
+            ipv4,
+            ipv6,
+            dns_timeout,
+        })
+    }
+}
+
+/// 一次探测中已完成的阶段
+#[derive(Default)]
+struct Timings {
+    address: Option<SocketAddr>,
+    dns_ms: Option<f64>,
+    connect_ms: Option<f64>,
+    tls_ms: Option<f64>,
+    ttfb_ms: Option<f64>,
+}
+
+/// 解析、连接、握手并发送请求，返回响应状态码
+async fn exchange(target: &HttpTarget, timings: &mut Timings) -> std::io::Result<u16> {
+    let endpoint = &target.endpoint;
+    let phase = Instant::now();
+    let ip = resolve_target(
+        &endpoint.host,
+        target.ipv4,
+        target.ipv6,
+        target.timeout,
+        target.dns_timeout,
+    )
+    .await
+    .map_err(|e| Error::other(e.to_string()))?;
+    timings.dns_ms = Some(elapsed_ms(phase));
+    let addr = SocketAddr::new(ip, endpoint.port);
+    timings.address = Some(addr);
+
+    let phase = Instant::now();
+    let stream = TcpStream::connect(addr).await?;
+    timings.connect_ms = Some(elapsed_ms(phase));
+
+    if let Some(connector) = &target.tls {
+        let phase = Instant::now();
+        let stream = connector.connect(tls::server_name(&endpoint.host)?, stream).await?;
+        timings.tls_ms = Some(elapsed_ms(phase));
+        request(stream, target, timings).await
+    } else {
+        request(stream, target, timings).await
+    }
+}
+
+/// 发送请求并读取完整响应
+async fn request<S>(mut stream: S, target: &HttpTarget, timings: &mut Timings) -> std::io::Result<u16>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    stream.write_all(target.request.as_bytes()).await?;
+    stream.flush().await?;
+
+    let phase = Instant::now();
+    let mut head = vec![0u8; READ_BUFFER_LEN];
+    let len = stream.read(&mut head).await?;
+    if len == 0 {
+        return Err(Error::new(
+            ErrorKind::UnexpectedEof,
+            "connection closed before response",
+        ));
+    }
+    timings.ttfb_ms = Some(elapsed_ms(phase));
+    head.truncate(len);
+
+    // 读完响应体，总耗时包含下载时间；未发送 close_notify 就关闭的 TLS 连接按正常结束处理
+    let mut buffer = vec![0u8; READ_BUFFER_LEN];
+    loop {
+        match stream.read(&mut buffer).await {
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(e),
+        }
+    }
+    parse_status(&head)
+}
+
+/// 发送一次 HTTP 请求
+///
+/// 发送前等待全局速率限制。整个请求（包括 DNS 解析）受 `timeout` 限制，失败时结果中保留已完成阶段的耗时。
+pub async fn probe(target: &HttpTarget) -> HttpPingResult {
+    rate_limit::acquire().await;
+    let mut timings = Timings::default();
+    let started = Instant::now();
+    let outcome = tokio::time::timeout(target.timeout, exchange(target, &mut timings)).await;
+    let total_ms = elapsed_ms(started);
+
+    let (status, error, timed_out) = match outcome {
+        Ok(Ok(status)) => (Some(status), None, false),
+        Ok(Err(e)) => (None, Some(e.to_string()), e.kind() == ErrorKind::TimedOut),
+        Err(_) => (
+            None,
+            Some(format!("request timed out after {}ms", target.timeout.as_millis())),
+            true,
+        ),
+    };
+    HttpPingResult {
+        url: target.endpoint.url(),
+        method: target.method.to_string(),
+        address: timings.address.map(|addr| addr.ip().to_string()),
+        status,
+        dns_ms: timings.dns_ms,
+        connect_ms: timings.connect_ms,
+        tls_ms: timings.tls_ms,
+        ttfb_ms: timings.ttfb_ms,
+        total_ms,
+        error,
+        timed_out,
+    }
+}
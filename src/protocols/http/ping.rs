@@ -0,0 +1,114 @@
+//! HTTP 探测的 Python 接口
+
+use super::result::HttpPingResult;
+use super::{probe, HttpTarget};
+use crate::protocols::common::run_probes;
+use crate::utils::validation::{i64_to_u64_positive, validate_count};
+use pyo3::prelude::*;
+use pyo3_async_runtimes::tokio::future_into_py;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// 测量 HTTP(S) 请求各阶段耗时的 Pinger
+#[pyclass(module = "ping_rs._ping_rs")]
+pub struct HttpPinger {
+    target: HttpTarget,
+    interval: Duration,
+}
+
+#[pymethods]
+impl HttpPinger {
+    /// 创建新的 `HttpPinger` 实例
+    ///
+    /// # Arguments
+    /// - `url`: `http://` 或 `https://` URL
+    /// - `method`: 请求方法，`HEAD`（默认）或 `GET`
+    /// - `interval_ms`: `ping_multiple` 中两次请求的间隔（默认 1000ms）
+    /// - `timeout_ms`: 单次请求的超时时间，包括 DNS 解析和读取响应（默认 5000ms）
+    /// - `headers`: 额外的请求头
+    /// - `verify`: 是否校验服务器证书（默认 True）
+    /// - `ipv4` / `ipv6`: 强制使用 IPv4 / IPv6
+    /// - `dns_resolve_timeout_ms`: DNS 解析超时（默认使用 `timeout_ms`）
+    ///
+    /// # Errors
+    /// - `PyValueError`: If the URL, method or a header is invalid, or an interval / timeout is not positive
+    /// - `PyRuntimeError`: If the TLS configuration cannot be created
+    #[new]
+    #[pyo3(signature = (url, method="HEAD", interval_ms=1000, timeout_ms=5000, headers=None, verify=true, ipv4=false, ipv6=false, dns_resolve_timeout_ms=None))]
+    pub fn new(
+        url: &str,
+        method: &str,
+        interval_ms: i64,
+        timeout_ms: i64,
+        headers: Option<BTreeMap<String, String>>,
+        verify: bool,
+        ipv4: bool,
+        ipv6: bool,
+        dns_resolve_timeout_ms: Option<i64>,
+    ) -> PyResult<Self> {
+        let interval = Duration::from_millis(i64_to_u64_positive(interval_ms, "interval_ms")?);
+        let target = HttpTarget::from_args(
+            url,
+            method,
+            timeout_ms,
+            headers,
+            verify,
+            ipv4,
+            ipv6,
+            dns_resolve_timeout_ms,
+        )?;
+        Ok(Self { target, interval })
+    }
+
+    /// 发送一次请求
+    pub fn ping_once(&self, py: Python<'_>) -> HttpPingResult {
+        py.detach(|| pyo3_async_runtimes::tokio::get_runtime().block_on(probe(&self.target)))
+    }
+
+    /// 发送 `count` 次请求
+    ///
+    /// # Errors
+    /// - `PyValueError`: If `count` is not positive
+    #[pyo3(signature = (count=4))]
+    pub fn ping_multiple(&self, py: Python<'_>, count: i32) -> PyResult<Vec<HttpPingResult>> {
+        let count = validate_count(count, "count")?;
+        Ok(py.detach(|| {
+            pyo3_async_runtimes::tokio::get_runtime().block_on(run_probes(count, self.interval, || probe(&self.target)))
+        }))
+    }
+
+    /// 异步发送一次请求
+    ///
+    /// # Errors
+    /// - `PyRuntimeError`: If the coroutine cannot be created
+    pub fn ping_once_async<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let target = self.target.clone();
+        future_into_py(py, async move { Ok(probe(&target).await) })
+    }
+
+    /// 异步发送 `count` 次请求
+    ///
+    /// # Errors
+    /// - `PyValueError`: If `count` is not positive
+    #[pyo3(signature = (count=4))]
+    pub fn ping_multiple_async<'py>(&self, py: Python<'py>, count: i32) -> PyResult<Bound<'py, PyAny>> {
+        let count = validate_count(count, "count")?;
+        let target = self.target.clone();
+        let interval = self.interval;
+        future_into_py(
+            py,
+            async move { Ok(run_probes(count, interval, || probe(&target)).await) },
+        )
+    }
+
+    /// Python `__repr__` method for string representation
+    pub fn __repr__(&self) -> String {
+        format!(
+            "HttpPinger(url='{}', method='{}', interval_ms={}, timeout_ms={})",
+            self.target.endpoint.url(),
+            self.target.method,
+            self.interval.as_millis(),
+            self.target.timeout.as_millis()
+        )
+    }
+}
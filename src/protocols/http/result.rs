@@ -0,0 +1,94 @@
+//! HTTP 探测结果
+
+use crate::types::result::PingResult;
+use pyo3::prelude::*;
+
+/// 一次 HTTP 请求的结果和各阶段耗时
+///
+/// 各阶段耗时都是该阶段自身的耗时（毫秒），不是从请求开始累计的时间；未完成的阶段为 None。
+#[pyclass(module = "ping_rs._ping_rs", frozen)]
+#[derive(Debug, Clone)]
+pub struct HttpPingResult {
+    /// 请求的 URL
+    #[pyo3(get)]
+    pub url: String,
+    /// 请求方法
+    #[pyo3(get)]
+    pub method: String,
+    /// 连接的 IP 地址
+    #[pyo3(get)]
+    pub address: Option<String>,
+    /// 响应状态码
+    #[pyo3(get)]
+    pub status: Option<u16>,
+    /// DNS 解析耗时
+    #[pyo3(get)]
+    pub dns_ms: Option<f64>,
+    /// TCP 连接耗时
+    #[pyo3(get)]
+    pub connect_ms: Option<f64>,
+    /// TLS 握手耗时（仅 `https://`）
+    #[pyo3(get)]
+    pub tls_ms: Option<f64>,
+    /// 从发送请求到收到第一个响应字节的耗时
+    #[pyo3(get)]
+    pub ttfb_ms: Option<f64>,
+    /// 从开始解析到读完响应（或失败）的总耗时
+    #[pyo3(get)]
+    pub total_ms: f64,
+    /// 失败原因
+    #[pyo3(get)]
+    pub error: Option<String>,
+    /// 是否因超时失败
+    #[pyo3(get)]
+    pub timed_out: bool,
+}
+
+#[pymethods]
+impl HttpPingResult {
+    /// 是否收到了状态码小于 400 的响应
+    pub fn is_success(&self) -> bool {
+        self.status.is_some_and(|status| status < 400)
+    }
+
+    /// 转换为 `PingResult`，以便接入现有的统计和输出端
+    ///
+    /// 收到任意响应（包括 4xx / 5xx）时为 `Pong`，延迟为 `total_ms`；超时为 `Timeout`；
+    /// 其他失败（解析失败、连接被拒绝、TLS 错误等）为 `PingExited`。
+    pub fn to_ping_result(&self) -> PingResult {
+        match (self.status, &self.error) {
+            (Some(status), _) => PingResult::Pong {
+                duration_ms: self.total_ms,
+                line: format!("HTTP {status} from {}: time={:.3} ms", self.url, self.total_ms),
+            },
+            (None, _) if self.timed_out => PingResult::Timeout {
+                line: format!("Request timeout for {}", self.url),
+            },
+            (None, error) => PingResult::PingExited {
+                exit_code: -1,
+                stderr: error.clone().unwrap_or_default(),
+            },
+        }
+    }
+
+    /// Python `__repr__` method for string representation
+    pub fn __repr__(&self) -> String {
+        let fmt = |v: Option<f64>| v.map_or_else(|| "None".to_string(), |v| format!("{v:.3}"));
+        let status = self
+            .status
+            .map_or_else(|| "None".to_string(), |status| status.to_string());
+        let tail = self
+            .error
+            .as_ref()
+            .map_or_else(String::new, |error| format!(", error='{error}'"));
+        format!(
+            "HttpPingResult(url='{}', status={status}, dns_ms={}, connect_ms={}, tls_ms={}, ttfb_ms={}, total_ms={:.3}{tail})",
+            self.url,
+            fmt(self.dns_ms),
+            fmt(self.connect_ms),
+            fmt(self.tls_ms),
+            fmt(self.ttfb_ms),
+            self.total_ms
+        )
+    }
+}
@@ -0,0 +1,130 @@
+//! 持续 HTTP 探测流
+
+use super::result::HttpPingResult;
+use super::{probe, HttpTarget};
+use crate::utils::validation::i64_to_u64_positive;
+use pyo3::exceptions::PyStopAsyncIteration;
+use pyo3::prelude::*;
+use pyo3_async_runtimes::tokio::future_into_py;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// 流的迭代状态
+struct StreamState {
+    next_at: Instant,
+    count: usize,
+}
+
+/// 流的配置，在同步和异步迭代之间共享
+struct StreamConfig {
+    target: HttpTarget,
+    interval: Duration,
+    max_count: Option<usize>,
+}
+
+/// 持续发送 HTTP 请求的流，支持同步和异步迭代
+///
+/// 单次请求失败不会结束流，只有达到 `max_count` 时结束。
+#[pyclass(module = "ping_rs._ping_rs")]
+pub struct HttpPingStream {
+    config: Arc<StreamConfig>,
+    state: Arc<tokio::sync::Mutex<StreamState>>,
+}
+
+/// 等到下一次请求时间后发送请求，流结束时返回 None
+async fn next_result(config: &StreamConfig, state: &tokio::sync::Mutex<StreamState>) -> Option<HttpPingResult> {
+    let mut state = state.lock().await;
+    if config.max_count.is_some_and(|max| state.count >= max) {
+        return None;
+    }
+    tokio::time::sleep_until(state.next_at.into()).await;
+    state.next_at = Instant::now() + config.interval;
+    let result = probe(&config.target).await;
+    state.count += 1;
+    Some(result)
+}
+
+#[pymethods]
+impl HttpPingStream {
+    /// 创建新的 `HttpPingStream` 实例
+    ///
+    /// 参数与 `HttpPinger` 相同，`max_count` 限制请求次数（None 表示不限）。
+    ///
+    /// # Errors
+    /// - `PyValueError`: If the URL, method or a header is invalid, or an interval / timeout is not positive
+    /// - `PyRuntimeError`: If the TLS configuration cannot be created
+    #[new]
+    #[pyo3(signature = (url, method="HEAD", interval_ms=1000, timeout_ms=5000, headers=None, verify=true, max_count=None, ipv4=false, ipv6=false, dns_resolve_timeout_ms=None))]
+    pub fn new(
+        url: &str,
+        method: &str,
+        interval_ms: i64,
+        timeout_ms: i64,
+        headers: Option<BTreeMap<String, String>>,
+        verify: bool,
+        max_count: Option<usize>,
+        ipv4: bool,
+        ipv6: bool,
+        dns_resolve_timeout_ms: Option<i64>,
+    ) -> PyResult<Self> {
+        let interval = Duration::from_millis(i64_to_u64_positive(interval_ms, "interval_ms")?);
+        let target = HttpTarget::from_args(
+            url,
+            method,
+            timeout_ms,
+            headers,
+            verify,
+            ipv4,
+            ipv6,
+            dns_resolve_timeout_ms,
+        )?;
+        Ok(Self {
+            config: Arc::new(StreamConfig {
+                target,
+                interval,
+                max_count,
+            }),
+            state: Arc::new(tokio::sync::Mutex::new(StreamState {
+                next_at: Instant::now(),
+                count: 0,
+            })),
+        })
+    }
+
+    /// 流是否还会产生结果
+    pub fn is_active(&self) -> bool {
+        self.state
+            .try_lock()
+            .map_or(true, |state| self.config.max_count.is_none_or(|max| state.count < max))
+    }
+
+    /// Python iterator protocol: return self
+    pub fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Python iterator protocol: block until the next request completes
+    pub fn __next__(&self, py: Python<'_>) -> Option<HttpPingResult> {
+        py.detach(|| pyo3_async_runtimes::tokio::get_runtime().block_on(next_result(&self.config, &self.state)))
+    }
+
+    /// Python async iterator protocol: return self
+    pub fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Python async iterator protocol: get the next request result
+    ///
+    /// # Errors
+    /// - `PyStopAsyncIteration`: When `max_count` is reached
+    pub fn __anext__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let config = self.config.clone();
+        let state = self.state.clone();
+        future_into_py(py, async move {
+            next_result(&config, &state)
+                .await
+                .ok_or_else(|| PyStopAsyncIteration::new_err("Stream exhausted"))
+        })
+    }
+}
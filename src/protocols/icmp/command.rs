@@ -0,0 +1,275 @@
+//! 系统 ping 命令
+//!
+//! Unix 上由这里直接启动 ping 进程（而不是通过 pinger），以便设置 `LC_ALL=C`：
+//! ping 的输出和 `-i` 参数的小数点因此始终使用 C 语言环境，由 `parse_line` 按英文格式解析。
+//! 命令参数与 pinger 保持一致：Linux 使用 iputils 的 `ping -O`（未收到响应时输出 `no answer yet`），
+//! macOS / FreeBSD 使用 `ping`（IPv6 为 `ping6`，未收到响应时输出 `Request timeout`）。
+//!
+//! Windows 上 pinger 使用系统 API 而不启动进程，只有设置了负载大小或禁止分片
+//! （`PingOptions::raw_arguments`，见 `PacketOptions`）时才由这里启动 `ping.exe -t`，
+//! 此时间隔固定为 1 秒、不支持指定网络接口，输出按本地化格式兜底解析。
+
+use pinger::target::{IPVersion, Target};
+use pinger::{PingCreationError, PingOptions, PingResult};
+use std::process::Stdio;
+use std::time::Duration;
+
+/// 解析一行 C 语言环境的 ping 输出
+///
+/// 例如 `64 bytes from 127.0.0.1: icmp_seq=1 ttl=64 time=0.045 ms`、`no answer yet for icmp_seq=2`、
+/// `Request timeout for icmp_seq 3`。与 pinger 的解析器一样，
+/// 标题行、统计摘要等不对应单个探测的行返回 None。
+/// 探测出错的行（见 `is_probe_error`）返回 `Unknown`，例如禁止分片时的"需要分片"消息。
+pub fn parse_line(line: String) -> Option<PingResult> {
+    if line.starts_with("no answer yet") || line.starts_with("Request timeout") {
+        return Some(PingResult::Timeout(line));
+    }
+    if line.contains(" bytes from ") {
+        if let Some(rtt) = reply_time(&line) {
+            return Some(PingResult::Pong(rtt, line));
+        }
+    }
+    is_probe_error(&line).then_some(PingResult::Unknown(line))
+}
+
+/// 是否是报告单个探测出错的行
+///
+/// 例如路由器返回的 ICMP 差错 `From 10.0.0.1 icmp_seq=1 Frag needed and DF set (mtu = 1400)`、
+/// `36 bytes from 10.0.0.1: frag needed and DF set (MTU 1400)`，本机的 `ping: local error: message too long, mtu=1500`，
+/// 以及 Windows 的 `Packet needs to be fragmented but DF set.`。
+fn is_probe_error(line: &str) -> bool {
+    let lower = line.to_lowercase();
+    line.starts_with("From ")
+        || lower.contains(" bytes from ")
+        || lower.contains("message too long")
+        || lower.contains("needs to be fragmented")
+}
+
+/// 解析 ping 进程输出的一行
+///
+/// Windows 的 ping.exe 不受 `LC_ALL` 影响，英文格式无法识别时按本地化格式兜底。
+fn parse_output(line: String) -> Option<PingResult> {
+    #[cfg(windows)]
+    {
+        use crate::utils::locale::{parse_localized_line, LocalizedLine};
+        if !is_probe_error(&line) {
+            match parse_localized_line(&line) {
+                Some(LocalizedLine::Pong(rtt_ms)) => {
+                    let rtt = Duration::try_from_secs_f64(rtt_ms / 1000.0).unwrap_or_default();
+                    return Some(PingResult::Pong(rtt, line));
+                }
+                Some(LocalizedLine::Timeout) => return Some(PingResult::Timeout(line)),
+                None => {}
+            }
+        }
+    }
+    parse_line(line)
+}
+
+/// 提取响应行中 `time=` 之后的延迟
+fn reply_time(line: &str) -> Option<Duration> {
+    let (_, rest) = line.split_once("time=")?;
+    let number_len = rest
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(rest.len());
+    let rtt_ms: f64 = rest.get(..number_len)?.parse().ok()?;
+    Duration::try_from_secs_f64(rtt_ms / 1000.0).ok()
+}
+
+/// 目标要求的 IP 版本，主机名未指定版本时为 None
+fn ip_version(target: &Target) -> Option<IPVersion> {
+    match target {
+        Target::IP(ip) if ip.is_ipv4() => Some(IPVersion::V4),
+        Target::IP(_) => Some(IPVersion::V6),
+        Target::Hostname { version, .. } => (*version != IPVersion::Any).then_some(*version),
+    }
+}
+
+/// 持续 ping 的程序名和参数（Linux）
+#[cfg(all(unix, not(any(target_os = "macos", target_os = "freebsd"))))]
+fn ping_args(options: &PingOptions) -> (&'static str, Vec<String>) {
+    let mut args = vec!["-O".to_string(), format!("-i{:.1}", options.interval.as_secs_f64())];
+    match ip_version(&options.target) {
+        Some(IPVersion::V4) => args.push("-4".to_string()),
+        Some(IPVersion::V6) => args.push("-6".to_string()),
+        _ => {}
+    }
+    if let Some(interface) = &options.interface {
+        args.push("-I".to_string());
+        args.push(interface.clone());
+    }
+    args.extend(options.raw_arguments.iter().flatten().cloned());
+    args.push(options.target.to_string());
+    ("ping", args)
+}
+
+/// 持续 ping 的程序名和参数（macOS / FreeBSD）
+#[cfg(any(target_os = "macos", target_os = "freebsd"))]
+fn ping_args(options: &PingOptions) -> (&'static str, Vec<String>) {
+    let ipv6 = ip_version(&options.target) == Some(IPVersion::V6);
+    let mut args = vec![format!("-i{:.1}", options.interval.as_secs_f64())];
+    if let Some(interface) = &options.interface {
+        args.push(if ipv6 { "-I" } else { "-b" }.to_string());
+        args.push(interface.clone());
+    }
+    // ping6 的禁止分片参数是 `-m`（不按最小 MTU 分片）
+    let raw_arguments = options.raw_arguments.iter().flatten();
+    args.extend(raw_arguments.map(|arg| {
+        if ipv6 && arg == "-D" {
+            "-m".to_string()
+        } else {
+            arg.clone()
+        }
+    }));
+    args.push(options.target.to_string());
+    (if ipv6 { "ping6" } else { "ping" }, args)
+}
+
+/// 持续 ping 的程序名和参数（Windows 的 ping.exe）
+#[cfg(windows)]
+fn ping_args(options: &PingOptions) -> (&'static str, Vec<String>) {
+    let version = ip_version(&options.target);
+    let ipv6 = version == Some(IPVersion::V6);
+    let mut args = vec!["-t".to_string()];
+    match version {
+        Some(IPVersion::V4) => args.push("-4".to_string()),
+        Some(IPVersion::V6) => args.push("-6".to_string()),
+        _ => {}
+    }
+    // `-f` 只支持 IPv4
+    let raw_arguments = options.raw_arguments.iter().flatten();
+    args.extend(raw_arguments.filter(|arg| !(ipv6 && *arg == "-f")).cloned());
+    args.push(options.target.to_string());
+    ("ping", args)
+}
+
+/// 启动持续 ping 的进程，结果通过标准库的通道返回
+///
+/// 只转发对应单个探测的行（见 `parse_line`），stdout 和 stderr 分别由单独的线程读取；
+/// 接收端被丢弃后，下一行输出到达时结束 ping 进程。
+/// 进程退出后发送 `PingExited`，其中包含退出状态和 stderr 中其余的输出。
+///
+/// # Errors
+/// ping 进程无法启动时返回 `PingCreationError::SpawnError`
+pub fn spawn(options: &PingOptions) -> Result<std::sync::mpsc::Receiver<PingResult>, PingCreationError> {
+    use std::io::{BufRead, BufReader};
+    use std::process::Command;
+
+    let (program, args) = ping_args(options);
+    let mut child = Command::new(program)
+        .args(args)
+        .env("LC_ALL", "C")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(PingCreationError::SpawnError)?;
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let (stdout, stderr) = (child.stdout.take(), child.stderr.take());
+    let stderr_tx = tx.clone();
+    let stderr_reader = std::thread::spawn(move || {
+        let mut rest = String::new();
+        for line in stderr.map(BufReader::new).into_iter().flat_map(BufRead::lines) {
+            let Ok(line) = line else { break };
+            match parse_output(line.clone()) {
+                Some(result) => {
+                    let _ = stderr_tx.send(result);
+                }
+                None => rest.extend([line.as_str(), "\n"]),
+            }
+        }
+        rest
+    });
+    std::thread::spawn(move || {
+        for line in stdout.map(BufReader::new).into_iter().flat_map(BufRead::lines) {
+            let Ok(line) = line else { break };
+            let Some(result) = parse_output(line) else { continue };
+            if tx.send(result).is_err() {
+                let _ = child.kill();
+                let _ = child.wait();
+                return;
+            }
+        }
+        let status = child.wait();
+        let stderr = stderr_reader.join().unwrap_or_default();
+        let _ = tx.send(exited(status, stderr));
+    });
+    Ok(rx)
+}
+
+/// 异步版本的 `spawn`，结果通过 tokio 的通道返回
+///
+/// 接收端被丢弃后立即结束 ping 进程。
+///
+/// # Errors
+/// ping 进程无法启动时返回 `PingCreationError::SpawnError`
+pub fn spawn_async(
+    options: &PingOptions,
+) -> Result<tokio::sync::mpsc::UnboundedReceiver<PingResult>, PingCreationError> {
+    use tokio::io::{AsyncBufReadExt, BufReader};
+    use tokio::process::Command;
+
+    let (program, args) = ping_args(options);
+    let mut child = Command::new(program)
+        .args(args)
+        .env("LC_ALL", "C")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(PingCreationError::SpawnError)?;
+
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+    let (stdout, stderr) = (child.stdout.take(), child.stderr.take());
+    tokio::spawn(async move {
+        let (Some(stdout), Some(stderr)) = (stdout, stderr) else {
+            return;
+        };
+        let mut stdout = BufReader::new(stdout).lines();
+        let mut stderr = BufReader::new(stderr).lines();
+        let (mut stdout_open, mut stderr_open) = (true, true);
+        let mut rest = String::new();
+        while stdout_open || stderr_open {
+            let result = tokio::select! {
+                line = stdout.next_line(), if stdout_open => {
+                    if let Ok(Some(line)) = line {
+                        parse_output(line)
+                    } else {
+                        stdout_open = false;
+                        None
+                    }
+                }
+                line = stderr.next_line(), if stderr_open => {
+                    if let Ok(Some(line)) = line {
+                        parse_output(line.clone()).or_else(|| {
+                            rest.extend([line.as_str(), "\n"]);
+                            None
+                        })
+                    } else {
+                        stderr_open = false;
+                        None
+                    }
+                }
+                // 接收端被丢弃，`child` 随任务结束被终止
+                () = tx.closed() => return,
+            };
+            if let Some(result) = result {
+                if tx.send(result).is_err() {
+                    return;
+                }
+            }
+        }
+        let _ = tx.send(exited(child.wait().await, rest));
+    });
+    Ok(rx)
+}
+
+/// 由 ping 进程的退出状态构造 `PingExited`
+fn exited(status: std::io::Result<std::process::ExitStatus>, stderr: String) -> PingResult {
+    match status {
+        Ok(status) => PingResult::PingExited(status, stderr),
+        Err(e) => PingResult::PingExited(std::process::ExitStatus::default(), e.to_string()),
+    }
+}
@@ -0,0 +1,66 @@
+//! 启动 ping 会话和 ICMP 探测的错误
+
+use crate::utils::concurrency::QueueTimeout;
+use pyo3::exceptions::{PyRuntimeError, PyTimeoutError};
+use pyo3::PyErr;
+use std::fmt;
+
+/// 启动 ping 会话失败的原因
+#[derive(Debug)]
+pub enum PingError {
+    /// ping 进程启动失败
+    Creation(pinger::PingCreationError),
+    /// 达到并发上限后排队超时
+    QueueTimeout(QueueTimeout),
+    /// 目标主机名解析失败
+    Resolution(String),
+    /// ICMP 套接字创建、发送或接收失败
+    Socket(std::io::Error),
+}
+
+impl fmt::Display for PingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Creation(e) => e.fmt(f),
+            Self::QueueTimeout(QueueTimeout(timeout)) => write!(
+                f,
+                "Timed out after {}ms waiting for a free ping slot",
+                timeout.as_millis()
+            ),
+            Self::Resolution(message) => write!(f, "Failed to resolve target: {message}"),
+            Self::Socket(e) => write!(f, "ICMP socket error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for PingError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Creation(e) => Some(e),
+            Self::Socket(e) => Some(e),
+            Self::QueueTimeout(_) | Self::Resolution(_) => None,
+        }
+    }
+}
+
+impl From<pinger::PingCreationError> for PingError {
+    fn from(e: pinger::PingCreationError) -> Self {
+        Self::Creation(e)
+    }
+}
+
+impl From<QueueTimeout> for PingError {
+    fn from(e: QueueTimeout) -> Self {
+        Self::QueueTimeout(e)
+    }
+}
+
+impl From<PingError> for PyErr {
+    fn from(e: PingError) -> Self {
+        match e {
+            PingError::Creation(_) => PyErr::new::<PyRuntimeError, _>(format!("Failed to start ping: {e}")),
+            PingError::QueueTimeout(_) => PyErr::new::<PyTimeoutError, _>(e.to_string()),
+            PingError::Resolution(_) | PingError::Socket(_) => PyErr::new::<PyRuntimeError, _>(e.to_string()),
+        }
+    }
+}
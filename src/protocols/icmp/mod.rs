@@ -7,15 +7,66 @@ pub mod ping;
 )]
 pub mod platform;
 
+pub mod command;
+pub mod error;
+pub mod pmtu;
+pub mod probe;
+pub mod resolve;
+pub mod session;
 pub mod stream;
+pub mod sweep;
+pub mod traceroute;
 
-use crate::types::options::DnsPreResolveOptions;
+use crate::types::options::{DnsPreResolveOptions, ResolveMode};
+use crate::utils::{concurrency, rate_limit};
+use error::PingError;
 use pinger::{PingOptions, PingResult};
+use std::net::IpAddr;
+use std::ops::{Deref, DerefMut};
 use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// ping 会话的接收端，持有会话的并发许可，被丢弃时释放许可
+///
+/// 解引用为内部的通道接收端，可以直接调用 `recv` 等方法。
+#[derive(Debug)]
+pub struct SessionReceiver<R> {
+    receiver: R,
+    _permit: Option<concurrency::SessionPermit>,
+}
+
+impl<R> SessionReceiver<R> {
+    fn new(receiver: R, permit: Option<concurrency::SessionPermit>) -> Self {
+        Self {
+            receiver,
+            _permit: permit,
+        }
+    }
+}
+
+impl<R> Deref for SessionReceiver<R> {
+    type Target = R;
+
+    fn deref(&self) -> &R {
+        &self.receiver
+    }
+}
+
+impl<R> DerefMut for SessionReceiver<R> {
+    fn deref_mut(&mut self) -> &mut R {
+        &mut self.receiver
+    }
+}
+
+/// `execute_ping` 返回的接收端
+pub type PingReceiver = SessionReceiver<mpsc::Receiver<PingResult>>;
+
+/// `execute_ping_async` 返回的接收端
+pub type AsyncPingReceiver = SessionReceiver<tokio::sync::mpsc::UnboundedReceiver<PingResult>>;
 
 /// 执行ping操作的统一接口，返回标准库的通道
 ///
-/// 所有平台统一使用 pinger 库的实现
+/// Unix 上以 `LC_ALL=C` 直接启动系统 ping 命令（见 `command`），Windows 上使用 pinger 库的原生实现
 ///
 /// # 参数
 ///
@@ -33,6 +84,10 @@ use std::sync::mpsc;
 /// 其他类型的 `PingCreationError` (如 `UnknownPing`, `SpawnError`, `NotSupported`)
 /// 仍然会作为错误返回，因为它们表示环境问题而非目标问题。
 ///
+/// 启动 ping 进程前会等待全局速率限制（见 `set_global_rate_limit`），并在达到并发上限时排队
+/// （见 `set_max_concurrent_pings`），排队超时返回 `PingError::QueueTimeout`。
+/// 并发许可由返回的接收端持有，直到接收端被丢弃。
+///
 /// # 示例
 ///
 /// ```ignore
@@ -48,40 +103,52 @@ use std::sync::mpsc;
 /// let receiver = execute_ping(options.clone(), DnsPreResolveOptions::default())?;
 ///
 /// // 禁用 DNS 预解析
-/// let dns_opts = DnsPreResolveOptions { enable: false, timeout: None };
+/// let dns_opts = DnsPreResolveOptions { enable: false, ..DnsPreResolveOptions::default() };
 /// let receiver = execute_ping(options, dns_opts)?;
 /// # Ok(())
 /// # }
 /// ```
-pub fn execute_ping(
-    mut options: PingOptions,
-    dns_options: DnsPreResolveOptions,
-) -> Result<mpsc::Receiver<PingResult>, pinger::PingCreationError> {
+pub fn execute_ping(mut options: PingOptions, dns_options: DnsPreResolveOptions) -> Result<PingReceiver, PingError> {
     // 尝试预解析主机名，以避免 ping 命令解析超时或卡住
     if dns_options.enable {
-        if let pinger::target::Target::Hostname { .. } = &options.target {
-            let target = options.target.clone();
+        if let pinger::target::Target::Hostname { domain, version } = &options.target {
             let resolve_timeout = dns_options.timeout.unwrap_or(options.interval);
 
-            let (tx_resolve, rx_resolve) = std::sync::mpsc::channel();
-            std::thread::spawn(move || {
-                let result = pinger::utils::resolve_target(&target);
-                let _ = tx_resolve.send(result);
-            });
+            // 缓存命中时不需要启动解析线程
+            let cached = if dns_options.mode == ResolveMode::HappyEyeballs {
+                None
+            } else {
+                resolve::cached_first(domain, *version, dns_options.resolver.as_deref())
+            };
+            let resolved = cached.map_or_else(
+                || {
+                    let target = options.target.clone();
+                    let interface = options.interface.clone();
+                    let (tx_resolve, rx_resolve) = std::sync::mpsc::channel();
+                    std::thread::spawn(move || {
+                        let result = pyo3_async_runtimes::tokio::get_runtime().block_on(resolve_address(
+                            &target,
+                            &dns_options,
+                            interface,
+                            resolve_timeout,
+                        ));
+                        let _ = tx_resolve.send(result);
+                    });
+                    rx_resolve.recv_timeout(resolve_timeout)
+                },
+                Ok,
+            );
 
-            match rx_resolve.recv_timeout(resolve_timeout) {
+            match resolved {
                 Ok(Ok(ip)) => {
                     // 解析成功，更新 target 为 IP，避免 ping 命令再次解析
                     options.target = pinger::target::Target::IP(ip);
                 }
-                Ok(Err(e)) => {
+                Ok(Err(message)) => {
                     // 解析失败，直接返回 PingExited
                     let (tx, rx) = mpsc::channel();
-                    let _ = tx.send(PingResult::PingExited(
-                        std::process::ExitStatus::default(),
-                        e.to_string(),
-                    ));
-                    return Ok(rx);
+                    let _ = tx.send(PingResult::PingExited(std::process::ExitStatus::default(), message));
+                    return Ok(SessionReceiver::new(rx, None));
                 }
                 Err(_) => {
                     // 解析超时，直接返回 PingExited
@@ -90,14 +157,18 @@ pub fn execute_ping(
                         std::process::ExitStatus::default(),
                         "Hostname resolution timeout".to_string(),
                     ));
-                    return Ok(rx);
+                    return Ok(SessionReceiver::new(rx, None));
                 }
             }
         }
     }
 
-    match pinger::ping(options) {
-        Ok(rx) => Ok(rx),
+    // 启动 ping 进程前等待并发许可和全局速率限制
+    let permit = concurrency::acquire_blocking()?;
+    rate_limit::acquire_blocking();
+
+    match start_ping(&options) {
+        Ok(rx) => Ok(SessionReceiver::new(rx, Some(permit))),
         Err(e @ pinger::PingCreationError::HostnameError(_)) => {
             // 主机名解析失败，创建一个返回错误结果的接收器
             let (tx, rx) = mpsc::channel();
@@ -105,15 +176,15 @@ pub fn execute_ping(
                 std::process::ExitStatus::default(),
                 e.to_string(),
             ));
-            Ok(rx)
+            Ok(SessionReceiver::new(rx, None))
         }
-        Err(e) => Err(e), // 其他错误继续传播
+        Err(e) => Err(e.into()), // 其他错误继续传播
     }
 }
 
 /// 异步执行ping操作，返回 tokio 异步通道
 ///
-/// 所有平台统一使用 pinger 库的实现
+/// Unix 上以 `LC_ALL=C` 直接启动系统 ping 命令（见 `command`），Windows 上使用 pinger 库的原生实现
 ///
 /// # 参数
 ///
@@ -131,6 +202,10 @@ pub fn execute_ping(
 /// 其他类型的 `PingCreationError` (如 `UnknownPing`, `SpawnError`, `NotSupported`)
 /// 仍然会作为错误返回，因为它们表示环境问题而非目标问题。
 ///
+/// 启动 ping 进程前会等待全局速率限制（见 `set_global_rate_limit`），并在达到并发上限时排队
+/// （见 `set_max_concurrent_pings`），排队超时返回 `PingError::QueueTimeout`。
+/// 并发许可由返回的接收端持有，直到接收端被丢弃。
+///
 /// # 示例
 ///
 /// ```ignore
@@ -147,7 +222,7 @@ pub fn execute_ping(
 /// let receiver = execute_ping_async(options.clone(), DnsPreResolveOptions::default()).await?;
 ///
 /// // 禁用 DNS 预解析
-/// let dns_opts = DnsPreResolveOptions { enable: false, timeout: None };
+/// let dns_opts = DnsPreResolveOptions { enable: false, ..DnsPreResolveOptions::default() };
 /// let receiver = execute_ping_async(options, dns_opts).await?;
 /// # Ok(())
 /// # }
@@ -155,40 +230,21 @@ pub fn execute_ping(
 pub async fn execute_ping_async(
     mut options: PingOptions,
     dns_options: DnsPreResolveOptions,
-) -> Result<tokio::sync::mpsc::UnboundedReceiver<PingResult>, pinger::PingCreationError> {
+) -> Result<AsyncPingReceiver, PingError> {
     // 尝试预解析主机名，以避免 ping 命令解析超时或卡住
-    if dns_options.enable {
-        if let pinger::target::Target::Hostname { .. } = &options.target {
-            let resolve_timeout = dns_options.timeout.unwrap_or(options.interval);
-            match tokio::time::timeout(resolve_timeout, pinger::utils::resolve_target_async(&options.target)).await {
-                Ok(Ok(ip)) => {
-                    // 解析成功，更新 target 为 IP，避免 ping 命令再次解析
-                    options.target = pinger::target::Target::IP(ip);
-                }
-                Ok(Err(e)) => {
-                    // 解析失败，直接返回 PingExited
-                    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
-                    let _ = tx.send(PingResult::PingExited(
-                        std::process::ExitStatus::default(),
-                        e.to_string(),
-                    ));
-                    return Ok(rx);
-                }
-                Err(_) => {
-                    // 解析超时，返回错误
-                    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
-                    let _ = tx.send(PingResult::PingExited(
-                        std::process::ExitStatus::default(),
-                        "Hostname resolution timeout".to_string(),
-                    ));
-                    return Ok(rx);
-                }
-            }
-        }
+    if let Err(message) = pre_resolve_async(&mut options, dns_options).await {
+        // 解析失败或超时，直接返回 PingExited
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let _ = tx.send(PingResult::PingExited(std::process::ExitStatus::default(), message));
+        return Ok(SessionReceiver::new(rx, None));
     }
 
-    match pinger::ping_async(options).await {
-        Ok(rx) => Ok(rx),
+    // 启动 ping 进程前等待并发许可和全局速率限制
+    let permit = concurrency::acquire().await?;
+    rate_limit::acquire().await;
+
+    match start_ping_async(options).await {
+        Ok(rx) => Ok(SessionReceiver::new(rx, Some(permit))),
         Err(e @ pinger::PingCreationError::HostnameError(_)) => {
             // 主机名解析失败，创建一个返回错误结果的接收器
             let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
@@ -196,8 +252,123 @@ pub async fn execute_ping_async(
                 std::process::ExitStatus::default(),
                 e.to_string(),
             ));
-            Ok(rx)
+            Ok(SessionReceiver::new(rx, None))
         }
-        Err(e) => Err(e), // 其他错误继续传播
+        Err(e) => Err(e.into()), // 其他错误继续传播
+    }
+}
+
+/// 启动 ping 进程：Unix 上直接启动系统 ping 命令，使其使用 C 语言环境输出
+#[cfg(unix)]
+fn start_ping(options: &PingOptions) -> Result<mpsc::Receiver<PingResult>, pinger::PingCreationError> {
+    command::spawn(options)
+}
+
+/// 启动 ping 会话：Windows 上使用 pinger 的原生实现（不解析命令输出），
+/// 需要设置负载大小或禁止分片时改为启动 ping.exe
+#[cfg(not(unix))]
+fn start_ping(options: &PingOptions) -> Result<mpsc::Receiver<PingResult>, pinger::PingCreationError> {
+    if options.raw_arguments.is_some() {
+        return command::spawn(options);
+    }
+    pinger::ping(options.clone())
+}
+
+/// 异步版本的 `start_ping`
+#[cfg(unix)]
+#[allow(clippy::unused_async)]
+async fn start_ping_async(
+    options: PingOptions,
+) -> Result<tokio::sync::mpsc::UnboundedReceiver<PingResult>, pinger::PingCreationError> {
+    command::spawn_async(&options)
+}
+
+/// 异步版本的 `start_ping`
+#[cfg(not(unix))]
+async fn start_ping_async(
+    options: PingOptions,
+) -> Result<tokio::sync::mpsc::UnboundedReceiver<PingResult>, pinger::PingCreationError> {
+    if options.raw_arguments.is_some() {
+        return command::spawn_async(&options);
+    }
+    pinger::ping_async(options).await
+}
+
+/// 按 DNS 预解析选项把 `options.target` 中的主机名解析为 IP
+///
+/// 未启用预解析或目标已经是 IP 时不做任何处理。解析超时默认为 `options.interval`。
+///
+/// # Errors
+/// 解析失败或超时时返回错误信息
+pub async fn pre_resolve_async(options: &mut PingOptions, dns_options: DnsPreResolveOptions) -> Result<(), String> {
+    if !dns_options.enable {
+        return Ok(());
+    }
+    if let pinger::target::Target::Hostname { .. } = &options.target {
+        let resolve_timeout = dns_options.timeout.unwrap_or(options.interval);
+        let address = resolve_address(
+            &options.target,
+            &dns_options,
+            options.interface.clone(),
+            resolve_timeout,
+        )
+        .await?;
+        // 解析成功，更新 target 为 IP，避免 ping 命令再次解析
+        options.target = pinger::target::Target::IP(address);
+    }
+    Ok(())
+}
+
+/// 按解析模式把主机名目标解析为单个地址，整个过程（包括 Happy Eyeballs 竞速）受 `timeout` 限制
+async fn resolve_address(
+    target: &pinger::target::Target,
+    dns_options: &DnsPreResolveOptions,
+    interface: Option<String>,
+    timeout: Duration,
+) -> Result<IpAddr, String> {
+    let pinger::target::Target::Hostname { domain, version } = target else {
+        return Err("target is not a hostname".to_string());
+    };
+    let started = Instant::now();
+    let resolver = dns_options.resolver.as_deref();
+    if dns_options.mode == ResolveMode::HappyEyeballs {
+        let addresses = resolve::interleave(resolve::lookup(domain, *version, resolver, true, timeout).await?);
+        return resolve::race(&addresses, interface, timeout.saturating_sub(started.elapsed()))
+            .await
+            .ok_or_else(|| format!("no addresses found for '{domain}'"));
+    }
+    resolve::lookup(domain, *version, resolver, false, timeout)
+        .await?
+        .first()
+        .copied()
+        .ok_or_else(|| format!("no addresses found for '{domain}'"))
+}
+
+/// 按 DNS 预解析逻辑把目标解析为 IP（用于需要 IP 地址的探测），`dns_timeout` 为 None 时使用 `timeout`
+///
+/// # Errors
+/// 解析失败或超时时返回 `PingError::Resolution`
+pub async fn resolve_target(
+    target: &str,
+    ipv4: bool,
+    ipv6: bool,
+    timeout: Duration,
+    dns_timeout: Option<Duration>,
+) -> Result<IpAddr, PingError> {
+    let interval_ms = u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX);
+    let mut options = crate::utils::conversion::create_ping_options(target, interval_ms, None, ipv4, ipv6);
+    let dns_options = DnsPreResolveOptions {
+        enable: true,
+        timeout: dns_timeout,
+        ..DnsPreResolveOptions::default()
+    };
+    pre_resolve_async(&mut options, dns_options)
+        .await
+        .map_err(PingError::Resolution)?;
+    match options.target {
+        pinger::target::Target::IP(address) => Ok(address),
+        pinger::target::Target::Hostname { .. } => Err(PingError::Resolution(format!(
+            "'{target}' was not resolved to an IP address"
+        ))),
     }
 }
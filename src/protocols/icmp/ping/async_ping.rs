@@ -1,11 +1,14 @@
+use crate::protocols::dns::resolver::DnsResolver;
 use crate::protocols::icmp::execute_ping_async;
-use crate::types::options::DnsPreResolveOptions;
+use crate::types::batch::PingResultBatch;
+use crate::types::options::{DnsPreResolveOptions, PacketOptions, ResolveMode};
 use crate::types::result::PingResult;
 use crate::utils::conversion::{create_ping_options, extract_target};
 use crate::utils::validation::{validate_interval_ms, validate_timeout_ms};
 use pyo3::exceptions::PyRuntimeError;
 use pyo3::prelude::*;
 use pyo3_async_runtimes::tokio::future_into_py;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use super::helpers::calculate_timeout_info;
@@ -19,6 +22,7 @@ pub struct AsyncPinger {
     ipv4: bool,
     ipv6: bool,
     dns_options: DnsPreResolveOptions,
+    packet: PacketOptions,
 }
 
 #[pymethods]
@@ -33,12 +37,17 @@ impl AsyncPinger {
     /// - `ipv6`: Force IPv6 (default: false)
     /// - `dns_pre_resolve`: Enable DNS pre-resolution (default: true)
     /// - `dns_resolve_timeout_ms`: DNS resolution timeout in milliseconds (default: None, uses `interval_ms`)
+    /// - `dns_resolver`: Custom DNS resolver for pre-resolution (default: None, uses the system resolver)
+    /// - `resolve_mode`: `first` (default) or `happy_eyeballs` (prefer IPv6 with IPv4 fallback, RFC 8305)
+    /// - `size`: ICMP payload size in bytes (default: None, uses the ping default)
+    /// - `dont_fragment`: Set the Don't Fragment flag (default: false)
     ///
     /// # Errors
-    /// - `PyValueError`: If `interval_ms` is negative, less than 100ms, or not a multiple of 100ms
+    /// - `PyValueError`: If `interval_ms` is negative, less than 100ms, not a multiple of 100ms, `resolve_mode` is invalid,
+    ///   or `size` is outside 16..=65500
     /// - `PyTypeError`: If the target cannot be converted to a string
     #[new]
-    #[pyo3(signature = (target, interval_ms=1000, interface=None, ipv4=false, ipv6=false, dns_pre_resolve=true, dns_resolve_timeout_ms=None))]
+    #[pyo3(signature = (target, interval_ms=1000, interface=None, ipv4=false, ipv6=false, dns_pre_resolve=true, dns_resolve_timeout_ms=None, dns_resolver=None, resolve_mode="first", size=None, dont_fragment=false))]
     pub fn new(
         target: &Bound<PyAny>,
         interval_ms: i64,
@@ -47,6 +56,10 @@ impl AsyncPinger {
         ipv6: bool,
         dns_pre_resolve: bool,
         dns_resolve_timeout_ms: Option<i64>,
+        dns_resolver: Option<DnsResolver>,
+        resolve_mode: &str,
+        size: Option<i64>,
+        dont_fragment: bool,
     ) -> PyResult<Self> {
         let target_str = extract_target(target)?;
 
@@ -70,7 +83,10 @@ impl AsyncPinger {
             dns_options: DnsPreResolveOptions {
                 enable: dns_pre_resolve,
                 timeout: dns_timeout,
+                resolver: dns_resolver.map(Arc::new),
+                mode: ResolveMode::single_from_name(resolve_mode)?,
             },
+            packet: PacketOptions::from_args(size, dont_fragment)?,
         })
     }
 
@@ -78,23 +94,24 @@ impl AsyncPinger {
     ///
     /// # Errors
     /// - `PyRuntimeError`: If the ping process fails to start or execute
+    /// - `PyTimeoutError`: If waiting for a free ping slot times out (see `set_max_concurrent_pings`)
     pub fn ping_once<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
         let target = self.target.clone();
         let interval_ms = self.interval_ms;
         let interface = self.interface.clone();
         let ipv4 = self.ipv4;
         let ipv6 = self.ipv6;
-        let dns_options = self.dns_options;
+        let dns_options = self.dns_options.clone();
+        let packet = self.packet;
 
         future_into_py(py, async move {
-            let options = create_ping_options(&target, interval_ms, interface, ipv4, ipv6);
+            let mut options = create_ping_options(&target, interval_ms, interface, ipv4, ipv6);
+            packet.apply(&mut options);
 
             let interval_duration = std::time::Duration::from_millis(interval_ms);
 
             // 获取异步通道
-            let mut receiver = execute_ping_async(options, dns_options)
-                .await
-                .map_err(|e| PyErr::new::<PyRuntimeError, _>(format!("Failed to start ping: {e}")))?;
+            let mut receiver = execute_ping_async(options, dns_options).await?;
 
             // 使用 interval 作为超时时间（单次 ping 的最大等待时间）
             match tokio::time::timeout(interval_duration, receiver.recv()).await {
@@ -121,6 +138,7 @@ impl AsyncPinger {
     /// # Errors
     /// - `PyValueError`: If `count` is not positive, or `timeout_ms` is invalid
     /// - `PyRuntimeError`: If the ping process fails to start or execute
+    /// - `PyTimeoutError`: If waiting for a free ping slot times out (see `set_max_concurrent_pings`)
     #[pyo3(signature = (count=4, timeout_ms=None))]
     pub fn ping_multiple<'py>(
         &self,
@@ -128,120 +146,166 @@ impl AsyncPinger {
         count: i32,
         timeout_ms: Option<i64>,
     ) -> PyResult<Bound<'py, PyAny>> {
+        let (pinger, count, timeout) = self.prepare_multiple(count, timeout_ms)?;
+
+        future_into_py(py, async move {
+            collect_multiple(pinger, count, timeout)
+                .await
+                .map(PingResultBatch::into_results)
+        })
+    }
+
+    /// 异步执行多次 ping，返回列式的 `PingResultBatch`
+    ///
+    /// # Errors
+    /// - `PyValueError`: If `count` is not positive, or `timeout_ms` is invalid
+    /// - `PyRuntimeError`: If the ping process fails to start or execute
+    /// - `PyTimeoutError`: If waiting for a free ping slot times out (see `set_max_concurrent_pings`)
+    #[pyo3(signature = (count=4, timeout_ms=None))]
+    pub fn ping_multiple_batch<'py>(
+        &self,
+        py: Python<'py>,
+        count: i32,
+        timeout_ms: Option<i64>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let (pinger, count, timeout) = self.prepare_multiple(count, timeout_ms)?;
+
+        future_into_py(py, collect_multiple(pinger, count, timeout))
+    }
+
+    /// Python `__repr__` method for string representation
+    pub fn __repr__(&self) -> String {
+        format!(
+            "AsyncPinger(target='{}', interval_ms={}, ipv4={}, ipv6={})",
+            self.target, self.interval_ms, self.ipv4, self.ipv6
+        )
+    }
+}
+
+impl AsyncPinger {
+    /// 验证多次 ping 的参数，并复制一份配置供异步任务使用
+    fn prepare_multiple(
+        &self,
+        count: i32,
+        timeout_ms: Option<i64>,
+    ) -> PyResult<(PingerConfig, usize, Option<Duration>)> {
         // 验证 count 参数
         let count = crate::utils::validation::validate_count(count, "count")?;
 
         // 验证 timeout_ms 参数
         let timeout = validate_timeout_ms(timeout_ms, self.interval_ms, "timeout_ms")?;
 
-        let target = self.target.clone();
-        let interval_ms = self.interval_ms;
-        let interface = self.interface.clone();
-        let ipv4 = self.ipv4;
-        let ipv6 = self.ipv6;
-        let dns_options = self.dns_options;
+        let config = PingerConfig {
+            target: self.target.clone(),
+            interval_ms: self.interval_ms,
+            interface: self.interface.clone(),
+            ipv4: self.ipv4,
+            ipv6: self.ipv6,
+            dns_options: self.dns_options.clone(),
+            packet: self.packet,
+        };
+        Ok((config, count, timeout))
+    }
+}
 
-        future_into_py(py, async move {
-            // 不传递 count 给底层 ping 命令，由 Rust 层控制接收数量
-            let options = create_ping_options(&target, interval_ms, interface, ipv4, ipv6);
-            let start_time = Instant::now();
+/// 异步任务使用的 `AsyncPinger` 配置副本
+struct PingerConfig {
+    target: String,
+    interval_ms: u64,
+    interface: Option<String>,
+    ipv4: bool,
+    ipv6: bool,
+    dns_options: DnsPreResolveOptions,
+    packet: PacketOptions,
+}
 
-            // 获取异步通道
-            let mut receiver = execute_ping_async(options, dns_options)
-                .await
-                .map_err(|e| PyErr::new::<PyRuntimeError, _>(format!("Failed to start ping: {e}")))?;
+/// 异步执行多次 ping，按接收时间记录结果
+async fn collect_multiple(pinger: PingerConfig, count: usize, timeout: Option<Duration>) -> PyResult<PingResultBatch> {
+    let interval_ms = pinger.interval_ms;
+    // 不传递 count 给底层 ping 命令，由 Rust 层控制接收数量
+    let mut options = create_ping_options(&pinger.target, interval_ms, pinger.interface, pinger.ipv4, pinger.ipv6);
+    pinger.packet.apply(&mut options);
+    let start_time = Instant::now();
 
-            let mut results = Vec::new();
-            let mut received_count = 0;
+    // 获取异步通道
+    let mut receiver = execute_ping_async(options, pinger.dns_options).await?;
 
-            let mut should_stop = false;
+    let mut results = PingResultBatch::default();
+    let mut received_count = 0;
 
-            while received_count < count && !should_stop {
-                // 计算剩余时间，支持 interval 浮动
-                let remaining_time = if let Some(timeout_duration) = timeout {
-                    let (should_timeout, remaining, timeout_result) =
-                        calculate_timeout_info(start_time, timeout_duration, interval_ms, count, received_count);
+    let mut should_stop = false;
 
-                    if should_timeout {
-                        // 已经过了超时时间
-                        if let Some(result) = timeout_result {
-                            results.push(result);
-                        }
-                        // 标记应该停止，但先尝试接收已经在通道中的结果
-                        should_stop = true;
-                        // 使用 0 超时来尝试接收已经准备好的结果
-                        Some(Duration::from_millis(0))
-                    } else {
-                        remaining
-                    }
-                } else {
-                    // 没有设置 timeout，无限等待
-                    None
-                };
+    while received_count < count && !should_stop {
+        // 计算剩余时间，支持 interval 浮动
+        let remaining_time = if let Some(timeout_duration) = timeout {
+            let (should_timeout, remaining, timeout_result) =
+                calculate_timeout_info(start_time, timeout_duration, interval_ms, count, received_count);
 
-                // 使用 timeout 等待下一个结果
-                let recv_result = if let Some(timeout_dur) = remaining_time {
-                    tokio::time::timeout(timeout_dur, receiver.recv()).await
-                } else {
-                    Ok(receiver.recv().await)
-                };
+            if should_timeout {
+                // 已经过了超时时间
+                if let Some(result) = timeout_result {
+                    results.push(result);
+                }
+                // 标记应该停止，但先尝试接收已经在通道中的结果
+                should_stop = true;
+                // 使用 0 超时来尝试接收已经准备好的结果
+                Some(Duration::from_millis(0))
+            } else {
+                remaining
+            }
+        } else {
+            // 没有设置 timeout，无限等待
+            None
+        };
+
+        // 使用 timeout 等待下一个结果
+        let recv_result = if let Some(timeout_dur) = remaining_time {
+            tokio::time::timeout(timeout_dur, receiver.recv()).await
+        } else {
+            Ok(receiver.recv().await)
+        };
 
-                match recv_result {
-                    Ok(Some(result)) => {
-                        let ping_result: PingResult = result.into();
+        match recv_result {
+            Ok(Some(result)) => {
+                let ping_result: PingResult = result.into();
 
-                        // 处理 PingExited
-                        if matches!(ping_result, PingResult::PingExited { .. }) {
-                            results.push(ping_result);
-                            break;
-                        }
+                // 处理 PingExited
+                if matches!(ping_result, PingResult::PingExited { .. }) {
+                    results.push(ping_result);
+                    break;
+                }
 
-                        results.push(ping_result);
-                        received_count += 1;
-                    }
-                    Ok(None) => break, // 通道关闭
-                    Err(_) => {
-                        // tokio::time::timeout 超时
-                        if should_stop {
-                            // 已经标记要停止，现在真的停止
-                            break;
-                        }
+                results.push(ping_result);
+                received_count += 1;
+            }
+            Ok(None) => break, // 通道关闭
+            Err(_) => {
+                // tokio::time::timeout 超时
+                if should_stop {
+                    // 已经标记要停止，现在真的停止
+                    break;
+                }
+
+                // 检查是否真的到达了总超时时间
+                if let Some(timeout_duration) = timeout {
+                    let (should_timeout, _, timeout_result) =
+                        calculate_timeout_info(start_time, timeout_duration, interval_ms, count, received_count);
 
-                        // 检查是否真的到达了总超时时间
-                        if let Some(timeout_duration) = timeout {
-                            let (should_timeout, _, timeout_result) = calculate_timeout_info(
-                                start_time,
-                                timeout_duration,
-                                interval_ms,
-                                count,
-                                received_count,
-                            );
-
-                            if should_timeout {
-                                // 已经到达总超时时间
-                                if let Some(result) = timeout_result {
-                                    results.push(result);
-                                }
-                                break;
-                            }
-                            // 否则继续循环
-                        } else {
-                            // 没有设置 timeout，不应该超时
-                            break;
+                    if should_timeout {
+                        // 已经到达总超时时间
+                        if let Some(result) = timeout_result {
+                            results.push(result);
                         }
+                        break;
                     }
+                    // 否则继续循环
+                } else {
+                    // 没有设置 timeout，不应该超时
+                    break;
                 }
             }
-
-            Ok(results)
-        })
+        }
     }
 
-    /// Python `__repr__` method for string representation
-    pub fn __repr__(&self) -> String {
-        format!(
-            "AsyncPinger(target='{}', interval_ms={}, ipv4={}, ipv6={})",
-            self.target, self.interval_ms, self.ipv4, self.ipv6
-        )
-    }
+    Ok(results)
 }
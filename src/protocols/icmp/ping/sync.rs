@@ -1,10 +1,14 @@
+use crate::protocols::dns::resolver::DnsResolver;
 use crate::protocols::icmp::execute_ping;
-use crate::types::options::DnsPreResolveOptions;
+use crate::types::batch::PingResultBatch;
+use crate::types::options::{DnsPreResolveOptions, PacketOptions, ResolveMode};
 use crate::types::result::PingResult;
 use crate::utils::conversion::{create_ping_options, extract_target};
 use crate::utils::validation::{validate_interval_ms, validate_timeout_ms};
+use pinger::PingOptions;
 use pyo3::exceptions::PyRuntimeError;
 use pyo3::prelude::*;
+use std::sync::Arc;
 
 use super::helpers::calculate_timeout_info;
 
@@ -17,6 +21,7 @@ pub struct Pinger {
     ipv4: bool,
     ipv6: bool,
     dns_options: DnsPreResolveOptions,
+    packet: PacketOptions,
 }
 
 #[pymethods]
@@ -31,12 +36,17 @@ impl Pinger {
     /// - `ipv6`: Force IPv6 (default: false)
     /// - `dns_pre_resolve`: Enable DNS pre-resolution (default: true)
     /// - `dns_resolve_timeout_ms`: DNS resolution timeout in milliseconds (default: None, uses `interval_ms`)
+    /// - `dns_resolver`: Custom DNS resolver for pre-resolution (default: None, uses the system resolver)
+    /// - `resolve_mode`: `first` (default) or `happy_eyeballs` (prefer IPv6 with IPv4 fallback, RFC 8305)
+    /// - `size`: ICMP payload size in bytes (default: None, uses the ping default)
+    /// - `dont_fragment`: Set the Don't Fragment flag (default: false)
     ///
     /// # Errors
-    /// - `PyValueError`: If `interval_ms` is negative, less than 100ms, or not a multiple of 100ms
+    /// - `PyValueError`: If `interval_ms` is negative, less than 100ms, not a multiple of 100ms, `resolve_mode` is invalid,
+    ///   or `size` is outside 16..=65500
     /// - `PyTypeError`: If the target cannot be converted to a string
     #[new]
-    #[pyo3(signature = (target, interval_ms=1000, interface=None, ipv4=false, ipv6=false, dns_pre_resolve=true, dns_resolve_timeout_ms=None))]
+    #[pyo3(signature = (target, interval_ms=1000, interface=None, ipv4=false, ipv6=false, dns_pre_resolve=true, dns_resolve_timeout_ms=None, dns_resolver=None, resolve_mode="first", size=None, dont_fragment=false))]
     pub fn new(
         target: &Bound<PyAny>,
         interval_ms: i64,
@@ -45,6 +55,10 @@ impl Pinger {
         ipv6: bool,
         dns_pre_resolve: bool,
         dns_resolve_timeout_ms: Option<i64>,
+        dns_resolver: Option<DnsResolver>,
+        resolve_mode: &str,
+        size: Option<i64>,
+        dont_fragment: bool,
     ) -> PyResult<Self> {
         let target_str = extract_target(target)?;
 
@@ -68,7 +82,10 @@ impl Pinger {
             dns_options: DnsPreResolveOptions {
                 enable: dns_pre_resolve,
                 timeout: dns_timeout,
+                resolver: dns_resolver.map(Arc::new),
+                mode: ResolveMode::single_from_name(resolve_mode)?,
             },
+            packet: PacketOptions::from_args(size, dont_fragment)?,
         })
     }
 
@@ -76,18 +93,13 @@ impl Pinger {
     ///
     /// # Errors
     /// - `PyRuntimeError`: If the ping process fails to start or execute
-    pub fn ping_once(&self) -> PyResult<PingResult> {
-        let options = create_ping_options(
-            &self.target,
-            self.interval_ms,
-            self.interface.clone(),
-            self.ipv4,
-            self.ipv6,
-        );
+    /// - `PyTimeoutError`: If waiting for a free ping slot times out (see `set_max_concurrent_pings`)
+    pub fn ping_once(&self, py: Python<'_>) -> PyResult<PingResult> {
+        let options = self.ping_options();
 
-        // 执行ping并等待第一个结果
-        let receiver = execute_ping(options, self.dns_options)
-            .map_err(|e| PyErr::new::<PyRuntimeError, _>(format!("Failed to start ping: {e}")))?;
+        // 执行ping并等待第一个结果（等待并发许可和令牌时释放 GIL）
+        let dns_options = self.dns_options.clone();
+        let receiver = py.detach(|| execute_ping(options, dns_options))?;
 
         // 使用 interval 作为超时时间
         let timeout = std::time::Duration::from_millis(self.interval_ms);
@@ -116,28 +128,68 @@ impl Pinger {
     /// # Errors
     /// - `PyValueError`: If `count` is not positive, or `timeout_ms` is invalid
     /// - `PyRuntimeError`: If the ping process fails to start or execute
+    /// - `PyTimeoutError`: If waiting for a free ping slot times out (see `set_max_concurrent_pings`)
     #[pyo3(signature = (count=4, timeout_ms=None))]
-    pub fn ping_multiple(&self, count: i32, timeout_ms: Option<i64>) -> PyResult<Vec<PingResult>> {
-        // 验证 count 参数
-        let count = crate::utils::validation::validate_count(count, "count")?;
+    pub fn ping_multiple(&self, py: Python<'_>, count: i32, timeout_ms: Option<i64>) -> PyResult<Vec<PingResult>> {
+        self.collect_multiple(py, count, timeout_ms)
+            .map(PingResultBatch::into_results)
+    }
 
-        // 验证 timeout_ms 参数
-        let timeout = validate_timeout_ms(timeout_ms, self.interval_ms, "timeout_ms")?;
+    /// 同步执行多次 ping，返回列式的 `PingResultBatch`
+    ///
+    /// # Errors
+    /// - `PyValueError`: If `count` is not positive, or `timeout_ms` is invalid
+    /// - `PyRuntimeError`: If the ping process fails to start or execute
+    /// - `PyTimeoutError`: If waiting for a free ping slot times out (see `set_max_concurrent_pings`)
+    #[pyo3(signature = (count=4, timeout_ms=None))]
+    pub fn ping_multiple_batch(
+        &self,
+        py: Python<'_>,
+        count: i32,
+        timeout_ms: Option<i64>,
+    ) -> PyResult<PingResultBatch> {
+        self.collect_multiple(py, count, timeout_ms)
+    }
 
-        // 不传递 count 给底层 ping 命令，由 Rust 层控制接收数量
-        let options = create_ping_options(
+    /// Python `__repr__` method for string representation
+    pub fn __repr__(&self) -> String {
+        format!(
+            "Pinger(target='{}', interval_ms={}, ipv4={}, ipv6={})",
+            self.target, self.interval_ms, self.ipv4, self.ipv6
+        )
+    }
+}
+
+impl Pinger {
+    /// 创建本实例的 `PingOptions`（包含负载大小和禁止分片参数）
+    fn ping_options(&self) -> PingOptions {
+        let mut options = create_ping_options(
             &self.target,
             self.interval_ms,
             self.interface.clone(),
             self.ipv4,
             self.ipv6,
         );
+        self.packet.apply(&mut options);
+        options
+    }
+
+    /// 执行多次 ping，按接收时间记录结果
+    fn collect_multiple(&self, py: Python<'_>, count: i32, timeout_ms: Option<i64>) -> PyResult<PingResultBatch> {
+        // 验证 count 参数
+        let count = crate::utils::validation::validate_count(count, "count")?;
+
+        // 验证 timeout_ms 参数
+        let timeout = validate_timeout_ms(timeout_ms, self.interval_ms, "timeout_ms")?;
+
+        // 不传递 count 给底层 ping 命令，由 Rust 层控制接收数量
+        let options = self.ping_options();
 
-        // 执行ping
-        let receiver = execute_ping(options, self.dns_options)
-            .map_err(|e| PyErr::new::<PyRuntimeError, _>(format!("Failed to start ping: {e}")))?;
+        // 执行ping（等待并发许可和令牌时释放 GIL）
+        let dns_options = self.dns_options.clone();
+        let receiver = py.detach(|| execute_ping(options, dns_options))?;
 
-        let mut results = Vec::new();
+        let mut results = PingResultBatch::default();
         let mut received_count = 0;
         let start_time = std::time::Instant::now();
 
@@ -215,12 +267,4 @@ impl Pinger {
 
         Ok(results)
     }
-
-    /// Python `__repr__` method for string representation
-    pub fn __repr__(&self) -> String {
-        format!(
-            "Pinger(target='{}', interval_ms={}, ipv4={}, ipv6={})",
-            self.target, self.interval_ms, self.ipv4, self.ipv6
-        )
-    }
 }
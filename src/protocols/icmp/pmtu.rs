@@ -0,0 +1,239 @@
+//! 路径 MTU 探测
+//!
+//! 与 `Pinger(size=..., dont_fragment=True)` 一样启动 ping（见 `PacketOptions`），
+//! 在 `[min_mtu, max_mtu]` 内二分查找仍能收到响应的最大包长。
+//! 收到"需要分片"消息时记录其中的 MTU 提示，并用它收紧搜索上界。
+
+use super::error::PingError;
+use super::{execute_ping_async, resolve_target};
+use crate::types::options::{DnsPreResolveOptions, PacketOptions};
+use crate::utils::conversion::create_ping_options;
+use pinger::PingResult;
+use pyo3::prelude::*;
+use std::net::IpAddr;
+use std::time::Duration;
+
+/// 探测时 ping 的发送间隔（毫秒），`timeout` 超过间隔时后续的 echo 请求也可以作为响应
+const PROBE_INTERVAL_MS: u64 = 1000;
+
+/// 路径 MTU 探测配置
+#[derive(Clone, Debug)]
+pub struct PmtuConfig {
+    /// 搜索下界（包含 IP 头部的包长）
+    pub min_mtu: u32,
+    /// 搜索上界（包含 IP 头部的包长）
+    pub max_mtu: u32,
+    /// 单次探测的超时时间（支持小于 1 秒）
+    pub timeout: Duration,
+    /// 每个包长在没有响应时的重试次数
+    pub retries: u32,
+    /// 可选的网络接口
+    pub interface: Option<String>,
+    /// 是否强制使用 IPv4
+    pub ipv4: bool,
+    /// 是否强制使用 IPv6
+    pub ipv6: bool,
+    /// DNS 解析超时（None 表示使用 `timeout`）
+    pub dns_timeout: Option<Duration>,
+}
+
+/// 路径 MTU 探测结果
+#[pyclass(module = "ping_rs._ping_rs", frozen)]
+#[derive(Debug, Clone)]
+pub struct PathMtuResult {
+    /// 目标主机名或 IP 地址
+    #[pyo3(get)]
+    pub target: String,
+    /// 目标解析后的 IP 地址
+    #[pyo3(get)]
+    pub address: String,
+    /// 能够通过的最大包长（包含 IP 和 ICMP 头部），下界也无法通过时为 None
+    #[pyo3(get)]
+    pub mtu: Option<u32>,
+    /// 对应的 ICMP 负载大小（即 ping 的 `-s` 参数）
+    #[pyo3(get)]
+    pub payload_size: Option<u32>,
+    /// 最近一次"需要分片"消息中报告的 MTU
+    #[pyo3(get)]
+    pub mtu_hint: Option<u32>,
+    /// 发送的探测数
+    #[pyo3(get)]
+    pub probes: u32,
+}
+
+#[pymethods]
+impl PathMtuResult {
+    /// Python `__repr__` method for string representation
+    pub fn __repr__(&self) -> String {
+        let fmt = |v: Option<u32>| v.map_or_else(|| "None".to_string(), |v| v.to_string());
+        format!(
+            "PathMtuResult(target='{}', address='{}', mtu={}, mtu_hint={}, probes={})",
+            self.target,
+            self.address,
+            fmt(self.mtu),
+            fmt(self.mtu_hint),
+            self.probes
+        )
+    }
+}
+
+/// IP 和 ICMP 头部的总长度
+fn header_len(address: IpAddr) -> u32 {
+    match address {
+        IpAddr::V4(_) => 20 + 8,
+        IpAddr::V6(_) => 40 + 8,
+    }
+}
+
+/// 单次探测的结果
+enum Probe {
+    /// 收到响应
+    Reply,
+    /// 本机或路由器报告需要分片，附带报告的 MTU
+    FragmentationNeeded(Option<u32>),
+    /// 超时或 ping 退出
+    NoReply,
+}
+
+/// 识别"需要分片"消息，返回带有其中报告的 MTU 的 `Probe::FragmentationNeeded`
+///
+/// 例如 `From 10.0.0.1 icmp_seq=1 Frag needed and DF set (mtu = 1400)`、
+/// `ping: local error: message too long, mtu=1500`、`Packet needs to be fragmented but DF set.`
+fn fragmentation_needed(line: &str) -> Option<Probe> {
+    let lower = line.to_lowercase();
+    [
+        "frag needed",
+        "message too long",
+        "needs to be fragmented",
+        "packet too big",
+    ]
+    .iter()
+    .any(|keyword| lower.contains(keyword))
+    .then(|| Probe::FragmentationNeeded(reported_mtu(&lower)))
+}
+
+/// 提取 `mtu` 之后的数字，例如 `mtu=1500`、`(mtu = 1400)`、`MTU 1400`
+fn reported_mtu(lower: &str) -> Option<u32> {
+    let (_, rest) = lower.rsplit_once("mtu")?;
+    let digits: String = rest
+        .trim_start_matches(|c: char| c == '=' || c == ':' || c.is_whitespace())
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
+}
+
+/// 二分查找的状态
+struct Search<'a> {
+    address: IpAddr,
+    config: &'a PmtuConfig,
+    probes: u32,
+    mtu_hint: Option<u32>,
+}
+
+impl Search<'_> {
+    /// 长度为 `mtu` 的包能否在禁止分片时通过，没有响应时重试
+    async fn fits(&mut self, mtu: u32) -> Result<bool, PingError> {
+        let packet = PacketOptions {
+            size: Some(mtu.saturating_sub(header_len(self.address))),
+            dont_fragment: true,
+        };
+        for _ in 0..=self.config.retries {
+            self.probes += 1;
+            match self.probe(packet).await? {
+                Probe::Reply => return Ok(true),
+                Probe::FragmentationNeeded(hint) => {
+                    if hint.is_some() {
+                        self.mtu_hint = hint;
+                    }
+                    return Ok(false);
+                }
+                Probe::NoReply => {}
+            }
+        }
+        Ok(false)
+    }
+
+    /// 启动 ping，等待 `timeout` 内的第一个响应或"需要分片"消息
+    async fn probe(&self, packet: PacketOptions) -> Result<Probe, PingError> {
+        let mut options = create_ping_options(
+            &self.address.to_string(),
+            PROBE_INTERVAL_MS,
+            self.config.interface.clone(),
+            self.address.is_ipv4(),
+            self.address.is_ipv6(),
+        );
+        packet.apply(&mut options);
+        let dns_options = DnsPreResolveOptions {
+            enable: false,
+            ..DnsPreResolveOptions::default()
+        };
+        let mut receiver = execute_ping_async(options, dns_options).await?;
+
+        let wait = async {
+            while let Some(result) = receiver.recv().await {
+                match result {
+                    PingResult::Pong(..) => return Probe::Reply,
+                    PingResult::Unknown(line) => {
+                        if let Some(probe) = fragmentation_needed(&line) {
+                            return probe;
+                        }
+                    }
+                    PingResult::PingExited(_, stderr) => {
+                        return fragmentation_needed(&stderr).unwrap_or(Probe::NoReply);
+                    }
+                    // 间隔内未收到响应，继续等到 `timeout`
+                    PingResult::Timeout(_) => {}
+                }
+            }
+            Probe::NoReply
+        };
+        // 接收端随之丢弃，ping 进程被终止
+        Ok(tokio::time::timeout(self.config.timeout, wait)
+            .await
+            .unwrap_or(Probe::NoReply))
+    }
+}
+
+/// 探测到 `target` 的路径 MTU
+///
+/// # Errors
+/// 目标解析失败、ping 进程无法启动或排队等待并发许可超时时返回 `PingError`
+pub async fn run_pmtu(target: String, config: PmtuConfig) -> Result<PathMtuResult, PingError> {
+    let address = resolve_target(&target, config.ipv4, config.ipv6, config.timeout, config.dns_timeout).await?;
+    let mut search = Search {
+        address,
+        config: &config,
+        probes: 0,
+        mtu_hint: None,
+    };
+
+    // 不变式：`low` 可以通过，大于 `high` 的包长无法通过
+    let (mut low, mut high) = (config.min_mtu, config.max_mtu);
+    let mtu = if search.fits(low).await? {
+        while low < high {
+            let mid = low + (high - low).div_ceil(2);
+            if search.fits(mid).await? {
+                low = mid;
+            } else {
+                high = mid - 1;
+                // 路由器报告的 MTU 说明更大的包都无法通过
+                if let Some(hint) = search.mtu_hint.filter(|hint| (low..high).contains(hint)) {
+                    high = hint;
+                }
+            }
+        }
+        Some(low)
+    } else {
+        None
+    };
+
+    Ok(PathMtuResult {
+        target,
+        address: address.to_string(),
+        mtu,
+        payload_size: mtu.map(|mtu| mtu.saturating_sub(header_len(address))),
+        mtu_hint: search.mtu_hint,
+        probes: search.probes,
+    })
+}
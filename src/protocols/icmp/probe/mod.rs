@@ -0,0 +1,85 @@
+//! 单次 ICMP 探测
+//!
+//! 直接通过 ICMP 套接字发送一个 echo 请求（可指定 TTL），识别目标的响应和中间路由器返回的 TTL 超时消息。
+//! 延迟从发送数据包前开始计时，不包含创建套接字等开销。
+//!
+//! - Unix：优先使用无需特权的 ICMP 数据报套接字（Linux 需要当前组在 `net.ipv4.ping_group_range` 内），
+//!   否则使用原始套接字（需要 root 或 `CAP_NET_RAW`），见 `unix`
+//! - Windows：使用 `IcmpSendEcho2` / `Icmp6SendEcho2`，见 `windows`
+
+use super::error::PingError;
+use crate::utils::{concurrency, rate_limit};
+use std::net::IpAddr;
+use std::time::Duration;
+
+#[cfg(unix)]
+mod unix;
+#[cfg(unix)]
+use unix as sys;
+
+#[cfg(windows)]
+mod windows;
+#[cfg(windows)]
+use windows as sys;
+
+/// 探测选项
+#[derive(Clone, Debug)]
+pub struct ProbeOptions {
+    /// 生存时间（IPv6 为跳数限制），None 使用系统默认值
+    pub ttl: Option<u8>,
+    /// 等待响应的超时时间
+    pub timeout: Duration,
+    /// 可选的网络接口（接口名或本机地址）
+    pub interface: Option<String>,
+}
+
+impl ProbeOptions {
+    /// 只指定 TTL 的探测（用于路由追踪）
+    pub fn with_ttl(ttl: u8, timeout: Duration, interface: Option<String>) -> Self {
+        Self {
+            ttl: Some(ttl),
+            timeout,
+            interface,
+        }
+    }
+}
+
+/// 一次探测的结果
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProbeReply {
+    /// 目标返回了 echo 响应
+    Reply {
+        /// 响应地址
+        from: IpAddr,
+        /// 往返延迟（毫秒）
+        rtt_ms: f64,
+    },
+    /// 中间路由器返回了 TTL 超时
+    TtlExceeded {
+        /// 路由器地址
+        from: IpAddr,
+        /// 往返延迟（毫秒）
+        rtt_ms: f64,
+    },
+    /// 超时未收到响应
+    Timeout,
+}
+
+/// 向 `target` 发送一次探测
+///
+/// 与 `execute_ping_async` 一样，发送前等待并发许可和全局速率限制。
+/// 套接字的收发是阻塞调用，在 tokio 的阻塞线程池中执行。
+///
+/// # Errors
+/// 无法创建 ICMP 套接字（例如权限不足）、发送失败，或排队等待并发许可超时时返回 `PingError`
+pub async fn probe(target: IpAddr, options: &ProbeOptions) -> Result<ProbeReply, PingError> {
+    let _permit = concurrency::acquire().await?;
+    rate_limit::acquire().await;
+
+    let options = options.clone();
+    tokio::task::spawn_blocking(move || sys::probe(target, &options))
+        .await
+        // 阻塞任务不会 panic，被取消时按超时处理
+        .unwrap_or(Ok(ProbeReply::Timeout))
+        .map_err(PingError::Socket)
+}
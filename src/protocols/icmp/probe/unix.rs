@@ -0,0 +1,390 @@
+//! Unix 上的 ICMP 套接字探测
+//!
+//! 无需特权的数据报套接字在 Linux 上由内核改写 echo 标识符，并只把对应本套接字的响应投递过来；
+//! 路由器返回的 TTL 超时消息不作为普通数据投递，需要打开 `IP_RECVERR` 后从错误队列读取。
+//! macOS 的数据报套接字和原始套接字一样接收本机所有 ICMP 消息（IPv4 包含 IP 头部），按标识符和序号过滤。
+
+use super::{ProbeOptions, ProbeReply};
+use socket2::{Domain, Protocol, SockAddr, Socket, Type};
+use std::io;
+use std::mem::MaybeUninit;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::time::Instant;
+
+/// `ICMPv4` echo 请求、echo 响应和 TTL 超时的消息类型
+const ECHO_REQUEST_V4: u8 = 8;
+const ECHO_REPLY_V4: u8 = 0;
+const TIME_EXCEEDED_V4: u8 = 11;
+/// `ICMPv6` echo 请求、echo 响应和跳数超限的消息类型
+const ECHO_REQUEST_V6: u8 = 128;
+const ECHO_REPLY_V6: u8 = 129;
+const TIME_EXCEEDED_V6: u8 = 3;
+
+/// IPv6 固定头部的长度
+const IPV6_HEADER_LEN: usize = 40;
+/// echo 请求的负载长度
+const PAYLOAD_LEN: usize = 32;
+/// 接收缓冲区的大小，足够容纳引用了原始数据包的差错消息
+const RECV_BUFFER_LEN: usize = 2048;
+
+/// 进程内递增的序号，区分并发的探测
+static SEQUENCE: AtomicU16 = AtomicU16::new(0);
+
+/// 发送一次探测并等待响应或 TTL 超时消息
+///
+/// # Errors
+/// 无法创建或配置 ICMP 套接字、发送或接收失败时返回 `io::Error`
+pub fn probe(target: IpAddr, options: &ProbeOptions) -> io::Result<ProbeReply> {
+    let icmp = IcmpSocket::open(target)?;
+    icmp.configure(options)?;
+
+    let identifier = u16::try_from(std::process::id() & 0xffff).unwrap_or_default();
+    let expected = Expected {
+        identifier: icmp.filters_by_identifier().then_some(identifier),
+        sequence: SEQUENCE.fetch_add(1, Ordering::Relaxed),
+    };
+    let packet = echo_request(icmp.ipv4, identifier, expected.sequence);
+
+    let started = Instant::now();
+    icmp.socket
+        .send_to(&packet, &SockAddr::from(SocketAddr::new(target, 0)))?;
+    let deadline = started + options.timeout;
+
+    let mut buf = [MaybeUninit::new(0u8); RECV_BUFFER_LEN];
+    loop {
+        let Some(remaining) = deadline
+            .checked_duration_since(Instant::now())
+            .filter(|remaining| !remaining.is_zero())
+        else {
+            return Ok(ProbeReply::Timeout);
+        };
+        icmp.socket.set_read_timeout(Some(remaining))?;
+        match icmp.socket.recv_from(&mut buf) {
+            Ok((len, from)) => {
+                let rtt_ms = started.elapsed().as_secs_f64() * 1000.0;
+                let Some(from) = from.as_socket().map(|from| from.ip()) else {
+                    continue;
+                };
+                let data = initialized(&buf).get(..len).unwrap_or_default();
+                if let Some(reply) = parse_message(data, icmp.ipv4, from, &expected, rtt_ms) {
+                    return Ok(reply);
+                }
+            }
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut | io::ErrorKind::Interrupted
+                ) => {}
+            // 错误队列中有差错消息时，接收会先返回对应的错误
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Err(_) if !icmp.raw => {
+                if let Some(reply) = icmp.read_error_queue(&expected, started)? {
+                    return Ok(reply);
+                }
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// 打开的 ICMP 套接字
+struct IcmpSocket {
+    socket: Socket,
+    /// 是否是原始套接字
+    raw: bool,
+    /// 是否是 IPv4
+    ipv4: bool,
+}
+
+impl IcmpSocket {
+    /// 优先打开数据报套接字，没有权限时改用原始套接字
+    fn open(target: IpAddr) -> io::Result<Self> {
+        let ipv4 = target.is_ipv4();
+        let (domain, protocol) = if ipv4 {
+            (Domain::IPV4, Protocol::ICMPV4)
+        } else {
+            (Domain::IPV6, Protocol::ICMPV6)
+        };
+        if let Ok(socket) = Socket::new(domain, Type::DGRAM, Some(protocol)) {
+            return Ok(Self {
+                socket,
+                raw: false,
+                ipv4,
+            });
+        }
+        match Socket::new(domain, Type::RAW, Some(protocol)) {
+            Ok(socket) => Ok(Self {
+                socket,
+                raw: true,
+                ipv4,
+            }),
+            Err(e) => Err(io::Error::new(
+                e.kind(),
+                format!(
+                    "cannot open an ICMP socket ({e}); run as root, grant CAP_NET_RAW, \
+                     or add the group to net.ipv4.ping_group_range"
+                ),
+            )),
+        }
+    }
+
+    /// 设置 TTL 和网络接口，Linux 的数据报套接字打开错误队列
+    fn configure(&self, options: &ProbeOptions) -> io::Result<()> {
+        if let Some(ttl) = options.ttl {
+            if self.ipv4 {
+                self.socket.set_ttl_v4(u32::from(ttl))?;
+            } else {
+                self.socket.set_unicast_hops_v6(u32::from(ttl))?;
+            }
+        }
+        if let Some(interface) = &options.interface {
+            bind_interface(&self.socket, interface, self.ipv4)?;
+        }
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        if !self.raw {
+            enable_error_queue(&self.socket, self.ipv4)?;
+        }
+        Ok(())
+    }
+
+    /// 收到的消息是否需要按标识符过滤
+    ///
+    /// Linux 的数据报套接字会改写标识符，且只投递本套接字的消息，只按序号匹配。
+    fn filters_by_identifier(&self) -> bool {
+        self.raw || !cfg!(any(target_os = "linux", target_os = "android"))
+    }
+
+    /// 从错误队列读取一条差错消息，是本次探测的 TTL 超时时返回 `TtlExceeded`
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn read_error_queue(&self, expected: &Expected, started: Instant) -> io::Result<Option<ProbeReply>> {
+        use socket2::{MaybeUninitSlice, MsgHdrMut};
+
+        let mut data = [MaybeUninit::new(0u8); RECV_BUFFER_LEN];
+        let mut control = [MaybeUninit::new(0u8); 256];
+        let (len, control_len) = {
+            let mut bufs = [MaybeUninitSlice::new(&mut data)];
+            let mut msg = MsgHdrMut::new().with_buffers(&mut bufs).with_control(&mut control);
+            match self.socket.recvmsg(&mut msg, libc::MSG_ERRQUEUE | libc::MSG_DONTWAIT) {
+                Ok(len) => (len, msg.control_len()),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(None),
+                Err(e) => return Err(e),
+            }
+        };
+        let rtt_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+        // 错误队列中的数据是引发差错的原始 echo 请求
+        let sent = initialized(&data).get(..len).unwrap_or_default();
+        if !expected.matches(sent) {
+            return Ok(None);
+        }
+        let control = initialized(&control).get(..control_len).unwrap_or_default();
+        Ok(time_exceeded_offender(control).map(|from| ProbeReply::TtlExceeded { from, rtt_ms }))
+    }
+}
+
+/// 期望的 echo 标识符（None 表示不检查）和序号
+struct Expected {
+    identifier: Option<u16>,
+    sequence: u16,
+}
+
+impl Expected {
+    /// `echo` 开头的 ICMP echo 头部是否属于本次探测
+    fn matches(&self, echo: &[u8]) -> bool {
+        let identifier_matches = self
+            .identifier
+            .is_none_or(|identifier| read_u16(echo, 4) == Some(identifier));
+        identifier_matches && read_u16(echo, 6) == Some(self.sequence)
+    }
+}
+
+/// 构造 echo 请求
+fn echo_request(ipv4: bool, identifier: u16, sequence: u16) -> Vec<u8> {
+    let kind = if ipv4 { ECHO_REQUEST_V4 } else { ECHO_REQUEST_V6 };
+    let mut packet = vec![kind, 0, 0, 0];
+    packet.extend_from_slice(&identifier.to_be_bytes());
+    packet.extend_from_slice(&sequence.to_be_bytes());
+    packet.resize(8 + PAYLOAD_LEN, 0);
+    // ICMPv6 的校验和包含伪首部，由内核计算
+    if ipv4 {
+        let checksum = checksum(&packet);
+        if let Some(field) = packet.get_mut(2..4) {
+            field.copy_from_slice(&checksum.to_be_bytes());
+        }
+    }
+    packet
+}
+
+/// 互联网校验和（RFC 1071）
+fn checksum(data: &[u8]) -> u16 {
+    let mut sum: u32 = data
+        .chunks(2)
+        .map(|chunk| match chunk {
+            [high, low] => u32::from(u16::from_be_bytes([*high, *low])),
+            [high] => u32::from(*high) << 8,
+            _ => 0,
+        })
+        .sum();
+    while sum > 0xffff {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !u16::try_from(sum).unwrap_or(u16::MAX)
+}
+
+/// 解析收到的 ICMP 消息，不属于本次探测时返回 None
+fn parse_message(data: &[u8], ipv4: bool, from: IpAddr, expected: &Expected, rtt_ms: f64) -> Option<ProbeReply> {
+    let (echo_reply, time_exceeded) = if ipv4 {
+        (ECHO_REPLY_V4, TIME_EXCEEDED_V4)
+    } else {
+        (ECHO_REPLY_V6, TIME_EXCEEDED_V6)
+    };
+    let message = if ipv4 { strip_ipv4_header(data)? } else { data };
+    let kind = *message.first()?;
+    if kind == echo_reply && expected.matches(message) {
+        return Some(ProbeReply::Reply { from, rtt_ms });
+    }
+    if kind == time_exceeded {
+        // 差错消息在 8 字节的头部之后引用了原始数据包：IP 头部和发送的 echo 请求
+        let quoted = message.get(8..)?;
+        let quoted = if ipv4 {
+            strip_ipv4_header(quoted)?
+        } else {
+            quoted.get(IPV6_HEADER_LEN..)?
+        };
+        if expected.matches(quoted) {
+            return Some(ProbeReply::TtlExceeded { from, rtt_ms });
+        }
+    }
+    None
+}
+
+/// 去掉开头的 IPv4 头部（如果有）
+///
+/// 原始套接字和 macOS 的数据报套接字收到的数据包含 IP 头部，Linux 的数据报套接字不包含。
+/// IP 头部的版本号是 4，而 ICMP 消息的类型（0、3、11 等）高 4 位为 0，可以据此区分。
+fn strip_ipv4_header(data: &[u8]) -> Option<&[u8]> {
+    let first = *data.first()?;
+    if first >> 4 != 4 {
+        return Some(data);
+    }
+    data.get(usize::from(first & 0x0f) * 4..)
+}
+
+/// 读取 `offset` 处的大端 u16
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset + 2)?;
+    Some(u16::from_be_bytes(bytes.try_into().ok()?))
+}
+
+/// 预先清零的缓冲区的内容
+fn initialized(buf: &[MaybeUninit<u8>]) -> &[u8] {
+    // SAFETY: 缓冲区创建时每个元素都已初始化，之后只会被写入有效字节；`MaybeUninit<u8>` 与 `u8` 的布局相同
+    unsafe { std::slice::from_raw_parts(buf.as_ptr().cast::<u8>(), buf.len()) }
+}
+
+/// 绑定到接口名或本机地址（与 ping 的 `-I` 一样）
+fn bind_interface(socket: &Socket, interface: &str, ipv4: bool) -> io::Result<()> {
+    if let Ok(address) = interface.parse::<IpAddr>() {
+        return socket.bind(&SockAddr::from(SocketAddr::new(address, 0)));
+    }
+    bind_device(socket, interface, ipv4)
+}
+
+/// 绑定到接口名（Linux）
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn bind_device(socket: &Socket, interface: &str, _ipv4: bool) -> io::Result<()> {
+    socket.bind_device(Some(interface.as_bytes()))
+}
+
+/// 绑定到接口名（macOS）
+#[cfg(target_os = "macos")]
+fn bind_device(socket: &Socket, interface: &str, ipv4: bool) -> io::Result<()> {
+    let name = std::ffi::CString::new(interface).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    // SAFETY: `name` 是以 NUL 结尾的有效字符串
+    let index = unsafe { libc::if_nametoindex(name.as_ptr()) };
+    let index = std::num::NonZeroU32::new(index)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("unknown interface '{interface}'")))?;
+    if ipv4 {
+        socket.bind_device_by_index_v4(Some(index))
+    } else {
+        socket.bind_device_by_index_v6(Some(index))
+    }
+}
+
+/// 其他平台只支持绑定本机地址
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos")))]
+fn bind_device(_socket: &Socket, interface: &str, _ipv4: bool) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("binding to interface '{interface}' is not supported on this platform, pass a local address instead"),
+    ))
+}
+
+/// 打开 `IP_RECVERR` / `IPV6_RECVERR`，让 ICMP 差错消息进入套接字的错误队列
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn enable_error_queue(socket: &Socket, ipv4: bool) -> io::Result<()> {
+    use std::os::fd::AsRawFd;
+
+    let (level, name) = if ipv4 {
+        (libc::SOL_IP, libc::IP_RECVERR)
+    } else {
+        (libc::SOL_IPV6, libc::IPV6_RECVERR)
+    };
+    let enable: libc::c_int = 1;
+    let len = libc::socklen_t::try_from(size_of::<libc::c_int>()).unwrap_or(4);
+    // SAFETY: 传入有效的套接字描述符，以及与 `len` 一致的 c_int 选项值
+    let ret = unsafe { libc::setsockopt(socket.as_raw_fd(), level, name, std::ptr::from_ref(&enable).cast(), len) };
+    if ret == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+/// 在错误队列的控制消息中查找 TTL 超时差错，返回报告差错的路由器地址
+///
+/// 控制消息的数据是 `sock_extended_err`，紧跟着报告者的 `sockaddr`（`SO_EE_OFFENDER`）。
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn time_exceeded_offender(control: &[u8]) -> Option<IpAddr> {
+    // cmsghdr：cmsg_len（size_t）、cmsg_level、cmsg_type（int），数据按 size_t 对齐
+    let align = size_of::<usize>();
+    let header_len = size_of::<libc::cmsghdr>();
+    let mut rest = control;
+    while rest.len() >= header_len {
+        let len = usize::from_ne_bytes(rest.get(..align)?.try_into().ok()?);
+        let level = libc::c_int::from_ne_bytes(rest.get(align..align + 4)?.try_into().ok()?);
+        let kind = libc::c_int::from_ne_bytes(rest.get(align + 4..align + 8)?.try_into().ok()?);
+        let data = rest.get(header_len..len)?;
+        if (level, kind) == (libc::SOL_IP, libc::IP_RECVERR) || (level, kind) == (libc::SOL_IPV6, libc::IPV6_RECVERR) {
+            // sock_extended_err：ee_errno（u32）、ee_origin、ee_type、ee_code、ee_pad、ee_info、ee_data
+            let (origin, icmp_type) = (*data.get(4)?, *data.get(5)?);
+            let is_time_exceeded = (origin, icmp_type) == (libc::SO_EE_ORIGIN_ICMP, TIME_EXCEEDED_V4)
+                || (origin, icmp_type) == (libc::SO_EE_ORIGIN_ICMP6, TIME_EXCEEDED_V6);
+            return if is_time_exceeded {
+                offender_address(data.get(16..)?)
+            } else {
+                None
+            };
+        }
+        rest = rest.get(len.next_multiple_of(align)..)?;
+    }
+    None
+}
+
+/// 解析 `sockaddr_in` / `sockaddr_in6` 中的地址
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn offender_address(sockaddr: &[u8]) -> Option<IpAddr> {
+    let family = libc::sa_family_t::from_ne_bytes(sockaddr.get(..2)?.try_into().ok()?);
+    match libc::c_int::from(family) {
+        libc::AF_INET => {
+            let octets: [u8; 4] = sockaddr.get(4..8)?.try_into().ok()?;
+            Some(IpAddr::from(octets))
+        }
+        libc::AF_INET6 => {
+            let octets: [u8; 16] = sockaddr.get(8..24)?.try_into().ok()?;
+            Some(IpAddr::from(octets))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,270 @@
+//! Windows 上的 ICMP 探测
+//!
+//! 使用 `iphlpapi` 的 `IcmpSendEcho2Ex`（IPv4）和 `Icmp6SendEcho2`（IPv6），不需要管理员权限。
+//! 中间路由器返回 TTL 超时时，响应的状态为 `IP_TTL_EXPIRED_TRANSIT`，地址为路由器地址。
+
+use super::{ProbeOptions, ProbeReply};
+use std::ffi::c_void;
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::time::Instant;
+
+/// 句柄类型
+type Handle = *mut c_void;
+
+/// `INVALID_HANDLE_VALUE`
+const INVALID_HANDLE: Handle = usize::MAX as Handle;
+/// `AF_INET6`
+const AF_INET6: u16 = 23;
+/// 响应状态：成功
+const IP_SUCCESS: u32 = 0;
+/// 响应状态：TTL 在传输中超时
+const IP_TTL_EXPIRED_TRANSIT: u32 = 11013;
+/// 响应状态：请求超时
+const IP_REQ_TIMED_OUT: u32 = 11010;
+
+/// echo 请求的负载
+const PAYLOAD: [u8; 32] = [0; 32];
+/// 响应缓冲区的大小（以 u64 计，保证对齐），足够容纳响应结构、回显的负载和 `IO_STATUS_BLOCK`
+const REPLY_BUFFER_WORDS: usize = 128;
+
+/// `IP_OPTION_INFORMATION`
+#[repr(C)]
+struct IpOptionInformation {
+    ttl: u8,
+    tos: u8,
+    flags: u8,
+    options_size: u8,
+    options_data: *mut u8,
+}
+
+/// `ICMP_ECHO_REPLY`
+#[repr(C)]
+#[allow(dead_code)] // 只读取部分字段，其余字段保持与系统定义一致的布局
+struct IcmpEchoReply {
+    address: u32,
+    status: u32,
+    round_trip_time: u32,
+    data_size: u16,
+    reserved: u16,
+    data: *mut c_void,
+    options: IpOptionInformation,
+}
+
+/// `IPV6_ADDRESS_EX`（按 1 字节对齐）
+#[repr(C, packed)]
+#[allow(dead_code)] // 同上
+struct Ipv6AddressEx {
+    port: u16,
+    flowinfo: u32,
+    addr: [u16; 8],
+    scope_id: u32,
+}
+
+/// `ICMPV6_ECHO_REPLY`
+#[repr(C)]
+#[allow(dead_code)] // 只读取部分字段，其余字段保持与系统定义一致的布局
+struct Icmpv6EchoReply {
+    address: Ipv6AddressEx,
+    status: u32,
+    round_trip_time: u32,
+}
+
+/// `SOCKADDR_IN6`
+#[repr(C)]
+struct SockaddrIn6 {
+    family: u16,
+    port: u16,
+    flowinfo: u32,
+    addr: [u8; 16],
+    scope_id: u32,
+}
+
+impl SockaddrIn6 {
+    fn new(address: Ipv6Addr) -> Self {
+        Self {
+            family: AF_INET6,
+            port: 0,
+            flowinfo: 0,
+            addr: address.octets(),
+            scope_id: 0,
+        }
+    }
+}
+
+#[link(name = "iphlpapi")]
+extern "system" {
+    fn IcmpCreateFile() -> Handle;
+    fn Icmp6CreateFile() -> Handle;
+    fn IcmpCloseHandle(handle: Handle) -> i32;
+    fn IcmpSendEcho2Ex(
+        handle: Handle,
+        event: Handle,
+        apc_routine: *mut c_void,
+        apc_context: *mut c_void,
+        source_address: u32,
+        destination_address: u32,
+        request_data: *const c_void,
+        request_size: u16,
+        request_options: *const IpOptionInformation,
+        reply_buffer: *mut c_void,
+        reply_size: u32,
+        timeout: u32,
+    ) -> u32;
+    fn Icmp6SendEcho2(
+        handle: Handle,
+        event: Handle,
+        apc_routine: *mut c_void,
+        apc_context: *mut c_void,
+        source_address: *const SockaddrIn6,
+        destination_address: *const SockaddrIn6,
+        request_data: *const c_void,
+        request_size: u16,
+        request_options: *const IpOptionInformation,
+        reply_buffer: *mut c_void,
+        reply_size: u32,
+        timeout: u32,
+    ) -> u32;
+}
+
+/// 自动关闭的 ICMP 句柄
+struct IcmpHandle(Handle);
+
+impl IcmpHandle {
+    fn open(ipv4: bool) -> io::Result<Self> {
+        // SAFETY: 无参数的系统调用，返回值在下面检查
+        let handle = unsafe {
+            if ipv4 {
+                IcmpCreateFile()
+            } else {
+                Icmp6CreateFile()
+            }
+        };
+        if handle == INVALID_HANDLE {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self(handle))
+    }
+}
+
+impl Drop for IcmpHandle {
+    fn drop(&mut self) {
+        // SAFETY: 句柄由 `open` 创建且只关闭一次
+        unsafe {
+            IcmpCloseHandle(self.0);
+        }
+    }
+}
+
+/// 发送一次探测并等待响应或 TTL 超时消息
+///
+/// # Errors
+/// 无法创建 ICMP 句柄、`interface` 不是本机地址，或发送失败时返回 `io::Error`
+pub fn probe(target: IpAddr, options: &ProbeOptions) -> io::Result<ProbeReply> {
+    // 只支持以本机地址指定源地址
+    let source = options
+        .interface
+        .as_deref()
+        .map(|interface| {
+            interface.parse::<IpAddr>().map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    format!(
+                        "binding to interface '{interface}' is not supported on Windows, pass a local address instead"
+                    ),
+                )
+            })
+        })
+        .transpose()?;
+    let request_options = IpOptionInformation {
+        ttl: options.ttl.unwrap_or(128),
+        tos: 0,
+        flags: 0,
+        options_size: 0,
+        options_data: std::ptr::null_mut(),
+    };
+    let timeout = u32::try_from(options.timeout.as_millis()).unwrap_or(u32::MAX).max(1);
+    let request_size = u16::try_from(PAYLOAD.len()).unwrap_or_default();
+    let mut reply = [0u64; REPLY_BUFFER_WORDS];
+    let reply_size = u32::try_from(size_of_val(&reply)).unwrap_or_default();
+
+    let handle = IcmpHandle::open(target.is_ipv4())?;
+    let started = Instant::now();
+    let (count, status, from) = match target {
+        IpAddr::V4(destination) => {
+            let source = match source {
+                Some(IpAddr::V4(source)) => source,
+                Some(IpAddr::V6(_)) => return Err(io::Error::from(io::ErrorKind::InvalidInput)),
+                None => Ipv4Addr::UNSPECIFIED,
+            };
+            // SAFETY: 所有指针都指向在调用期间有效的缓冲区，`reply_size` 与缓冲区大小一致；
+            // 未传入事件和回调，调用同步返回
+            let count = unsafe {
+                IcmpSendEcho2Ex(
+                    handle.0,
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                    u32::from_ne_bytes(source.octets()),
+                    u32::from_ne_bytes(destination.octets()),
+                    PAYLOAD.as_ptr().cast(),
+                    request_size,
+                    &raw const request_options,
+                    reply.as_mut_ptr().cast(),
+                    reply_size,
+                    timeout,
+                )
+            };
+            // SAFETY: 缓冲区按 8 字节对齐且大于 `IcmpEchoReply`，调用前已清零
+            let echo = unsafe { &*reply.as_ptr().cast::<IcmpEchoReply>() };
+            let from = IpAddr::from(Ipv4Addr::from(echo.address.to_ne_bytes()));
+            (count, echo.status, from)
+        }
+        IpAddr::V6(destination) => {
+            let source = match source {
+                Some(IpAddr::V6(source)) => source,
+                Some(IpAddr::V4(_)) => return Err(io::Error::from(io::ErrorKind::InvalidInput)),
+                None => Ipv6Addr::UNSPECIFIED,
+            };
+            let (source, destination) = (SockaddrIn6::new(source), SockaddrIn6::new(destination));
+            // SAFETY: 同上
+            let count = unsafe {
+                Icmp6SendEcho2(
+                    handle.0,
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                    &raw const source,
+                    &raw const destination,
+                    PAYLOAD.as_ptr().cast(),
+                    request_size,
+                    &raw const request_options,
+                    reply.as_mut_ptr().cast(),
+                    reply_size,
+                    timeout,
+                )
+            };
+            // SAFETY: 同上
+            let echo = unsafe { &*reply.as_ptr().cast::<Icmpv6EchoReply>() };
+            let words = echo.address.addr;
+            let from = IpAddr::from(Ipv6Addr::from(words.map(u16::from_be)));
+            (count, echo.status, from)
+        }
+    };
+    let rtt_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+    // 没有响应时返回 0，状态通过 GetLastError 报告；TTL 超时时响应缓冲区中仍有路由器地址
+    let status = if count > 0 {
+        status
+    } else {
+        io::Error::last_os_error()
+            .raw_os_error()
+            .and_then(|code| u32::try_from(code).ok())
+            .unwrap_or(IP_REQ_TIMED_OUT)
+    };
+    Ok(match status {
+        IP_SUCCESS if count > 0 => ProbeReply::Reply { from, rtt_ms },
+        IP_TTL_EXPIRED_TRANSIT => ProbeReply::TtlExceeded { from, rtt_ms },
+        _ => ProbeReply::Timeout,
+    })
+}
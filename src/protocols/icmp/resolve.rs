@@ -0,0 +1,249 @@
+//! 主机名的多地址解析
+//!
+//! 经过 DNS 缓存解析主机名的第一个或全部 A / AAAA 地址，按 RFC 8305（Happy Eyeballs v2）交替排列地址族并竞速选择地址，
+//! 或者并发 ping 每个地址（`resolve_mode="all"`）。
+
+use super::probe::{probe, ProbeOptions, ProbeReply};
+use super::sweep::{run_sweep, HostResult, SweepConfig, SweepResult};
+use crate::protocols::dns::resolver::{matches_version, DnsResolver};
+use crate::types::options::DnsPreResolveOptions;
+use crate::types::result::PingResult;
+use crate::utils::conversion::create_ping_options;
+use crate::utils::dns_cache;
+use pinger::target::{IPVersion, Target};
+use std::net::IpAddr;
+use std::time::Duration;
+use tokio::task::JoinSet;
+
+/// 相邻两次尝试的间隔（RFC 8305 推荐的 Connection Attempt Delay）
+const CONNECTION_ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+
+/// `getaddrinfo` 表示域名不存在或没有地址的错误信息（glibc、musl、macOS，小写）
+const NO_SUCH_HOST_MESSAGES: &[&str] = &[
+    "name or service not known",
+    "no address associated with",
+    "name does not resolve",
+    "name has no usable address",
+    "nodename nor servname provided",
+    "no such host",
+];
+
+/// Windows 的 `WSAHOST_NOT_FOUND` 和 `WSANO_DATA`
+const NO_SUCH_HOST_CODES: &[i32] = &[11001, 11004];
+
+/// 系统解析器的错误是否表示域名确实不存在或没有地址（其余错误视为临时失败）
+fn is_no_such_host(error: &std::io::Error) -> bool {
+    if error.kind() == std::io::ErrorKind::NotFound {
+        return true;
+    }
+    if error
+        .raw_os_error()
+        .is_some_and(|code| NO_SUCH_HOST_CODES.contains(&code))
+    {
+        return true;
+    }
+    let message = error.to_string().to_lowercase();
+    NO_SUCH_HOST_MESSAGES.iter().any(|m| message.contains(m))
+}
+
+/// 把系统解析器的错误转换为解析结果，只有域名不存在时才可以缓存
+fn system_error(error: &std::io::Error, message: String) -> Outcome {
+    if is_no_such_host(error) {
+        Outcome::NotFound(message, None)
+    } else {
+        Outcome::Failed(message)
+    }
+}
+
+/// 一次未经缓存的解析的结果
+enum Outcome {
+    /// 解析到地址，以及 TTL（如果有）
+    Found(Vec<IpAddr>, Option<Duration>),
+    /// 确定没有地址（可以缓存），以及否定缓存 TTL（如果有）
+    NotFound(String, Option<Duration>),
+    /// 临时失败，例如服务器故障或网络不可用（不缓存）
+    Failed(String),
+}
+
+/// 不经过缓存解析主机名，`all` 为 false 时系统解析器只返回第一个地址
+async fn lookup_uncached(
+    domain: &str,
+    version: IPVersion,
+    resolver: Option<&DnsResolver>,
+    all: bool,
+    timeout: Duration,
+) -> Outcome {
+    let not_found = || format!("no addresses found for '{domain}'");
+    if let Some(resolver) = resolver {
+        return match resolver.lookup(domain, version, timeout).await {
+            Ok(lookup) if lookup.addresses.is_empty() => Outcome::NotFound(not_found(), lookup.ttl),
+            Ok(lookup) => Outcome::Found(lookup.addresses, lookup.ttl),
+            Err(e) => Outcome::Failed(e),
+        };
+    }
+    if !all {
+        let target = Target::Hostname {
+            domain: domain.to_string(),
+            version,
+        };
+        return match pinger::utils::resolve_target_async(&target).await {
+            Ok(address) => Outcome::Found(vec![address], None),
+            Err(e) => system_error(&e, e.to_string()),
+        };
+    }
+    match tokio::net::lookup_host((domain, 0)).await {
+        Ok(addresses) => {
+            let addresses: Vec<_> = addresses
+                .map(|address| address.ip())
+                .filter(|&address| matches_version(address, version))
+                .collect();
+            if addresses.is_empty() {
+                Outcome::NotFound(not_found(), None)
+            } else {
+                Outcome::Found(addresses, None)
+            }
+        }
+        Err(e) => system_error(&e, format!("failed to resolve '{domain}': {e}")),
+    }
+}
+
+/// 经过 DNS 缓存解析主机名，去重并保持解析器返回的顺序
+///
+/// `all` 为 false 时只需要第一个地址（使用系统解析器时只返回一个地址）。
+///
+/// # Errors
+/// 解析失败、超时或没有符合 `version` 的地址时返回错误信息
+pub async fn lookup(
+    domain: &str,
+    version: IPVersion,
+    resolver: Option<&DnsResolver>,
+    all: bool,
+    timeout: Duration,
+) -> Result<Vec<IpAddr>, String> {
+    let key = dns_cache::Key::new(domain, version, resolver, all);
+    if let Some(answer) = dns_cache::get(&key) {
+        return answer;
+    }
+
+    let outcome = tokio::time::timeout(timeout, lookup_uncached(domain, version, resolver, all, timeout))
+        .await
+        .map_err(|_| "Hostname resolution timeout".to_string())?;
+    let (answer, ttl) = match outcome {
+        Outcome::Found(mut addresses, ttl) => {
+            let mut seen = Vec::with_capacity(addresses.len());
+            addresses.retain(|address| {
+                let new = !seen.contains(address);
+                seen.push(*address);
+                new
+            });
+            (Ok(addresses), ttl)
+        }
+        Outcome::NotFound(message, ttl) => (Err(message), ttl),
+        Outcome::Failed(message) => return Err(message),
+    };
+    dns_cache::insert(key, answer.clone(), ttl);
+    answer
+}
+
+/// 查找缓存中主机名的第一个地址，未缓存时返回 None
+pub fn cached_first(
+    domain: &str,
+    version: IPVersion,
+    resolver: Option<&DnsResolver>,
+) -> Option<Result<IpAddr, String>> {
+    let answer = dns_cache::get(&dns_cache::Key::new(domain, version, resolver, false))?;
+    Some(answer.and_then(|addresses| {
+        addresses
+            .first()
+            .copied()
+            .ok_or_else(|| format!("no addresses found for '{domain}'"))
+    }))
+}
+
+/// 按 RFC 8305 第 4 节交替排列 IPv6 和 IPv4 地址，IPv6 在前，同一地址族内保持原有顺序
+pub fn interleave(addresses: Vec<IpAddr>) -> Vec<IpAddr> {
+    let total = addresses.len();
+    let (ipv6, ipv4): (Vec<_>, Vec<_>) = addresses.into_iter().partition(IpAddr::is_ipv6);
+    let (mut ipv6, mut ipv4) = (ipv6.into_iter(), ipv4.into_iter());
+    let mut sorted = Vec::with_capacity(total);
+    while sorted.len() < total {
+        sorted.extend(ipv6.next());
+        sorted.extend(ipv4.next());
+    }
+    sorted
+}
+
+/// 按顺序每隔 250ms 向下一个地址发送一次探测，返回最先响应的地址
+///
+/// 在 `timeout` 内没有地址响应时返回首选（第一个）地址，由后续的 ping 报告超时。
+pub async fn race(addresses: &[IpAddr], interface: Option<String>, timeout: Duration) -> Option<IpAddr> {
+    let preferred = *addresses.first()?;
+    if addresses.len() == 1 {
+        return Some(preferred);
+    }
+
+    let options = ProbeOptions {
+        ttl: None,
+        timeout,
+        interface,
+    };
+    let mut attempts = JoinSet::new();
+    for (index, &address) in addresses.iter().enumerate() {
+        let options = options.clone();
+        let delay = CONNECTION_ATTEMPT_DELAY.saturating_mul(u32::try_from(index).unwrap_or(u32::MAX));
+        attempts.spawn(async move {
+            tokio::time::sleep(delay).await;
+            let reply = probe(address, &options).await;
+            matches!(reply, Ok(ProbeReply::Reply { .. })).then_some(address)
+        });
+    }
+
+    // JoinSet 被丢弃时取消其余尝试
+    let winner = async {
+        while let Some(result) = attempts.join_next().await {
+            if let Ok(Some(address)) = result {
+                return Some(address);
+            }
+        }
+        None
+    };
+    Some(
+        tokio::time::timeout(timeout, winner)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or(preferred),
+    )
+}
+
+/// 解析 `target` 的所有地址并并发 ping 每个地址，结果按 RFC 8305 的地址顺序排列
+///
+/// 目标是 IP 地址或未启用预解析时只 ping 目标本身。解析失败时返回只包含目标本身和 `PingExited` 结果的扫描结果。
+pub async fn ping_all(target: &str, config: SweepConfig, dns_options: DnsPreResolveOptions) -> SweepResult {
+    let interval_ms = u64::try_from(config.timeout.as_millis()).unwrap_or(u64::MAX);
+    let options = create_ping_options(target, interval_ms, None, config.ipv4, config.ipv6);
+    let addresses = match &options.target {
+        Target::Hostname { domain, version } if dns_options.enable => {
+            let timeout = dns_options.timeout.unwrap_or(config.timeout);
+            match lookup(domain, *version, dns_options.resolver.as_deref(), true, timeout).await {
+                Ok(addresses) => interleave(addresses),
+                Err(message) => {
+                    let result = PingResult::PingExited {
+                        exit_code: -1,
+                        stderr: message,
+                    };
+                    return SweepResult {
+                        hosts: vec![HostResult::new(target.to_string(), vec![result])],
+                    };
+                }
+            }
+        }
+        _ => return run_sweep(vec![target.to_string()], config).await,
+    };
+
+    let config = SweepConfig {
+        concurrency: addresses.len(),
+        ..config
+    };
+    run_sweep(addresses.iter().map(ToString::to_string).collect(), config).await
+}
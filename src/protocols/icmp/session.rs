@@ -0,0 +1,123 @@
+//! 长时间运行的 ping 会话
+//!
+//! 持续 ping 单个目标：每次启动 ping 进程前单独解析主机名，
+//! ping 进程退出后按退避策略等待并重新启动，直到任务被取消。
+
+use super::{execute_ping_async, pre_resolve_async};
+use crate::protocols::dns::resolver::DnsResolver;
+use crate::types::options::{DnsPreResolveOptions, ResolveMode};
+use crate::types::result::PingResult;
+use crate::utils::backoff::BackoffPolicy;
+use crate::utils::conversion::create_ping_options;
+use crate::utils::jitter::Jitter;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 单个目标的 ping 配置
+#[derive(Clone, Debug)]
+pub struct TargetConfig {
+    /// 目标主机名或 IP 地址
+    pub target: String,
+    /// ping 间隔（毫秒）
+    pub interval_ms: u64,
+    /// 可选的网络接口
+    pub interface: Option<String>,
+    /// 是否强制使用 IPv4
+    pub ipv4: bool,
+    /// 是否强制使用 IPv6
+    pub ipv6: bool,
+    /// DNS 解析超时（默认为 ping 间隔）
+    pub dns_timeout: Option<Duration>,
+    /// 自定义 DNS 解析器（默认为 None，表示使用系统解析器）
+    pub resolver: Option<Arc<DnsResolver>>,
+    /// 多地址的处理方式（`First` 或 `HappyEyeballs`）
+    pub resolve_mode: ResolveMode,
+    /// ping 进程退出或 DNS 解析失败后的重启退避策略
+    pub backoff: BackoffPolicy,
+    /// 首次启动的随机偏移和每次启动时的间隔抖动
+    pub jitter: Jitter,
+}
+
+/// 会话事件
+#[derive(Clone, Debug)]
+pub enum SessionEvent {
+    /// ping 结果，启动失败时为 `PingExited`
+    Result(PingResult),
+    /// 主机名解析失败
+    DnsFailure,
+    /// ping 进程已退出，即将按退避策略等待后重启（DNS 解析失败或启动失败时不发送）
+    Restarting,
+}
+
+/// 持续 ping 目标，将每个事件交给 `on_event`
+///
+/// 只有在任务被取消（例如 `JoinHandle::abort`）时才会结束。
+/// 收到 `Pong` 或 `Timeout` 后连续重启次数清零，退避重新从 `initial` 开始。
+pub async fn run_session<F>(mut config: TargetConfig, mut on_event: F)
+where
+    F: FnMut(SessionEvent) + Send,
+{
+    let interval = Duration::from_millis(config.interval_ms);
+    let mut attempt: u32 = 0;
+
+    // 随机推迟首次启动，避免大量目标同步探测
+    tokio::time::sleep(config.jitter.start_delay()).await;
+
+    loop {
+        let mut options = create_ping_options(
+            &config.target,
+            config.jitter.interval_ms(config.interval_ms),
+            config.interface.clone(),
+            config.ipv4,
+            config.ipv6,
+        );
+
+        let dns_options = DnsPreResolveOptions {
+            enable: true,
+            timeout: Some(config.dns_timeout.unwrap_or(interval)),
+            resolver: config.resolver.clone(),
+            mode: config.resolve_mode,
+        };
+        let resolved = pre_resolve_async(&mut options, dns_options).await.is_ok();
+        if !resolved {
+            on_event(SessionEvent::DnsFailure);
+        }
+
+        // 只有 ping 进程确实启动过才算一次重启
+        let mut started = false;
+        if resolved {
+            // 主机名已经解析，不再重复预解析
+            let dns_options = DnsPreResolveOptions {
+                enable: false,
+                ..DnsPreResolveOptions::default()
+            };
+            match execute_ping_async(options, dns_options).await {
+                Ok(mut receiver) => {
+                    started = true;
+                    while let Some(result) = receiver.recv().await {
+                        let result: PingResult = result.into();
+                        let exited = result.is_exited();
+                        if matches!(result, PingResult::Pong { .. } | PingResult::Timeout { .. }) {
+                            attempt = 0;
+                        }
+                        on_event(SessionEvent::Result(result));
+                        if exited {
+                            break;
+                        }
+                    }
+                }
+                Err(e) => on_event(SessionEvent::Result(PingResult::PingExited {
+                    exit_code: -1,
+                    stderr: e.to_string(),
+                })),
+            }
+        }
+
+        let delay = config.backoff.delay(attempt);
+        attempt = attempt.saturating_add(1);
+        if started {
+            on_event(SessionEvent::Restarting);
+        }
+        tokio::time::sleep(delay).await;
+    }
+}
@@ -1,7 +1,12 @@
-use crate::protocols::icmp::execute_ping_async;
-use crate::types::options::DnsPreResolveOptions;
+use super::RestartState;
+use crate::protocols::dns::resolver::DnsResolver;
+use crate::protocols::icmp::{execute_ping_async, AsyncPingReceiver};
+use crate::sinks::{dispatch_async, extract_sink, flush_all, ResultSink, StreamInfo};
+use crate::types::batch::PingResultBatch;
+use crate::types::options::{DnsPreResolveOptions, PacketOptions, ResolveMode, RestartPolicy};
 use crate::types::result::PingResult;
 use crate::utils::conversion::{create_ping_options, extract_target};
+use crate::utils::jitter::Jitter;
 use crate::utils::validation::validate_interval_ms;
 use pinger::PingOptions;
 use pinger::PingResult as RustPingResult;
@@ -9,31 +14,101 @@ use pyo3::exceptions::{PyRuntimeError, PyStopAsyncIteration};
 use pyo3::prelude::*;
 use pyo3_async_runtimes::tokio::future_into_py;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-async fn next_ping_stream(receiver: &mut tokio::sync::mpsc::UnboundedReceiver<RustPingResult>) -> PyResult<PingResult> {
+/// 接收下一个结果并交给输出端（包括退出信号），通道关闭时返回 None
+async fn next_ping_stream(
+    receiver: &mut tokio::sync::mpsc::UnboundedReceiver<RustPingResult>,
+    target: &str,
+    sinks: &[Arc<dyn ResultSink>],
+) -> Option<PingResult> {
     // ✅ 直接 await，无需 spawn_blocking
-    match receiver.recv().await {
-        Some(result) => {
-            let ping_result: PingResult = result.into();
-
-            // 如果是退出信号，跳出循环
-            if matches!(ping_result, PingResult::PingExited { .. }) {
-                Err(PyStopAsyncIteration::new_err("Stream exhausted"))
-            } else {
-                Ok(ping_result)
-            }
-        }
-        None => Err(PyStopAsyncIteration::new_err("Stream exhausted")),
-    }
+    let ping_result: PingResult = receiver.recv().await?.into();
+    dispatch_async(sinks, target, &ping_result).await;
+    Some(ping_result)
 }
 
 // 为 AsyncPingStream 创建内部状态结构
 struct AsyncPingStreamState {
+    target: String,
     options: PingOptions,
     dns_options: DnsPreResolveOptions,
-    receiver: Option<tokio::sync::mpsc::UnboundedReceiver<RustPingResult>>,
+    receiver: Option<AsyncPingReceiver>,
     max_count: Option<usize>,
     current_count: usize,
+    restart: RestartState,
+    /// ping 进程是否已启动过，退出且不再重启后不会重新启动
+    started: bool,
+    /// 首次启动 ping 进程的时间（启动偏移）
+    start_at: Instant,
+    /// 未加抖动的 ping 间隔，每次重启时重新抖动
+    interval_ms: u64,
+    jitter: Jitter,
+}
+
+impl AsyncPingStreamState {
+    /// 到达启动偏移或计划的重启时间后启动 ping 进程
+    ///
+    /// `wait` 为 false 时尚未到启动时间直接返回。重启失败时返回 `PingExited` 并重新安排重启。
+    ///
+    /// # Errors
+    /// - `PyStopAsyncIteration`: If the ping process has exited and will not restart
+    /// - `PyRuntimeError` / `PyTimeoutError`: If the first start fails
+    async fn start(&mut self, sinks: &[Arc<dyn ResultSink>], wait: bool) -> PyResult<Option<PingResult>> {
+        let start_at = match self.restart.pending() {
+            Some(restart_at) => restart_at,
+            None if self.started => return Err(PyStopAsyncIteration::new_err("Stream exhausted")),
+            None => self.start_at,
+        };
+        if !wait && start_at > Instant::now() {
+            return Ok(None);
+        }
+        tokio::time::sleep_until(start_at.into()).await;
+        let restarting = self.restart.take().is_some();
+        if restarting {
+            self.options.interval = Duration::from_millis(self.jitter.interval_ms(self.interval_ms));
+        }
+
+        match execute_ping_async(self.options.clone(), self.dns_options.clone()).await {
+            Ok(receiver) => {
+                self.receiver = Some(receiver);
+                self.started = true;
+                Ok(None)
+            }
+            Err(e) if !restarting => Err(e.into()),
+            Err(e) => {
+                let result = PingResult::PingExited {
+                    exit_code: -1,
+                    stderr: format!("Failed to restart ping: {e}"),
+                };
+                dispatch_async(sinks, &self.target, &result).await;
+                self.current_count += 1;
+                // 不再重启时下次迭代结束
+                self.restart.schedule();
+                Ok(Some(result))
+            }
+        }
+    }
+
+    /// 记录收到的结果，返回流是否随之结束
+    ///
+    /// 启用了自动重启时 ping 进程退出后安排重启（达到 `max_restarts` 时下次迭代结束），否则直接结束。
+    fn record(&mut self, result: &PingResult) -> bool {
+        self.restart.observe(result);
+        if matches!(result, PingResult::PingExited { .. }) {
+            self.receiver = None;
+            if !self.restart.schedule() && !self.restart.enabled() {
+                return true;
+            }
+        }
+        self.current_count += 1;
+        false
+    }
+
+    /// 是否已达到 `max_count`
+    fn exhausted(&self) -> bool {
+        self.max_count.is_some_and(|max| self.current_count >= max)
+    }
 }
 
 /// Asynchronous ping stream for continuous ping operations
@@ -43,17 +118,35 @@ struct AsyncPingStreamState {
 pub struct AsyncPingStream {
     // 使用 tokio::sync::Mutex 替换 std::sync::Mutex
     state: Arc<tokio::sync::Mutex<AsyncPingStreamState>>,
+    // 输出端单独加锁，避免 add_sink 等待正在进行的 __anext__
+    sinks: Arc<std::sync::Mutex<Vec<Arc<dyn ResultSink>>>>,
+    info: StreamInfo,
 }
 
 #[pymethods]
 impl AsyncPingStream {
     /// 创建新的 `AsyncPingStream` 实例
     ///
+    /// `restart_on_exit` 为 `True` 时，ping 进程退出后先返回 `PingExited` 结果，
+    /// 再按 `restart_backoff_ms` 起始、逐次翻倍（最多 60 秒）的退避重新启动；
+    /// `max_restarts` 限制连续重启次数（收到 `Pong` / `Timeout` 后清零）。
+    ///
+    /// `start_jitter_ms` 大于 0 时，ping 进程在创建后 `[0, start_jitter_ms]` 内的随机偏移后才启动；
+    /// `interval_jitter_pct` 在每次（重新）启动 ping 进程时将间隔随机调整 `±pct%`（取整到 100ms）。
+    /// 指定 `jitter_seed` 时随机结果可复现。
+    ///
+    /// `dns_resolver` 指定预解析使用的 `DnsResolver`，默认使用系统解析器；
+    /// `resolve_mode` 为 `happy_eyeballs` 时 IPv6 优先、IPv4 回退（RFC 8305）。
+    ///
+    /// `size` 指定 ICMP 负载大小（字节），`dont_fragment` 为 `True` 时设置禁止分片。
+    ///
     /// # Errors
-    /// - `PyValueError`: If `interval_ms` is negative, less than 100ms, not a multiple of 100ms, or `max_count` is too large
+    /// - `PyValueError`: If `interval_ms` is negative, less than 100ms, not a multiple of 100ms, `max_count` is too large,
+    ///   `restart_backoff_ms` is not positive, `resolve_mode` is invalid, a jitter argument is out of range,
+    ///   or `size` is outside 16..=65500
     /// - `PyTypeError`: If the target cannot be converted to a string
     #[new]
-    #[pyo3(signature = (target, interval_ms=1000, interface=None, ipv4=false, ipv6=false, max_count=None, dns_pre_resolve=true, dns_resolve_timeout_ms=None))]
+    #[pyo3(signature = (target, interval_ms=1000, interface=None, ipv4=false, ipv6=false, max_count=None, dns_pre_resolve=true, dns_resolve_timeout_ms=None, dns_resolver=None, resolve_mode="first", restart_on_exit=false, restart_backoff_ms=1000, max_restarts=None, start_jitter_ms=0, interval_jitter_pct=0.0, jitter_seed=None, size=None, dont_fragment=false))]
     pub fn new(
         target: &Bound<PyAny>,
         interval_ms: i64,
@@ -63,6 +156,16 @@ impl AsyncPingStream {
         max_count: Option<usize>,
         dns_pre_resolve: bool,
         dns_resolve_timeout_ms: Option<i64>,
+        dns_resolver: Option<DnsResolver>,
+        resolve_mode: &str,
+        restart_on_exit: bool,
+        restart_backoff_ms: i64,
+        max_restarts: Option<u32>,
+        start_jitter_ms: i64,
+        interval_jitter_pct: f64,
+        jitter_seed: Option<u64>,
+        size: Option<i64>,
+        dont_fragment: bool,
     ) -> PyResult<AsyncPingStream> {
         // 提取目标地址
         let target_str = extract_target(target)?;
@@ -88,30 +191,60 @@ impl AsyncPingStream {
             None
         };
 
+        let restart_policy = RestartPolicy::from_args(restart_on_exit, restart_backoff_ms, max_restarts)?;
+        let mut jitter = Jitter::new(start_jitter_ms, interval_jitter_pct, jitter_seed)?;
+
         // 创建 ping 选项（不传递 count 给底层 ping 命令）
         // max_count 参数保存在 state 中，在 __anext__ 迭代时由 Rust 层控制
-        let options = create_ping_options(&target_str, interval_ms_u64, interface, ipv4, ipv6);
+        let info = StreamInfo::new(&target_str, interface.clone(), ipv4, ipv6);
+        let mut options = create_ping_options(&target_str, jitter.interval_ms(interval_ms_u64), interface, ipv4, ipv6);
+        PacketOptions::from_args(size, dont_fragment)?.apply(&mut options);
 
         let dns_options = DnsPreResolveOptions {
             enable: dns_pre_resolve,
             timeout: dns_timeout,
+            resolver: dns_resolver.map(Arc::new),
+            mode: ResolveMode::single_from_name(resolve_mode)?,
         };
 
         // 创建内部状态
         let state = AsyncPingStreamState {
+            target: target_str,
             options,
             dns_options,
             receiver: None,
             max_count,
             current_count: 0,
+            restart: RestartState::new(restart_policy),
+            started: false,
+            start_at: Instant::now() + jitter.start_delay(),
+            interval_ms: interval_ms_u64,
+            jitter,
         };
 
         // 将状态包装到 Arc<tokio::sync::Mutex<>> 中
         Ok(AsyncPingStream {
             state: Arc::new(tokio::sync::Mutex::new(state)),
+            sinks: Arc::new(std::sync::Mutex::new(Vec::new())),
+            info,
         })
     }
 
+    /// 添加结果输出端（如 `Recorder`），之后收到的每个结果都会写入该输出端
+    ///
+    /// # Errors
+    /// - `PyTypeError`: If the object is not a supported sink type
+    /// - `PyRuntimeError`: If the sink list lock is poisoned
+    pub fn add_sink(&self, sink: &Bound<PyAny>) -> PyResult<()> {
+        let sink = extract_sink(sink)?;
+        sink.attach(&self.info);
+        self.sinks
+            .lock()
+            .map_err(|_| PyErr::new::<PyRuntimeError, _>("Failed to lock sinks"))?
+            .push(sink);
+        Ok(())
+    }
+
     /// Python async iterator protocol: return self
     pub fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
         slf
@@ -122,43 +255,106 @@ impl AsyncPingStream {
     /// Returns the next ping result or raises `StopAsyncIteration` when the stream is exhausted.
     ///
     /// # Errors
-    /// - `PyStopAsyncIteration`: When the stream is exhausted (`max_count` reached or ping process exited)
+    /// - `PyStopAsyncIteration`: When the stream is exhausted (`max_count` reached, or ping process exited and will not restart)
     /// - `PyRuntimeError`: If the ping process fails to start or execute
+    /// - `PyTimeoutError`: If waiting for a free ping slot times out (see `set_max_concurrent_pings`)
     pub fn __anext__<'py>(&mut self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
         // 获取状态的克隆，以便在异步闭包中使用
         let state_clone = self.state.clone();
+        let sinks = self
+            .sinks
+            .lock()
+            .map_err(|_| PyErr::new::<PyRuntimeError, _>("Failed to lock sinks"))?
+            .clone();
 
         future_into_py(py, async move {
             // 使用 tokio::sync::Mutex 的 .lock().await 异步锁定状态
             let mut state = state_clone.lock().await;
 
             // 检查是否达到最大数量
-            if let Some(max) = state.max_count {
-                if state.current_count >= max {
-                    state.receiver = None; // 清空接收器
-                    return Err(PyStopAsyncIteration::new_err("Stream exhausted"));
+            if state.exhausted() {
+                state.receiver = None; // 清空接收器
+                return Err(PyStopAsyncIteration::new_err("Stream exhausted"));
+            }
+
+            let state = &mut *state;
+            // 首次启动（等待启动偏移），或 ping 进程已退出且安排了重启
+            if state.receiver.is_none() {
+                if let Some(result) = state.start(&sinks, true).await? {
+                    return Ok(result);
                 }
             }
 
-            if let Some(receiver) = &mut state.receiver {
-                let result = next_ping_stream(receiver).await;
-                if result.is_ok() {
-                    state.current_count += 1;
+            let Some(receiver) = &mut state.receiver else {
+                return Err(PyStopAsyncIteration::new_err("Stream exhausted"));
+            };
+            let Some(result) = next_ping_stream(receiver, &state.target, &sinks).await else {
+                return Err(PyStopAsyncIteration::new_err("Stream exhausted"));
+            };
+            if state.record(&result) {
+                return Err(PyStopAsyncIteration::new_err("Stream exhausted"));
+            }
+            Ok(result)
+        })
+    }
+
+    /// 取出当前已到达的全部结果（最多 `max_items` 个），不等待新结果，返回列式的 `PingResultBatch`
+    ///
+    /// 到达启动时间后会启动（或重新启动）ping 进程。ping 进程退出且不再重启时，
+    /// `PingExited` 结果也包含在批次中，之后的迭代结束。
+    ///
+    /// # Errors
+    /// - `PyRuntimeError`: If the ping process fails to start
+    /// - `PyTimeoutError`: If waiting for a free ping slot times out (see `set_max_concurrent_pings`)
+    #[pyo3(signature = (max_items=None))]
+    pub fn drain<'py>(&self, py: Python<'py>, max_items: Option<usize>) -> PyResult<Bound<'py, PyAny>> {
+        let state_clone = self.state.clone();
+        let sinks = self
+            .sinks
+            .lock()
+            .map_err(|_| PyErr::new::<PyRuntimeError, _>("Failed to lock sinks"))?
+            .clone();
+
+        future_into_py(py, async move {
+            let mut state = state_clone.lock().await;
+            let state = &mut *state;
+            let mut batch = PingResultBatch::default();
+
+            while max_items.is_none_or(|max| batch.__len__() < max) && !state.exhausted() {
+                if state.receiver.is_none() {
+                    if state.started && state.restart.pending().is_none() {
+                        break;
+                    }
+                    if let Some(result) = state.start(&sinks, false).await? {
+                        batch.push(result);
+                        continue;
+                    }
                 }
-                result
-            } else {
-                // 如果接收器不存在，创建新的接收器
-                let mut receiver = execute_ping_async(state.options.clone(), state.dns_options)
-                    .await
-                    .map_err(|e| PyErr::new::<PyRuntimeError, _>(format!("Failed to start ping: {e}")))?;
-
-                let result = next_ping_stream(&mut receiver).await;
-                if result.is_ok() {
-                    state.current_count += 1;
+                let Some(receiver) = &mut state.receiver else {
+                    break;
+                };
+                let result: PingResult = match receiver.try_recv() {
+                    Ok(result) => result.into(),
+                    Err(tokio::sync::mpsc::error::TryRecvError::Empty) => break,
+                    Err(tokio::sync::mpsc::error::TryRecvError::Disconnected) => {
+                        state.receiver = None;
+                        break;
+                    }
+                };
+                dispatch_async(&sinks, &state.target, &result).await;
+                let ended = state.record(&result);
+                batch.push(result);
+                if ended {
+                    break;
                 }
-                state.receiver = Some(receiver);
-                result
             }
+            Ok(batch)
         })
     }
 }
+
+impl Drop for AsyncPingStream {
+    fn drop(&mut self) {
+        flush_all(&self.sinks.lock().unwrap_or_else(std::sync::PoisonError::into_inner));
+    }
+}
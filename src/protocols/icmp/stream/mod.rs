@@ -1,2 +1,68 @@
 pub mod async_stream;
+pub mod path;
 pub mod sync;
+
+use crate::types::options::RestartPolicy;
+use crate::types::result::PingResult;
+use std::time::Instant;
+
+/// 流的自动重启状态（也用于延迟首次启动）
+#[derive(Debug, Default)]
+pub struct RestartState {
+    policy: Option<RestartPolicy>,
+    /// 连续重启次数，收到 `Pong` / `Timeout` 后清零
+    attempt: u32,
+    /// 计划的重启时间
+    restart_at: Option<Instant>,
+}
+
+impl RestartState {
+    /// 使用重启策略创建，None 表示不自动重启
+    pub fn new(policy: Option<RestartPolicy>) -> Self {
+        Self {
+            policy,
+            attempt: 0,
+            restart_at: None,
+        }
+    }
+
+    /// 是否启用了自动重启
+    pub fn enabled(&self) -> bool {
+        self.policy.is_some()
+    }
+
+    /// 记录收到的结果，有效结果会清零连续重启次数
+    pub fn observe(&mut self, result: &PingResult) {
+        if matches!(result, PingResult::Pong { .. } | PingResult::Timeout { .. }) {
+            self.attempt = 0;
+        }
+    }
+
+    /// ping 进程退出时调用，按退避策略安排重启，返回是否会重启
+    pub fn schedule(&mut self) -> bool {
+        let Some(policy) = self.policy else {
+            return false;
+        };
+        if policy.max_restarts.is_some_and(|max| self.attempt >= max) {
+            return false;
+        }
+        self.restart_at = Some(Instant::now() + policy.backoff.delay(self.attempt));
+        self.attempt += 1;
+        true
+    }
+
+    /// 安排在指定时间（首次）启动，不计入重启次数
+    pub fn defer_until(&mut self, at: Instant) {
+        self.restart_at = Some(at);
+    }
+
+    /// 已安排的重启时间
+    pub fn pending(&self) -> Option<Instant> {
+        self.restart_at
+    }
+
+    /// 取出已安排的重启时间
+    pub fn take(&mut self) -> Option<Instant> {
+        self.restart_at.take()
+    }
+}
@@ -0,0 +1,410 @@
+//! 持续路径监控（类似 `mtr --report`）
+//!
+//! 每一轮向路径上的每一跳各发送一个 TTL 递增的 ICMP echo 探测（见 `probe`），
+//! 用与 `Monitor` 相同的滑动窗口统计累计每跳的丢包和延迟，每轮结束后产出一份报告。
+
+use crate::protocols::icmp::error::PingError;
+use crate::protocols::icmp::probe::{probe, ProbeOptions, ProbeReply};
+use crate::protocols::icmp::resolve_target;
+use crate::types::stats::RollingStats;
+use crate::utils::conversion::extract_target;
+use crate::utils::validation::i64_to_u64_positive;
+use pyo3::exceptions::{PyStopAsyncIteration, PyValueError};
+use pyo3::prelude::*;
+use pyo3_async_runtimes::tokio::future_into_py;
+use std::fmt::Write;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+/// 加锁，锁中毒时继续使用内部状态（持锁期间不会 panic）
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// 路径上一跳的统计
+#[pyclass(module = "ping_rs._ping_rs", frozen)]
+#[derive(Debug, Clone)]
+pub struct PathHopStats {
+    /// 跳数（从 1 开始）
+    #[pyo3(get)]
+    pub hop: u8,
+    /// 最近一次响应的地址，从未响应时为 None
+    #[pyo3(get)]
+    pub address: Option<String>,
+    /// 累计发送数
+    #[pyo3(get)]
+    pub packets_sent: u64,
+    /// 累计接收数
+    #[pyo3(get)]
+    pub packets_received: u64,
+    /// 窗口内的丢包率（百分比）
+    #[pyo3(get)]
+    pub loss_pct: Option<f64>,
+    /// 窗口内的最小延迟（毫秒）
+    #[pyo3(get)]
+    pub min_ms: Option<f64>,
+    /// 窗口内的平均延迟（毫秒）
+    #[pyo3(get)]
+    pub avg_ms: Option<f64>,
+    /// 窗口内的最大延迟（毫秒）
+    #[pyo3(get)]
+    pub max_ms: Option<f64>,
+    /// 窗口内延迟的标准差（毫秒）
+    #[pyo3(get)]
+    pub stddev_ms: Option<f64>,
+    /// 窗口内的抖动（毫秒）
+    #[pyo3(get)]
+    pub jitter_ms: Option<f64>,
+    /// 最近一次成功的延迟（毫秒）
+    #[pyo3(get)]
+    pub last_rtt_ms: Option<f64>,
+}
+
+#[pymethods]
+impl PathHopStats {
+    /// 累计丢失数
+    #[getter]
+    pub fn packets_lost(&self) -> u64 {
+        self.packets_sent - self.packets_received
+    }
+
+    /// Python `__repr__` method for string representation
+    pub fn __repr__(&self) -> String {
+        let fmt = |v: Option<f64>| v.map_or_else(|| "None".to_string(), |v| format!("{v:.3}"));
+        let address = self
+            .address
+            .as_ref()
+            .map_or_else(|| "None".to_string(), |address| format!("'{address}'"));
+        format!(
+            "PathHopStats(hop={}, address={address}, sent={}, received={}, loss_pct={}, avg_ms={})",
+            self.hop,
+            self.packets_sent,
+            self.packets_received,
+            fmt(self.loss_pct),
+            fmt(self.avg_ms)
+        )
+    }
+}
+
+/// 路径监控报告
+#[pyclass(module = "ping_rs._ping_rs", frozen)]
+#[derive(Debug, Clone)]
+pub struct PathReport {
+    /// 目标主机名或 IP 地址
+    #[pyo3(get)]
+    pub target: String,
+    /// 目标解析后的 IP 地址，尚未解析时为 None
+    #[pyo3(get)]
+    pub address: Option<String>,
+    /// 已完成的轮数
+    #[pyo3(get)]
+    pub cycles: u64,
+    /// 目标是否响应过
+    #[pyo3(get)]
+    pub reached: bool,
+    /// 每一跳的统计
+    #[pyo3(get)]
+    pub hops: Vec<PathHopStats>,
+}
+
+#[pymethods]
+impl PathReport {
+    /// Python `__repr__` method for string representation
+    pub fn __repr__(&self) -> String {
+        format!(
+            "PathReport(target='{}', cycles={}, hops={}, reached={})",
+            self.target,
+            self.cycles,
+            self.hops.len(),
+            if self.reached { "True" } else { "False" }
+        )
+    }
+
+    /// 按 `mtr --report` 的格式输出
+    pub fn __str__(&self) -> String {
+        let fmt = |v: Option<f64>| v.map_or_else(|| "-".to_string(), |v| format!("{v:.1}"));
+        let mut out = format!(
+            "HOST: {:<28} {:>6} {:>5} {:>7} {:>7} {:>7} {:>7} {:>7}\n",
+            self.target, "Loss%", "Snt", "Last", "Avg", "Best", "Wrst", "StDev"
+        );
+        for hop in &self.hops {
+            let _ = writeln!(
+                out,
+                "{:>3}.|-- {:<26} {:>5}% {:>5} {:>7} {:>7} {:>7} {:>7} {:>7}",
+                hop.hop,
+                hop.address.as_deref().unwrap_or("???"),
+                fmt(hop.loss_pct),
+                hop.packets_sent,
+                fmt(hop.last_rtt_ms),
+                fmt(hop.avg_ms),
+                fmt(hop.min_ms),
+                fmt(hop.max_ms),
+                fmt(hop.stddev_ms)
+            );
+        }
+        out
+    }
+}
+
+/// 单跳的累计状态
+#[derive(Debug)]
+struct HopState {
+    address: Option<IpAddr>,
+    stats: RollingStats,
+}
+
+/// 所有跳的统计
+#[derive(Debug)]
+struct PathState {
+    target: String,
+    address: Option<IpAddr>,
+    window: usize,
+    /// 按跳数排列（下标为 TTL - 1）
+    hops: Vec<HopState>,
+    /// 目标响应的最小 TTL
+    reached_at: Option<u8>,
+    cycles: u64,
+}
+
+impl PathState {
+    /// 记录一轮的探测结果
+    fn record(&mut self, replies: &[(u8, ProbeReply)]) {
+        for (ttl, reply) in replies {
+            let index = usize::from(*ttl) - 1;
+            while self.hops.len() <= index {
+                self.hops.push(HopState {
+                    address: None,
+                    stats: RollingStats::new(self.window),
+                });
+            }
+            let Some(hop) = self.hops.get_mut(index) else {
+                continue;
+            };
+            match reply {
+                ProbeReply::Reply { from, rtt_ms } => {
+                    hop.address = Some(*from);
+                    hop.stats.push_sample(Some(*rtt_ms));
+                    self.reached_at = Some(self.reached_at.map_or(*ttl, |reached| reached.min(*ttl)));
+                }
+                ProbeReply::TtlExceeded { from, rtt_ms } => {
+                    hop.address = Some(*from);
+                    hop.stats.push_sample(Some(*rtt_ms));
+                }
+                ProbeReply::Timeout => hop.stats.push_sample(None),
+            }
+        }
+        // 到达目标后，更远的跳不再有意义
+        if let Some(reached) = self.reached_at {
+            self.hops.truncate(usize::from(reached));
+        }
+        self.cycles += 1;
+    }
+
+    fn report(&self) -> PathReport {
+        // 未到达目标时，末尾从未响应的跳不输出
+        let len = if self.reached_at.is_some() {
+            self.hops.len()
+        } else {
+            self.hops
+                .iter()
+                .rposition(|hop| hop.address.is_some())
+                .map_or(0, |last| last + 1)
+        };
+        let hops = self
+            .hops
+            .iter()
+            .take(len)
+            .zip(1u8..)
+            .map(|(state, hop)| {
+                let summary = state.stats.rtt_summary();
+                PathHopStats {
+                    hop,
+                    address: state.address.map(|address| address.to_string()),
+                    packets_sent: state.stats.packets_sent(),
+                    packets_received: state.stats.packets_received(),
+                    loss_pct: state.stats.loss_pct(),
+                    min_ms: summary.map(|s| s.min_ms),
+                    avg_ms: summary.map(|s| s.avg_ms),
+                    max_ms: summary.map(|s| s.max_ms),
+                    stddev_ms: summary.map(|s| s.stddev_ms),
+                    jitter_ms: summary.map(|s| s.jitter_ms),
+                    last_rtt_ms: state.stats.last_rtt_ms(),
+                }
+            })
+            .collect();
+        PathReport {
+            target: self.target.clone(),
+            address: self.address.map(|address| address.to_string()),
+            cycles: self.cycles,
+            reached: self.reached_at.is_some(),
+            hops,
+        }
+    }
+}
+
+/// 探测参数
+#[derive(Debug, Clone)]
+struct PathConfig {
+    max_hops: u8,
+    interval: Duration,
+    timeout: Duration,
+    max_cycles: Option<u64>,
+    interface: Option<String>,
+    ipv4: bool,
+    ipv6: bool,
+    dns_timeout: Option<Duration>,
+}
+
+/// 轮次调度状态
+#[derive(Debug)]
+struct CycleState {
+    address: Option<IpAddr>,
+    next_cycle: Instant,
+}
+
+/// 持续监控到目标路径上每一跳的异步流（类似 `mtr`）
+///
+/// 每次迭代完成一轮探测并返回最新的 `PathReport`。
+#[pyclass(module = "ping_rs._ping_rs")]
+pub struct AsyncPathStream {
+    config: Arc<PathConfig>,
+    // 探测期间持有，保证同一时间只有一轮在运行
+    cycle: Arc<tokio::sync::Mutex<CycleState>>,
+    // 统计单独加锁，`report()` 不需要等待正在进行的一轮
+    state: Arc<Mutex<PathState>>,
+}
+
+/// 运行一轮探测，返回每个 TTL 的结果
+async fn run_cycle(address: IpAddr, max_ttl: u8, config: &PathConfig) -> Result<Vec<(u8, ProbeReply)>, PingError> {
+    let tasks: Vec<_> = (1..=max_ttl)
+        .map(|ttl| {
+            let options = ProbeOptions::with_ttl(ttl, config.timeout, config.interface.clone());
+            (ttl, tokio::spawn(async move { probe(address, &options).await }))
+        })
+        .collect();
+
+    let mut replies = Vec::with_capacity(tasks.len());
+    for (ttl, task) in tasks {
+        // 探测任务不会 panic，被取消时按超时处理
+        replies.push((ttl, task.await.unwrap_or(Ok(ProbeReply::Timeout))?));
+    }
+    Ok(replies)
+}
+
+#[pymethods]
+impl AsyncPathStream {
+    /// 创建新的 `AsyncPathStream` 实例
+    ///
+    /// 每隔 `interval_ms` 向每一跳各发送一个探测，单次探测最多等待 `timeout_ms`。
+    /// 统计使用最近 `window` 次探测的滑动窗口；`max_cycles` 限制总轮数（None 表示不限）。
+    ///
+    /// # Errors
+    /// - `PyValueError`: If `max_hops` or `window` is zero, or `interval_ms` / `timeout_ms` /
+    ///   `dns_resolve_timeout_ms` is not positive
+    /// - `PyTypeError`: If the target cannot be converted to a string
+    #[new]
+    #[pyo3(signature = (target, max_hops=30, interval_ms=1000, timeout_ms=1000, max_cycles=None, window=100, interface=None, ipv4=false, ipv6=false, dns_resolve_timeout_ms=None))]
+    pub fn new(
+        target: &Bound<PyAny>,
+        max_hops: u8,
+        interval_ms: i64,
+        timeout_ms: i64,
+        max_cycles: Option<u64>,
+        window: usize,
+        interface: Option<String>,
+        ipv4: bool,
+        ipv6: bool,
+        dns_resolve_timeout_ms: Option<i64>,
+    ) -> PyResult<Self> {
+        let target = extract_target(target)?;
+        if max_hops == 0 {
+            return Err(PyErr::new::<PyValueError, _>("max_hops must be between 1 and 255"));
+        }
+        if window == 0 {
+            return Err(PyErr::new::<PyValueError, _>("window must be a positive integer"));
+        }
+        let dns_timeout = dns_resolve_timeout_ms
+            .map(|timeout_ms| i64_to_u64_positive(timeout_ms, "dns_resolve_timeout_ms"))
+            .transpose()?
+            .map(Duration::from_millis);
+        let config = PathConfig {
+            max_hops,
+            interval: Duration::from_millis(i64_to_u64_positive(interval_ms, "interval_ms")?),
+            timeout: Duration::from_millis(i64_to_u64_positive(timeout_ms, "timeout_ms")?),
+            max_cycles,
+            interface,
+            ipv4,
+            ipv6,
+            dns_timeout,
+        };
+
+        Ok(Self {
+            config: Arc::new(config),
+            cycle: Arc::new(tokio::sync::Mutex::new(CycleState {
+                address: None,
+                next_cycle: Instant::now(),
+            })),
+            state: Arc::new(Mutex::new(PathState {
+                target,
+                address: None,
+                window,
+                hops: Vec::new(),
+                reached_at: None,
+                cycles: 0,
+            })),
+        })
+    }
+
+    /// 当前的统计报告（流结束后即为最终报告）
+    pub fn report(&self) -> PathReport {
+        lock(&self.state).report()
+    }
+
+    /// Python async iterator protocol: return self
+    pub fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Python async iterator protocol: run one probing cycle and return the updated report
+    ///
+    /// # Errors
+    /// - `PyStopAsyncIteration`: When `max_cycles` cycles have been run
+    /// - `PyRuntimeError`: If the target cannot be resolved, or the ping process fails to start
+    /// - `PyTimeoutError`: If waiting for a free ping slot times out (see `set_max_concurrent_pings`)
+    pub fn __anext__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let config = self.config.clone();
+        let cycle = self.cycle.clone();
+        let state = self.state.clone();
+
+        future_into_py(py, async move {
+            let mut cycle = cycle.lock().await;
+
+            let (target, cycles, reached_at) = {
+                let state = lock(&state);
+                (state.target.clone(), state.cycles, state.reached_at)
+            };
+            if config.max_cycles.is_some_and(|max| cycles >= max) {
+                return Err(PyStopAsyncIteration::new_err("Stream exhausted"));
+            }
+
+            let address = if let Some(address) = cycle.address {
+                address
+            } else {
+                let address =
+                    resolve_target(&target, config.ipv4, config.ipv6, config.timeout, config.dns_timeout).await?;
+                cycle.address = Some(address);
+                lock(&state).address = Some(address);
+                address
+            };
+
+            tokio::time::sleep_until(cycle.next_cycle.into()).await;
+            cycle.next_cycle = Instant::now() + config.interval;
+
+            let replies = run_cycle(address, reached_at.unwrap_or(config.max_hops), &config).await?;
+            let mut state = lock(&state);
+            state.record(&replies);
+            Ok(state.report())
+        })
+    }
+}
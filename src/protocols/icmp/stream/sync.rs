@@ -1,34 +1,110 @@
-use crate::protocols::icmp::execute_ping;
-use crate::types::options::DnsPreResolveOptions;
+use super::RestartState;
+use crate::protocols::dns::resolver::DnsResolver;
+use crate::protocols::icmp::{execute_ping, PingReceiver};
+use crate::sinks::{dispatch, extract_sink, flush_all, ResultSink, StreamInfo};
+use crate::types::batch::PingResultBatch;
+use crate::types::options::{DnsPreResolveOptions, PacketOptions, ResolveMode, RestartPolicy};
 use crate::types::result::PingResult;
 use crate::utils::conversion::{create_ping_options, extract_target};
+use crate::utils::jitter::Jitter;
 use crate::utils::validation::validate_interval_ms;
-use pinger::PingResult as RustPingResult;
+use pinger::PingOptions;
 use pyo3::exceptions::{PyRuntimeError, PyStopIteration};
 use pyo3::prelude::*;
-use std::sync::mpsc;
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
 
 /// Synchronous ping stream for continuous ping operations
 ///
 /// This struct provides an iterator interface for streaming ping results.
 #[pyclass]
 pub struct PingStream {
-    receiver: Option<std::sync::Arc<std::sync::Mutex<mpsc::Receiver<RustPingResult>>>>,
+    info: StreamInfo,
+    options: PingOptions,
+    dns_options: DnsPreResolveOptions,
+    receiver: Option<std::sync::Arc<std::sync::Mutex<PingReceiver>>>,
     max_count: Option<usize>,
     current_count: usize,
+    sinks: Vec<Arc<dyn ResultSink>>,
+    restart: RestartState,
+    /// ping 进程是否已启动过（设置了启动偏移时延迟到首次接收才启动）
+    started: bool,
+    /// 未加抖动的 ping 间隔，每次重启时重新抖动
+    interval_ms: u64,
+    jitter: Jitter,
+}
+
+impl PingStream {
+    /// 到达计划的（重新）启动时间后启动 ping 进程
+    ///
+    /// 非阻塞模式下尚未到启动时间时直接返回。首次启动失败时返回错误；
+    /// 重新启动失败时返回 `PingExited` 并重新安排重启。
+    fn try_restart(&mut self, py: Python<'_>, non_blocking: bool) -> PyResult<Option<PingResult>> {
+        let Some(restart_at) = self.restart.pending() else {
+            return Ok(None);
+        };
+        let now = Instant::now();
+        if restart_at > now {
+            if non_blocking {
+                return Ok(None);
+            }
+            // 退避时间最长 60 秒，等待时释放 GIL
+            py.detach(|| std::thread::sleep(restart_at - now));
+        }
+        self.restart.take();
+        self.options.interval = Duration::from_millis(self.jitter.interval_ms(self.interval_ms));
+
+        let (options, dns_options) = (self.options.clone(), self.dns_options.clone());
+        match py.detach(|| execute_ping(options, dns_options)) {
+            Ok(receiver) => {
+                self.receiver = Some(std::sync::Arc::new(std::sync::Mutex::new(receiver)));
+                self.started = true;
+                Ok(None)
+            }
+            Err(e) if !self.started => Err(e.into()),
+            Err(e) => {
+                let result = PingResult::PingExited {
+                    exit_code: -1,
+                    stderr: format!("Failed to restart ping: {e}"),
+                };
+                dispatch(&self.sinks, &self.info.target, &result);
+                self.current_count += 1;
+                self.restart.schedule();
+                Ok(Some(result))
+            }
+        }
+    }
 }
 
 #[pymethods]
 impl PingStream {
     /// 创建新的 `PingStream` 实例
     ///
+    /// `restart_on_exit` 为 `True` 时，ping 进程退出后先返回 `PingExited` 结果，
+    /// 再按 `restart_backoff_ms` 起始、逐次翻倍（最多 60 秒）的退避重新启动；
+    /// `max_restarts` 限制连续重启次数（收到 `Pong` / `Timeout` 后清零）。
+    ///
+    /// `start_jitter_ms` 大于 0 时，ping 进程在 `[0, start_jitter_ms]` 内的随机偏移后才启动
+    /// （延迟到首次接收时）；`interval_jitter_pct` 在每次（重新）启动 ping 进程时
+    /// 将间隔随机调整 `±pct%`（取整到 100ms）。
+    /// 指定 `jitter_seed` 时随机结果可复现。
+    ///
+    /// `dns_resolver` 指定预解析使用的 `DnsResolver`，默认使用系统解析器；
+    /// `resolve_mode` 为 `happy_eyeballs` 时 IPv6 优先、IPv4 回退（RFC 8305）。
+    ///
+    /// `size` 指定 ICMP 负载大小（字节），`dont_fragment` 为 `True` 时设置禁止分片。
+    ///
     /// # Errors
-    /// - `PyValueError`: If `interval_ms` is negative, less than 100ms, not a multiple of 100ms, or `max_count` is too large
+    /// - `PyValueError`: If `interval_ms` is negative, less than 100ms, not a multiple of 100ms, `max_count` is too large,
+    ///   `restart_backoff_ms` is not positive, `resolve_mode` is invalid, a jitter argument is out of range,
+    ///   or `size` is outside 16..=65500
     /// - `PyTypeError`: If the target cannot be converted to a string
     /// - `PyRuntimeError`: If the ping process fails to start
+    /// - `PyTimeoutError`: If waiting for a free ping slot times out (see `set_max_concurrent_pings`)
     #[new]
-    #[pyo3(signature = (target, interval_ms=1000, interface=None, ipv4=false, ipv6=false, max_count=None, dns_pre_resolve=true, dns_resolve_timeout_ms=None))]
+    #[pyo3(signature = (target, interval_ms=1000, interface=None, ipv4=false, ipv6=false, max_count=None, dns_pre_resolve=true, dns_resolve_timeout_ms=None, dns_resolver=None, resolve_mode="first", restart_on_exit=false, restart_backoff_ms=1000, max_restarts=None, start_jitter_ms=0, interval_jitter_pct=0.0, jitter_seed=None, size=None, dont_fragment=false))]
     pub fn new(
+        py: Python<'_>,
         target: &Bound<PyAny>,
         interval_ms: i64,
         interface: Option<String>,
@@ -37,6 +113,16 @@ impl PingStream {
         max_count: Option<usize>,
         dns_pre_resolve: bool,
         dns_resolve_timeout_ms: Option<i64>,
+        dns_resolver: Option<DnsResolver>,
+        resolve_mode: &str,
+        restart_on_exit: bool,
+        restart_backoff_ms: i64,
+        max_restarts: Option<u32>,
+        start_jitter_ms: i64,
+        interval_jitter_pct: f64,
+        jitter_seed: Option<u64>,
+        size: Option<i64>,
+        dont_fragment: bool,
     ) -> PyResult<Self> {
         // 提取目标地址
         let target_str = extract_target(target)?;
@@ -62,31 +148,51 @@ impl PingStream {
             None
         };
 
+        let restart_policy = RestartPolicy::from_args(restart_on_exit, restart_backoff_ms, max_restarts)?;
+        let mut jitter = Jitter::new(start_jitter_ms, interval_jitter_pct, jitter_seed)?;
+
         // 创建 ping 选项（不传递 count 给底层 ping 命令）
         // max_count 参数保存在 state 中，在迭代时由 Rust 层控制
-        let options = create_ping_options(&target_str, interval_ms_u64, interface, ipv4, ipv6);
+        let info = StreamInfo::new(&target_str, interface.clone(), ipv4, ipv6);
+        let mut options = create_ping_options(&target_str, jitter.interval_ms(interval_ms_u64), interface, ipv4, ipv6);
+        PacketOptions::from_args(size, dont_fragment)?.apply(&mut options);
 
         let dns_options = DnsPreResolveOptions {
             enable: dns_pre_resolve,
             timeout: dns_timeout,
+            resolver: dns_resolver.map(Arc::new),
+            mode: ResolveMode::single_from_name(resolve_mode)?,
         };
 
-        // 执行 ping 并获取接收器
-        let receiver = match execute_ping(options, dns_options) {
-            Ok(rx) => rx,
-            Err(e) => return Err(PyErr::new::<PyRuntimeError, _>(format!("Failed to start ping: {e}"))),
-        };
-
-        // 将接收器包装到 PingStream 中
-        Ok(PingStream {
-            receiver: Some(std::sync::Arc::new(std::sync::Mutex::new(receiver))),
+        let mut stream = PingStream {
+            info,
+            options,
+            dns_options,
+            receiver: None,
             max_count,
             current_count: 0,
-        })
+            sinks: Vec::new(),
+            restart: RestartState::new(restart_policy),
+            started: false,
+            interval_ms: interval_ms_u64,
+            jitter,
+        };
+
+        let start_delay = stream.jitter.start_delay();
+        if start_delay.is_zero() {
+            // 执行 ping 并获取接收器（等待并发许可和令牌时释放 GIL）
+            let (options, dns_options) = (stream.options.clone(), stream.dns_options.clone());
+            let receiver = py.detach(|| execute_ping(options, dns_options))?;
+            stream.receiver = Some(std::sync::Arc::new(std::sync::Mutex::new(receiver)));
+            stream.started = true;
+        } else {
+            stream.restart.defer_until(Instant::now() + start_delay);
+        }
+        Ok(stream)
     }
 
     #[allow(clippy::used_underscore_items)]
-    fn _recv(&mut self, non_blocking: bool, iter: bool) -> PyResult<Option<PingResult>> {
+    fn _recv(&mut self, py: Python<'_>, non_blocking: bool, iter: bool) -> PyResult<Option<PingResult>> {
         // 检查是否达到最大数量
         if let Some(max) = self.max_count {
             if self.current_count >= max {
@@ -97,6 +203,12 @@ impl PingStream {
                 return Ok(None);
             }
         }
+        // 延迟启动，或 ping 进程已退出且安排了重启
+        if self.receiver.is_none() {
+            if let Some(result) = self.try_restart(py, non_blocking)? {
+                return Ok(Some(result));
+            }
+        }
         if let Some(receiver) = &self.receiver {
             let result = {
                 let Ok(receiver_guard) = receiver.lock() else {
@@ -123,10 +235,18 @@ impl PingStream {
                 }
             };
 
+            // 将结果交给输出端
+            if let Ok(Some(ping_result)) = &result {
+                dispatch(&self.sinks, &self.info.target, ping_result);
+                self.restart.observe(ping_result);
+            }
+
             // 如果接收器已断开连接，则在锁释放后设置 receiver 为 None
             if let Ok(Some(PingResult::PingExited { .. })) = &result {
                 self.receiver = None;
                 self.current_count += 1;
+                // 启用了自动重启时安排重启，结果仍先返回给调用方
+                self.restart.schedule();
             } else if let Ok(None) = &result {
                 if !non_blocking {
                     // 如果是阻塞接收且没有结果，清空接收器
@@ -150,8 +270,8 @@ impl PingStream {
     /// # Errors
     /// - `PyRuntimeError`: If the receiver mutex lock fails
     #[allow(clippy::used_underscore_items)]
-    pub fn try_recv(&mut self) -> PyResult<Option<PingResult>> {
-        self._recv(true, false)
+    pub fn try_recv(&mut self, py: Python<'_>) -> PyResult<Option<PingResult>> {
+        self._recv(py, true, false)
     }
 
     /// 阻塞等待下一个 ping 结果
@@ -159,8 +279,36 @@ impl PingStream {
     /// # Errors
     /// - `PyRuntimeError`: If the receiver mutex lock fails
     #[allow(clippy::used_underscore_items)]
-    pub fn recv(&mut self) -> PyResult<Option<PingResult>> {
-        self._recv(false, false)
+    pub fn recv(&mut self, py: Python<'_>) -> PyResult<Option<PingResult>> {
+        self._recv(py, false, false)
+    }
+
+    /// 添加结果输出端（如 `Recorder`），之后收到的每个结果都会写入该输出端
+    ///
+    /// # Errors
+    /// - `PyTypeError`: If the object is not a supported sink type
+    pub fn add_sink(&mut self, sink: &Bound<PyAny>) -> PyResult<()> {
+        let sink = extract_sink(sink)?;
+        sink.attach(&self.info);
+        self.sinks.push(sink);
+        Ok(())
+    }
+
+    /// 非阻塞地取出当前已到达的全部结果（最多 `max_items` 个），返回列式的 `PingResultBatch`
+    ///
+    /// # Errors
+    /// - `PyRuntimeError`: If the receiver mutex lock fails
+    #[pyo3(signature = (max_items=None))]
+    #[allow(clippy::used_underscore_items)]
+    pub fn drain(&mut self, py: Python<'_>, max_items: Option<usize>) -> PyResult<PingResultBatch> {
+        let mut batch = PingResultBatch::default();
+        while max_items.is_none_or(|max| batch.__len__() < max) {
+            match self._recv(py, true, false)? {
+                Some(result) => batch.push(result),
+                None => break,
+            }
+        }
+        Ok(batch)
     }
 
     /// Python iterator protocol: return self
@@ -173,11 +321,11 @@ impl PingStream {
     /// Returns the next ping result or raises `StopIteration` when the stream is exhausted.
     ///
     /// # Errors
-    /// - `PyStopIteration`: When the stream is exhausted (`max_count` reached or ping process exited)
+    /// - `PyStopIteration`: When the stream is exhausted (`max_count` reached, or ping process exited and will not restart)
     /// - `PyRuntimeError`: If the receiver mutex lock fails
     #[allow(clippy::used_underscore_items)]
-    pub fn __next__(&mut self) -> PyResult<Option<PingResult>> {
-        self._recv(false, true)
+    pub fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<PingResult>> {
+        self._recv(py, false, true)
     }
 
     /// 检查流是否仍然活跃
@@ -187,6 +335,12 @@ impl PingStream {
                 return false;
             }
         }
-        self.receiver.is_some()
+        self.receiver.is_some() || self.restart.pending().is_some()
+    }
+}
+
+impl Drop for PingStream {
+    fn drop(&mut self) {
+        flush_all(&self.sinks);
     }
 }
@@ -0,0 +1,208 @@
+//! 多目标扫描
+//!
+//! 并发地向一组地址（通常由 CIDR 网段或地址范围展开）各发送若干次探测，汇总每个地址是否存活。
+
+use super::execute_ping_async;
+use crate::types::options::DnsPreResolveOptions;
+use crate::types::result::PingResult;
+use crate::utils::conversion::create_ping_options;
+use pyo3::prelude::*;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+
+/// 扫描配置
+#[derive(Clone, Debug)]
+pub struct SweepConfig {
+    /// 每个地址的最大探测次数
+    pub count: usize,
+    /// 单次探测的超时时间（也用作 ping 间隔）
+    pub timeout: Duration,
+    /// 可选的网络接口
+    pub interface: Option<String>,
+    /// 是否强制使用 IPv4
+    pub ipv4: bool,
+    /// 是否强制使用 IPv6
+    pub ipv6: bool,
+    /// 收到第一个响应后是否停止探测该地址
+    pub stop_on_reply: bool,
+    /// 同时探测的最大地址数
+    pub concurrency: usize,
+}
+
+/// 单个地址的扫描结果
+#[pyclass(module = "ping_rs._ping_rs", frozen)]
+#[derive(Debug, Clone)]
+pub struct HostResult {
+    /// 目标地址
+    #[pyo3(get)]
+    pub target: String,
+    /// 是否收到过响应
+    #[pyo3(get)]
+    pub alive: bool,
+    /// 第一个响应的延迟（毫秒）
+    #[pyo3(get)]
+    pub rtt_ms: Option<f64>,
+    /// 所有探测结果
+    #[pyo3(get)]
+    pub results: Vec<PingResult>,
+}
+
+impl HostResult {
+    /// 从探测结果创建，第一个 `Pong` 的延迟作为 `rtt_ms`
+    pub fn new(target: String, results: Vec<PingResult>) -> Self {
+        let rtt_ms = results.iter().find_map(|result| match result {
+            PingResult::Pong { duration_ms, .. } => Some(*duration_ms),
+            _ => None,
+        });
+        Self {
+            target,
+            alive: rtt_ms.is_some(),
+            rtt_ms,
+            results,
+        }
+    }
+}
+
+#[pymethods]
+impl HostResult {
+    /// Python `__repr__` method for string representation
+    pub fn __repr__(&self) -> String {
+        match self.rtt_ms {
+            Some(rtt) => format!("HostResult(target='{}', alive=True, rtt_ms={rtt:.3})", self.target),
+            None => format!("HostResult(target='{}', alive=False)", self.target),
+        }
+    }
+}
+
+/// 一次扫描的结果，按目标的输入顺序排列
+#[pyclass(module = "ping_rs._ping_rs", frozen)]
+#[derive(Debug, Clone)]
+pub struct SweepResult {
+    /// 每个地址的结果
+    #[pyo3(get)]
+    pub hosts: Vec<HostResult>,
+}
+
+impl SweepResult {
+    /// 只保留存活的地址
+    pub fn into_alive(self) -> Vec<HostResult> {
+        self.hosts.into_iter().filter(|host| host.alive).collect()
+    }
+}
+
+#[pymethods]
+impl SweepResult {
+    /// 存活的地址
+    #[getter]
+    pub fn alive(&self) -> Vec<String> {
+        self.hosts
+            .iter()
+            .filter(|host| host.alive)
+            .map(|host| host.target.clone())
+            .collect()
+    }
+
+    /// 没有响应的地址
+    #[getter]
+    pub fn dead(&self) -> Vec<String> {
+        self.hosts
+            .iter()
+            .filter(|host| !host.alive)
+            .map(|host| host.target.clone())
+            .collect()
+    }
+
+    /// Python `__len__` method
+    pub fn __len__(&self) -> usize {
+        self.hosts.len()
+    }
+
+    /// Python `__repr__` method for string representation
+    pub fn __repr__(&self) -> String {
+        let alive = self.hosts.iter().filter(|host| host.alive).count();
+        format!(
+            "SweepResult(hosts={}, alive={alive}, dead={})",
+            self.hosts.len(),
+            self.hosts.len() - alive
+        )
+    }
+}
+
+/// 探测单个地址
+async fn probe_host(target: String, config: &SweepConfig) -> HostResult {
+    let interval_ms = u64::try_from(config.timeout.as_millis()).unwrap_or(u64::MAX);
+    let options = create_ping_options(&target, interval_ms, config.interface.clone(), config.ipv4, config.ipv6);
+    let mut results = Vec::new();
+
+    match execute_ping_async(options, DnsPreResolveOptions::default()).await {
+        Ok(mut receiver) => {
+            let mut probes = 0;
+            while probes < config.count {
+                let result: PingResult = match tokio::time::timeout(config.timeout, receiver.recv()).await {
+                    Ok(Some(result)) => result.into(),
+                    Ok(None) => break,
+                    Err(_) => PingResult::Timeout {
+                        line: format!("Request timeout for icmp_seq {probes}"),
+                    },
+                };
+                match result {
+                    // 非探测结果的输出行不计入
+                    PingResult::Unknown { .. } => {}
+                    PingResult::PingExited { .. } => {
+                        results.push(result);
+                        break;
+                    }
+                    PingResult::Pong { .. } | PingResult::Timeout { .. } | PingResult::PortUnreachable { .. } => {
+                        probes += 1;
+                        let stop = config.stop_on_reply && matches!(result, PingResult::Pong { .. });
+                        results.push(result);
+                        if stop {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        Err(e) => results.push(PingResult::PingExited {
+            exit_code: -1,
+            stderr: e.to_string(),
+        }),
+    }
+
+    HostResult::new(target, results)
+}
+
+/// 并发探测所有地址，结果按输入顺序返回
+pub async fn run_sweep(targets: Vec<String>, config: SweepConfig) -> SweepResult {
+    let config = Arc::new(config);
+    let semaphore = Arc::new(Semaphore::new(config.concurrency.max(1)));
+
+    let mut tasks = Vec::with_capacity(targets.len());
+    for target in targets {
+        let config = config.clone();
+        let semaphore = semaphore.clone();
+        let task_target = target.clone();
+        let task = tokio::spawn(async move {
+            // 信号量不会被关闭
+            let _permit = semaphore.acquire_owned().await.ok();
+            probe_host(task_target, &config).await
+        });
+        tasks.push((target, task));
+    }
+
+    let mut hosts = Vec::with_capacity(tasks.len());
+    for (target, task) in tasks {
+        let host = task.await.unwrap_or_else(|e| {
+            HostResult::new(
+                target,
+                vec![PingResult::PingExited {
+                    exit_code: -1,
+                    stderr: e.to_string(),
+                }],
+            )
+        });
+        hosts.push(host);
+    }
+    SweepResult { hosts }
+}
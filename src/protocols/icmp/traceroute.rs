@@ -0,0 +1,143 @@
+//! 路由追踪
+//!
+//! 通过原生 ICMP 套接字逐跳发送 TTL 递增的 echo 请求（见 `probe`），同一跳的探测并发发送，
+//! 延迟从请求发出时开始计算。目标先按 `execute_ping_async` 的 DNS 预解析逻辑解析为 IP。
+//! 只支持 ICMP：UDP / TCP 探测需要在原始套接字上接收 ICMP 错误，不在本模块的范围内。
+
+use super::error::PingError;
+use super::probe::{probe, ProbeOptions, ProbeReply};
+use super::resolve_target;
+use pyo3::prelude::*;
+use std::net::IpAddr;
+use std::time::Duration;
+
+/// 路由追踪配置
+#[derive(Clone, Debug)]
+pub struct TracerouteConfig {
+    /// 最大跳数
+    pub max_hops: u8,
+    /// 每跳的探测次数
+    pub probes_per_hop: usize,
+    /// 单次探测的超时时间
+    pub timeout: Duration,
+    /// 可选的网络接口
+    pub interface: Option<String>,
+    /// 是否强制使用 IPv4
+    pub ipv4: bool,
+    /// 是否强制使用 IPv6
+    pub ipv6: bool,
+    /// DNS 解析超时（None 表示使用 `timeout`）
+    pub dns_timeout: Option<Duration>,
+}
+
+/// 路由上的一跳
+#[pyclass(module = "ping_rs._ping_rs", frozen)]
+#[derive(Debug, Clone)]
+pub struct TracerouteHop {
+    /// 跳数（从 1 开始）
+    #[pyo3(get)]
+    pub hop: u8,
+    /// 响应的地址，所有探测都超时时为 None
+    #[pyo3(get)]
+    pub address: Option<String>,
+    /// 收到响应的探测的延迟（毫秒）
+    #[pyo3(get)]
+    pub rtts_ms: Vec<f64>,
+    /// 超时的探测数
+    #[pyo3(get)]
+    pub timeouts: usize,
+    /// 是否已到达目标
+    #[pyo3(get)]
+    pub reached: bool,
+}
+
+impl TracerouteHop {
+    /// 由同一跳的探测结果创建
+    fn from_replies(hop: u8, replies: &[ProbeReply]) -> Self {
+        let mut address = None;
+        let mut rtts_ms = Vec::new();
+        let mut timeouts = 0;
+        let mut reached = false;
+        for reply in replies {
+            match reply {
+                ProbeReply::Reply { from, rtt_ms } => {
+                    address.get_or_insert(*from);
+                    rtts_ms.push(*rtt_ms);
+                    reached = true;
+                }
+                ProbeReply::TtlExceeded { from, rtt_ms } => {
+                    address.get_or_insert(*from);
+                    rtts_ms.push(*rtt_ms);
+                }
+                ProbeReply::Timeout => timeouts += 1,
+            }
+        }
+        Self {
+            hop,
+            address: address.map(|address| address.to_string()),
+            rtts_ms,
+            timeouts,
+            reached,
+        }
+    }
+}
+
+#[pymethods]
+impl TracerouteHop {
+    /// Python `__repr__` method for string representation
+    pub fn __repr__(&self) -> String {
+        let address = self
+            .address
+            .as_ref()
+            .map_or_else(|| "None".to_string(), |address| format!("'{address}'"));
+        let rtts = self
+            .rtts_ms
+            .iter()
+            .map(|rtt| format!("{rtt:.3}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "TracerouteHop(hop={}, address={address}, rtts_ms=[{rtts}], timeouts={}, reached={})",
+            self.hop,
+            self.timeouts,
+            if self.reached { "True" } else { "False" }
+        )
+    }
+}
+
+/// 追踪到 `target` 的路由，到达目标或达到最大跳数时停止
+///
+/// # Errors
+/// 目标解析失败、ICMP 套接字无法创建或收发失败、或排队等待并发许可超时时返回 `PingError`
+pub async fn run_traceroute(target: String, config: TracerouteConfig) -> Result<Vec<TracerouteHop>, PingError> {
+    let address = resolve_target(&target, config.ipv4, config.ipv6, config.timeout, config.dns_timeout).await?;
+    trace_icmp(address, &config).await
+}
+
+/// 使用 TTL 递增的 ICMP echo 探测追踪路由
+async fn trace_icmp(target: IpAddr, config: &TracerouteConfig) -> Result<Vec<TracerouteHop>, PingError> {
+    let mut hops = Vec::new();
+    for ttl in 1..=config.max_hops {
+        let options = ProbeOptions::with_ttl(ttl, config.timeout, config.interface.clone());
+        let tasks: Vec<_> = (0..config.probes_per_hop)
+            .map(|_| {
+                let options = options.clone();
+                tokio::spawn(async move { probe(target, &options).await })
+            })
+            .collect();
+
+        let mut replies = Vec::with_capacity(tasks.len());
+        for task in tasks {
+            // 探测任务不会 panic，被取消时按超时处理
+            replies.push(task.await.unwrap_or(Ok(ProbeReply::Timeout))?);
+        }
+
+        let hop = TracerouteHop::from_replies(ttl, &replies);
+        let reached = hop.reached;
+        hops.push(hop);
+        if reached {
+            break;
+        }
+    }
+    Ok(hops)
+}
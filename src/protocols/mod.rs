@@ -1,3 +1,8 @@
 // 导出不同的协议模块
+pub mod common;
+pub mod dns;
+pub mod http;
 pub mod icmp;
+pub mod tls;
+pub mod udp;
 // pub mod tcp;  // 未来添加TCP支持时取消注释
@@ -0,0 +1,161 @@
+//! TLS 握手探测协议
+//!
+//! 与 `host:port` 建立 TCP 连接并完成 TLS 握手（带 SNI），分别记录连接和握手耗时，
+//! 并报告协商的协议版本、密码套件和服务器证书的过期时间，用于跟踪握手退化和即将过期的证书。
+
+pub mod ping;
+pub mod result;
+
+use crate::protocols::common::{elapsed_ms, parse_timeouts};
+use crate::protocols::icmp::resolve_target;
+use crate::utils::conversion::unix_timestamp;
+use crate::utils::{rate_limit, tls};
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::prelude::*;
+use result::TlsPingResult;
+use std::io::{Error, ErrorKind};
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsConnector;
+
+/// TLS 探测目标
+#[derive(Clone)]
+pub struct TlsTarget {
+    /// 目标主机名或 IP 地址
+    pub host: String,
+    /// 目标端口
+    pub port: u16,
+    /// SNI 和证书校验使用的服务器名
+    pub server_name: String,
+    /// TLS 连接器
+    connector: TlsConnector,
+    /// 连接和握手的总超时时间
+    pub timeout: Duration,
+    /// 是否强制使用 IPv4
+    pub ipv4: bool,
+    /// 是否强制使用 IPv6
+    pub ipv6: bool,
+    /// DNS 解析超时（None 表示使用 `timeout`）
+    pub dns_timeout: Option<Duration>,
+}
+
+impl TlsTarget {
+    /// 从 Python 参数创建
+    ///
+    /// # Errors
+    /// - `PyValueError`: If `timeout_ms` or `dns_resolve_timeout_ms` is not positive
+    /// - `PyRuntimeError`: If the TLS configuration cannot be created
+    pub fn from_args(
+        host: String,
+        port: u16,
+        server_name: Option<String>,
+        timeout_ms: i64,
+        verify: bool,
+        ipv4: bool,
+        ipv6: bool,
+        dns_resolve_timeout_ms: Option<i64>,
+    ) -> PyResult<Self> {
+        let (timeout, dns_timeout) = parse_timeouts(timeout_ms, dns_resolve_timeout_ms)?;
+        let connector = tls::connector(verify, &[])
+            .map_err(|e| PyErr::new::<PyRuntimeError, _>(format!("Failed to configure TLS: {e}")))?;
+        Ok(Self {
+            server_name: server_name.unwrap_or_else(|| host.clone()),
+            host,
+            port,
+            connector,
+            timeout,
+            ipv4,
+            ipv6,
+            dns_timeout,
+        })
+    }
+}
+
+/// 握手完成后从连接中读取的信息
+#[derive(Default)]
+struct ConnectionInfo {
+    address: Option<SocketAddr>,
+    connect_ms: Option<f64>,
+    handshake_ms: Option<f64>,
+    protocol_version: Option<String>,
+    cipher_suite: Option<String>,
+    cert_not_after: Option<i64>,
+}
+
+/// 解析、连接并完成握手
+async fn handshake(target: &TlsTarget, info: &mut ConnectionInfo) -> std::io::Result<()> {
+    let ip = resolve_target(
+        &target.host,
+        target.ipv4,
+        target.ipv6,
+        target.timeout,
+        target.dns_timeout,
+    )
+    .await
+    .map_err(|e| Error::other(e.to_string()))?;
+    let addr = SocketAddr::new(ip, target.port);
+    info.address = Some(addr);
+    let server_name = tls::server_name(&target.server_name)?;
+
+    let phase = Instant::now();
+    let stream = TcpStream::connect(addr).await?;
+    info.connect_ms = Some(elapsed_ms(phase));
+
+    let phase = Instant::now();
+    let stream = target.connector.connect(server_name, stream).await?;
+    info.handshake_ms = Some(elapsed_ms(phase));
+
+    let (_, connection) = stream.get_ref();
+    // 例如 `TLSv1_3` -> `TLSv1.3`
+    info.protocol_version = connection.protocol_version().map(|version| {
+        version
+            .as_str()
+            .map_or_else(|| format!("{version:?}"), |name| name.replace('_', "."))
+    });
+    info.cipher_suite = connection.negotiated_cipher_suite().map(|suite| {
+        let suite = suite.suite();
+        suite.as_str().map_or_else(|| format!("{suite:?}"), str::to_string)
+    });
+    info.cert_not_after = connection
+        .peer_certificates()
+        .and_then(|certificates| certificates.first())
+        .and_then(|certificate| tls::certificate_not_after(certificate));
+    Ok(())
+}
+
+/// 完成一次 TLS 握手
+///
+/// 发送前等待全局速率限制。DNS 解析不计入耗时，但与连接和握手一起受 `timeout` 限制。
+pub async fn probe(target: &TlsTarget) -> TlsPingResult {
+    rate_limit::acquire().await;
+    let mut info = ConnectionInfo::default();
+    let outcome = tokio::time::timeout(target.timeout, handshake(target, &mut info)).await;
+
+    let (error, timed_out) = match outcome {
+        Ok(Ok(())) => (None, false),
+        Ok(Err(e)) => (Some(e.to_string()), e.kind() == ErrorKind::TimedOut),
+        Err(_) => (
+            Some(format!("handshake timed out after {}ms", target.timeout.as_millis())),
+            true,
+        ),
+    };
+    #[allow(clippy::cast_precision_loss)]
+    let cert_expires_in_days = info
+        .cert_not_after
+        .map(|not_after| (not_after as f64 - unix_timestamp()) / 86_400.0);
+    TlsPingResult {
+        target: target.host.clone(),
+        port: target.port,
+        server_name: target.server_name.clone(),
+        address: info.address.map(|addr| addr.ip().to_string()),
+        connect_ms: info.connect_ms,
+        handshake_ms: info.handshake_ms,
+        protocol_version: info.protocol_version,
+        cipher_suite: info.cipher_suite,
+        cert_not_after: info.cert_not_after,
+        cert_expires_in_days,
+        error,
+        timed_out,
+    }
+}
@@ -0,0 +1,117 @@
+//! TLS 探测的 Python 接口
+
+use super::result::TlsPingResult;
+use super::{probe, TlsTarget};
+use crate::protocols::common::run_probes;
+use crate::utils::conversion::extract_target;
+use crate::utils::validation::{i64_to_u64_positive, validate_count};
+use pyo3::prelude::*;
+use pyo3_async_runtimes::tokio::future_into_py;
+use std::time::Duration;
+
+/// 测量 TCP 连接和 TLS 握手耗时的 Pinger
+#[pyclass(module = "ping_rs._ping_rs")]
+pub struct TlsPinger {
+    target: TlsTarget,
+    interval: Duration,
+}
+
+#[pymethods]
+impl TlsPinger {
+    /// 创建新的 `TlsPinger` 实例
+    ///
+    /// # Arguments
+    /// - `target`: 目标主机（IP 地址或主机名）
+    /// - `port`: 目标端口（默认 443）
+    /// - `server_name`: SNI 服务器名（默认使用 `target`）
+    /// - `interval_ms`: `ping_multiple` 中两次握手的间隔（默认 1000ms）
+    /// - `timeout_ms`: 单次连接和握手的超时时间（默认 5000ms）
+    /// - `verify`: 是否校验服务器证书（默认 True）；为 False 时仍能报告已过期证书的过期时间
+    /// - `ipv4` / `ipv6`: 强制使用 IPv4 / IPv6
+    /// - `dns_resolve_timeout_ms`: DNS 解析超时（默认使用 `timeout_ms`）
+    ///
+    /// # Errors
+    /// - `PyValueError`: If `interval_ms`, `timeout_ms` or `dns_resolve_timeout_ms` is not positive
+    /// - `PyTypeError`: If the target cannot be converted to a string
+    /// - `PyRuntimeError`: If the TLS configuration cannot be created
+    #[new]
+    #[pyo3(signature = (target, port=443, server_name=None, interval_ms=1000, timeout_ms=5000, verify=true, ipv4=false, ipv6=false, dns_resolve_timeout_ms=None))]
+    pub fn new(
+        target: &Bound<PyAny>,
+        port: u16,
+        server_name: Option<String>,
+        interval_ms: i64,
+        timeout_ms: i64,
+        verify: bool,
+        ipv4: bool,
+        ipv6: bool,
+        dns_resolve_timeout_ms: Option<i64>,
+    ) -> PyResult<Self> {
+        let host = extract_target(target)?;
+        let interval = Duration::from_millis(i64_to_u64_positive(interval_ms, "interval_ms")?);
+        let target = TlsTarget::from_args(
+            host,
+            port,
+            server_name,
+            timeout_ms,
+            verify,
+            ipv4,
+            ipv6,
+            dns_resolve_timeout_ms,
+        )?;
+        Ok(Self { target, interval })
+    }
+
+    /// 完成一次握手
+    pub fn ping_once(&self, py: Python<'_>) -> TlsPingResult {
+        py.detach(|| pyo3_async_runtimes::tokio::get_runtime().block_on(probe(&self.target)))
+    }
+
+    /// 完成 `count` 次握手
+    ///
+    /// # Errors
+    /// - `PyValueError`: If `count` is not positive
+    #[pyo3(signature = (count=4))]
+    pub fn ping_multiple(&self, py: Python<'_>, count: i32) -> PyResult<Vec<TlsPingResult>> {
+        let count = validate_count(count, "count")?;
+        Ok(py.detach(|| {
+            pyo3_async_runtimes::tokio::get_runtime().block_on(run_probes(count, self.interval, || probe(&self.target)))
+        }))
+    }
+
+    /// 异步完成一次握手
+    ///
+    /// # Errors
+    /// - `PyRuntimeError`: If the coroutine cannot be created
+    pub fn ping_once_async<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let target = self.target.clone();
+        future_into_py(py, async move { Ok(probe(&target).await) })
+    }
+
+    /// 异步完成 `count` 次握手
+    ///
+    /// # Errors
+    /// - `PyValueError`: If `count` is not positive
+    #[pyo3(signature = (count=4))]
+    pub fn ping_multiple_async<'py>(&self, py: Python<'py>, count: i32) -> PyResult<Bound<'py, PyAny>> {
+        let count = validate_count(count, "count")?;
+        let target = self.target.clone();
+        let interval = self.interval;
+        future_into_py(
+            py,
+            async move { Ok(run_probes(count, interval, || probe(&target)).await) },
+        )
+    }
+
+    /// Python `__repr__` method for string representation
+    pub fn __repr__(&self) -> String {
+        format!(
+            "TlsPinger(target='{}', port={}, server_name='{}', interval_ms={}, timeout_ms={})",
+            self.target.host,
+            self.target.port,
+            self.target.server_name,
+            self.interval.as_millis(),
+            self.target.timeout.as_millis()
+        )
+    }
+}
@@ -0,0 +1,109 @@
+//! TLS 探测结果
+
+use crate::types::result::PingResult;
+use pyo3::prelude::*;
+
+/// 一次 TLS 握手的结果
+#[pyclass(module = "ping_rs._ping_rs", frozen)]
+#[derive(Debug, Clone)]
+pub struct TlsPingResult {
+    /// 目标主机名或 IP 地址
+    #[pyo3(get)]
+    pub target: String,
+    /// 目标端口
+    #[pyo3(get)]
+    pub port: u16,
+    /// 发送的 SNI 服务器名
+    #[pyo3(get)]
+    pub server_name: String,
+    /// 连接的 IP 地址
+    #[pyo3(get)]
+    pub address: Option<String>,
+    /// TCP 连接耗时（毫秒）
+    #[pyo3(get)]
+    pub connect_ms: Option<f64>,
+    /// TLS 握手耗时（毫秒）
+    #[pyo3(get)]
+    pub handshake_ms: Option<f64>,
+    /// 协商的协议版本，例如 `TLSv1.3`
+    #[pyo3(get)]
+    pub protocol_version: Option<String>,
+    /// 协商的密码套件，例如 `TLS13_AES_256_GCM_SHA384`
+    #[pyo3(get)]
+    pub cipher_suite: Option<String>,
+    /// 服务器证书的过期时间（Unix 时间戳，秒）
+    #[pyo3(get)]
+    pub cert_not_after: Option<i64>,
+    /// 距离证书过期的天数，已过期时为负数
+    #[pyo3(get)]
+    pub cert_expires_in_days: Option<f64>,
+    /// 失败原因
+    #[pyo3(get)]
+    pub error: Option<String>,
+    /// 是否因超时失败
+    #[pyo3(get)]
+    pub timed_out: bool,
+}
+
+#[pymethods]
+impl TlsPingResult {
+    /// 连接和握手的总耗时（毫秒），握手未完成时为 None
+    #[getter]
+    pub fn total_ms(&self) -> Option<f64> {
+        Some(self.connect_ms? + self.handshake_ms?)
+    }
+
+    /// 握手是否成功
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// 转换为 `PingResult`，以便接入现有的统计和输出端
+    ///
+    /// 握手成功时为 `Pong`，延迟为 `total_ms`；超时为 `Timeout`；其他失败（包括证书校验失败）为 `PingExited`。
+    pub fn to_ping_result(&self) -> PingResult {
+        let endpoint = format!("{}:{}", self.target, self.port);
+        if let Some(error) = &self.error {
+            if self.timed_out {
+                PingResult::Timeout {
+                    line: format!("Handshake timeout for {endpoint}"),
+                }
+            } else {
+                PingResult::PingExited {
+                    exit_code: -1,
+                    stderr: error.clone(),
+                }
+            }
+        } else {
+            let duration_ms = self.total_ms().unwrap_or(f64::NAN);
+            PingResult::Pong {
+                duration_ms,
+                line: format!(
+                    "{} {} handshake with {endpoint}: time={duration_ms:.3} ms",
+                    self.protocol_version.as_deref().unwrap_or("TLS"),
+                    self.cipher_suite.as_deref().unwrap_or("unknown"),
+                ),
+            }
+        }
+    }
+
+    /// Python `__repr__` method for string representation
+    pub fn __repr__(&self) -> String {
+        let fmt = |v: Option<f64>| v.map_or_else(|| "None".to_string(), |v| format!("{v:.3}"));
+        let quote = |v: Option<&String>| v.map_or_else(|| "None".to_string(), |v| format!("'{v}'"));
+        let tail = self
+            .error
+            .as_ref()
+            .map_or_else(String::new, |error| format!(", error='{error}'"));
+        format!(
+            "TlsPingResult(target='{}', port={}, connect_ms={}, handshake_ms={}, protocol_version={}, cipher_suite={}, cert_expires_in_days={}{tail})",
+            self.target,
+            self.port,
+            fmt(self.connect_ms),
+            fmt(self.handshake_ms),
+            quote(self.protocol_version.as_ref()),
+            quote(self.cipher_suite.as_ref()),
+            fmt(self.cert_expires_in_days)
+        )
+    }
+}
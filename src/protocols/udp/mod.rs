@@ -0,0 +1,168 @@
+//! UDP 探测协议
+//!
+//! 向 `host:port` 发送一个数据报，测量收到回显响应或 ICMP 端口不可达的时间，
+//! 适用于过滤了 ICMP 的 DNS / NTP / 游戏服务器。使用已连接的 UDP 套接字，
+//! 端口不可达由操作系统以"连接被拒绝"错误报告，不需要特权。
+
+pub mod ping;
+pub mod stream;
+
+use crate::protocols::common::parse_timeouts;
+use crate::protocols::icmp::resolve_target;
+use crate::types::result::PingResult;
+use crate::utils::rate_limit;
+use pyo3::prelude::*;
+use std::io;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::{Duration, Instant};
+use tokio::net::UdpSocket;
+
+/// 默认的探测负载
+const DEFAULT_PAYLOAD: &[u8] = b"ping-rs";
+
+/// 接收缓冲区大小（UDP 数据报的最大长度）
+const RECV_BUFFER_LEN: usize = 65_535;
+
+/// UDP 探测目标
+#[derive(Clone, Debug)]
+pub struct UdpTarget {
+    /// 目标主机名或 IP 地址
+    pub host: String,
+    /// 目标端口
+    pub port: u16,
+    /// 每次探测发送的负载
+    pub payload: Vec<u8>,
+    /// 等待响应的超时时间
+    pub timeout: Duration,
+    /// 是否强制使用 IPv4
+    pub ipv4: bool,
+    /// 是否强制使用 IPv6
+    pub ipv6: bool,
+    /// DNS 解析超时（None 表示使用 `timeout`）
+    pub dns_timeout: Option<Duration>,
+}
+
+impl UdpTarget {
+    /// 从 Python 参数创建
+    ///
+    /// # Errors
+    /// - `PyValueError`: If `timeout_ms` or `dns_resolve_timeout_ms` is not positive
+    pub fn from_args(
+        host: String,
+        port: u16,
+        timeout_ms: i64,
+        payload: Option<Vec<u8>>,
+        ipv4: bool,
+        ipv6: bool,
+        dns_resolve_timeout_ms: Option<i64>,
+    ) -> PyResult<Self> {
+        let (timeout, dns_timeout) = parse_timeouts(timeout_ms, dns_resolve_timeout_ms)?;
+        Ok(Self {
+            host,
+            port,
+            payload: payload.unwrap_or_else(|| DEFAULT_PAYLOAD.to_vec()),
+            timeout,
+            ipv4,
+            ipv6,
+            dns_timeout,
+        })
+    }
+
+    /// 解析目标地址，失败时返回对应的 `PingExited` 结果
+    async fn resolve(&self) -> Result<SocketAddr, PingResult> {
+        resolve_target(&self.host, self.ipv4, self.ipv6, self.timeout, self.dns_timeout)
+            .await
+            .map(|ip| SocketAddr::new(ip, self.port))
+            .map_err(|e| PingResult::PingExited {
+                exit_code: -1,
+                stderr: e.to_string(),
+            })
+    }
+}
+
+/// 创建连接到 `addr` 的 UDP 套接字
+async fn connect(addr: SocketAddr) -> io::Result<UdpSocket> {
+    let local: SocketAddr = if addr.is_ipv4() {
+        (Ipv4Addr::UNSPECIFIED, 0).into()
+    } else {
+        (Ipv6Addr::UNSPECIFIED, 0).into()
+    };
+    let socket = UdpSocket::bind(local).await?;
+    socket.connect(addr).await?;
+    Ok(socket)
+}
+
+/// Linux / macOS 报告为 `ECONNREFUSED`，Windows 报告为 `WSAECONNRESET`
+fn is_port_unreachable(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        io::ErrorKind::ConnectionRefused | io::ErrorKind::ConnectionReset
+    )
+}
+
+/// 向 `addr` 发送一次探测
+///
+/// 收到任意响应数据报返回 `Pong`，收到端口不可达返回 `PortUnreachable`，超时或发送失败返回 `Timeout`，
+/// 无法创建套接字时返回 `PingExited`。发送前等待全局速率限制。
+pub async fn probe(addr: SocketAddr, payload: &[u8], timeout: Duration) -> PingResult {
+    rate_limit::acquire().await;
+    let socket = match connect(addr).await {
+        Ok(socket) => socket,
+        Err(e) => {
+            return PingResult::PingExited {
+                exit_code: -1,
+                stderr: format!("Failed to open UDP socket to {addr}: {e}"),
+            }
+        }
+    };
+
+    let mut buffer = vec![0u8; RECV_BUFFER_LEN];
+    let started = Instant::now();
+    let received = tokio::time::timeout(timeout, async {
+        socket.send(payload).await?;
+        socket.recv(&mut buffer).await
+    })
+    .await;
+    let duration_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+    match received {
+        Ok(Ok(len)) => PingResult::Pong {
+            duration_ms,
+            line: format!("{len} bytes from {addr}: time={duration_ms:.3} ms"),
+        },
+        Ok(Err(e)) if is_port_unreachable(&e) => PingResult::PortUnreachable {
+            duration_ms,
+            line: format!("Port unreachable from {addr}: time={duration_ms:.3} ms"),
+        },
+        Ok(Err(e)) => PingResult::Timeout {
+            line: format!("Request to {addr} failed: {e}"),
+        },
+        Err(_) => PingResult::Timeout {
+            line: format!("Request timeout for udp {addr}"),
+        },
+    }
+}
+
+/// 以 `interval` 为间隔向目标发送 `count` 次探测
+///
+/// 解析失败或无法创建套接字时只返回一个 `PingExited` 结果。
+pub async fn run_probes(target: &UdpTarget, count: usize, interval: Duration) -> Vec<PingResult> {
+    let addr = match target.resolve().await {
+        Ok(addr) => addr,
+        Err(result) => return vec![result],
+    };
+
+    let mut results = Vec::with_capacity(count);
+    let mut next_at = Instant::now();
+    for _ in 0..count {
+        tokio::time::sleep_until(next_at.into()).await;
+        next_at = Instant::now() + interval;
+        let result = probe(addr, &target.payload, target.timeout).await;
+        let exited = matches!(result, PingResult::PingExited { .. });
+        results.push(result);
+        if exited {
+            break;
+        }
+    }
+    results
+}
@@ -0,0 +1,113 @@
+//! UDP 探测的 Python 接口
+
+use super::{run_probes, UdpTarget};
+use crate::types::result::PingResult;
+use crate::utils::conversion::extract_target;
+use crate::utils::validation::{i64_to_u64_positive, validate_count};
+use pyo3::prelude::*;
+use pyo3_async_runtimes::tokio::future_into_py;
+use std::time::Duration;
+
+/// 向 `host:port` 发送 UDP 数据报的 Pinger
+#[pyclass(module = "ping_rs._ping_rs")]
+pub struct UdpPinger {
+    target: UdpTarget,
+    interval: Duration,
+}
+
+#[pymethods]
+impl UdpPinger {
+    /// 创建新的 `UdpPinger` 实例
+    ///
+    /// # Arguments
+    /// - `target`: 目标主机（IP 地址或主机名）
+    /// - `port`: 目标 UDP 端口
+    /// - `interval_ms`: `ping_multiple` 中两次探测的间隔（默认 1000ms）
+    /// - `timeout_ms`: 单次探测等待响应的超时时间（默认 1000ms）
+    /// - `payload`: 发送的数据（默认 `b"ping-rs"`），需要时可以填入服务能识别的请求
+    /// - `ipv4` / `ipv6`: 强制使用 IPv4 / IPv6
+    /// - `dns_resolve_timeout_ms`: DNS 解析超时（默认使用 `timeout_ms`）
+    ///
+    /// # Errors
+    /// - `PyValueError`: If `interval_ms`, `timeout_ms` or `dns_resolve_timeout_ms` is not positive
+    /// - `PyTypeError`: If the target cannot be converted to a string
+    #[new]
+    #[pyo3(signature = (target, port, interval_ms=1000, timeout_ms=1000, payload=None, ipv4=false, ipv6=false, dns_resolve_timeout_ms=None))]
+    pub fn new(
+        target: &Bound<PyAny>,
+        port: u16,
+        interval_ms: i64,
+        timeout_ms: i64,
+        payload: Option<Vec<u8>>,
+        ipv4: bool,
+        ipv6: bool,
+        dns_resolve_timeout_ms: Option<i64>,
+    ) -> PyResult<Self> {
+        let host = extract_target(target)?;
+        let interval = Duration::from_millis(i64_to_u64_positive(interval_ms, "interval_ms")?);
+        let target = UdpTarget::from_args(host, port, timeout_ms, payload, ipv4, ipv6, dns_resolve_timeout_ms)?;
+        Ok(Self { target, interval })
+    }
+
+    /// 发送一次探测
+    pub fn ping_once(&self, py: Python<'_>) -> PingResult {
+        let results = py
+            .detach(|| pyo3_async_runtimes::tokio::get_runtime().block_on(run_probes(&self.target, 1, self.interval)));
+        first_result(results)
+    }
+
+    /// 发送 `count` 次探测
+    ///
+    /// # Errors
+    /// - `PyValueError`: If `count` is not positive
+    #[pyo3(signature = (count=4))]
+    pub fn ping_multiple(&self, py: Python<'_>, count: i32) -> PyResult<Vec<PingResult>> {
+        let count = validate_count(count, "count")?;
+        Ok(py.detach(|| {
+            pyo3_async_runtimes::tokio::get_runtime().block_on(run_probes(&self.target, count, self.interval))
+        }))
+    }
+
+    /// 异步发送一次探测
+    ///
+    /// # Errors
+    /// - `PyRuntimeError`: If the coroutine cannot be created
+    pub fn ping_once_async<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let target = self.target.clone();
+        let interval = self.interval;
+        future_into_py(
+            py,
+            async move { Ok(first_result(run_probes(&target, 1, interval).await)) },
+        )
+    }
+
+    /// 异步发送 `count` 次探测
+    ///
+    /// # Errors
+    /// - `PyValueError`: If `count` is not positive
+    #[pyo3(signature = (count=4))]
+    pub fn ping_multiple_async<'py>(&self, py: Python<'py>, count: i32) -> PyResult<Bound<'py, PyAny>> {
+        let count = validate_count(count, "count")?;
+        let target = self.target.clone();
+        let interval = self.interval;
+        future_into_py(py, async move { Ok(run_probes(&target, count, interval).await) })
+    }
+
+    /// Python `__repr__` method for string representation
+    pub fn __repr__(&self) -> String {
+        format!(
+            "UdpPinger(target='{}', port={}, interval_ms={}, timeout_ms={})",
+            self.target.host,
+            self.target.port,
+            self.interval.as_millis(),
+            self.target.timeout.as_millis()
+        )
+    }
+}
+
+/// 取第一个结果（`run_probes` 至少返回一个结果）
+fn first_result(results: Vec<PingResult>) -> PingResult {
+    results.into_iter().next().unwrap_or_else(|| PingResult::Unknown {
+        line: "No result received".to_string(),
+    })
+}
@@ -0,0 +1,145 @@
+//! 持续 UDP 探测流
+
+use super::{probe, UdpTarget};
+use crate::types::result::PingResult;
+use crate::utils::conversion::extract_target;
+use crate::utils::validation::i64_to_u64_positive;
+use pyo3::exceptions::PyStopAsyncIteration;
+use pyo3::prelude::*;
+use pyo3_async_runtimes::tokio::future_into_py;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// 流的迭代状态
+#[derive(Debug)]
+struct StreamState {
+    /// 首次探测时解析的目标地址
+    addr: Option<SocketAddr>,
+    next_at: Instant,
+    count: usize,
+    /// 解析失败或无法创建套接字后结束
+    finished: bool,
+}
+
+/// 流的配置，在同步和异步迭代之间共享
+#[derive(Debug)]
+struct StreamConfig {
+    target: UdpTarget,
+    interval: Duration,
+    max_count: Option<usize>,
+}
+
+/// 持续向 `host:port` 发送 UDP 探测的流，支持同步和异步迭代
+#[pyclass(module = "ping_rs._ping_rs")]
+pub struct UdpPingStream {
+    config: Arc<StreamConfig>,
+    state: Arc<tokio::sync::Mutex<StreamState>>,
+}
+
+/// 等到下一次探测时间后发送探测，流结束时返回 None
+async fn next_result(config: &StreamConfig, state: &tokio::sync::Mutex<StreamState>) -> Option<PingResult> {
+    let mut state = state.lock().await;
+    if state.finished || config.max_count.is_some_and(|max| state.count >= max) {
+        return None;
+    }
+
+    let addr = if let Some(addr) = state.addr {
+        addr
+    } else {
+        match config.target.resolve().await {
+            Ok(addr) => {
+                state.addr = Some(addr);
+                addr
+            }
+            Err(result) => {
+                state.finished = true;
+                return Some(result);
+            }
+        }
+    };
+
+    tokio::time::sleep_until(state.next_at.into()).await;
+    state.next_at = Instant::now() + config.interval;
+    let result = probe(addr, &config.target.payload, config.target.timeout).await;
+    state.finished = matches!(result, PingResult::PingExited { .. });
+    state.count += 1;
+    Some(result)
+}
+
+#[pymethods]
+impl UdpPingStream {
+    /// 创建新的 `UdpPingStream` 实例
+    ///
+    /// 参数与 `UdpPinger` 相同，`max_count` 限制探测次数（None 表示不限）。
+    ///
+    /// # Errors
+    /// - `PyValueError`: If `interval_ms`, `timeout_ms` or `dns_resolve_timeout_ms` is not positive
+    /// - `PyTypeError`: If the target cannot be converted to a string
+    #[new]
+    #[pyo3(signature = (target, port, interval_ms=1000, timeout_ms=1000, payload=None, max_count=None, ipv4=false, ipv6=false, dns_resolve_timeout_ms=None))]
+    pub fn new(
+        target: &Bound<PyAny>,
+        port: u16,
+        interval_ms: i64,
+        timeout_ms: i64,
+        payload: Option<Vec<u8>>,
+        max_count: Option<usize>,
+        ipv4: bool,
+        ipv6: bool,
+        dns_resolve_timeout_ms: Option<i64>,
+    ) -> PyResult<Self> {
+        let host = extract_target(target)?;
+        let interval = Duration::from_millis(i64_to_u64_positive(interval_ms, "interval_ms")?);
+        let target = UdpTarget::from_args(host, port, timeout_ms, payload, ipv4, ipv6, dns_resolve_timeout_ms)?;
+        Ok(Self {
+            config: Arc::new(StreamConfig {
+                target,
+                interval,
+                max_count,
+            }),
+            state: Arc::new(tokio::sync::Mutex::new(StreamState {
+                addr: None,
+                next_at: Instant::now(),
+                count: 0,
+                finished: false,
+            })),
+        })
+    }
+
+    /// 流是否还会产生结果
+    pub fn is_active(&self) -> bool {
+        self.state.try_lock().map_or(true, |state| {
+            !state.finished && self.config.max_count.is_none_or(|max| state.count < max)
+        })
+    }
+
+    /// Python iterator protocol: return self
+    pub fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Python iterator protocol: block until the next probe completes
+    pub fn __next__(&self, py: Python<'_>) -> Option<PingResult> {
+        py.detach(|| pyo3_async_runtimes::tokio::get_runtime().block_on(next_result(&self.config, &self.state)))
+    }
+
+    /// Python async iterator protocol: return self
+    pub fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Python async iterator protocol: get the next probe result
+    ///
+    /// # Errors
+    /// - `PyStopAsyncIteration`: When `max_count` is reached or the target cannot be probed any more
+    pub fn __anext__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let config = self.config.clone();
+        let state = self.state.clone();
+        future_into_py(py, async move {
+            next_result(&config, &state)
+                .await
+                .ok_or_else(|| PyStopAsyncIteration::new_err("Stream exhausted"))
+        })
+    }
+}
@@ -0,0 +1,388 @@
+//! `InfluxDB` 行协议输出端
+//!
+//! 每个结果格式化为一行 `InfluxDB` 行协议，经 UDP 立即发送，或经 HTTP 批量写入
+//! （`InfluxDB` `/write`、`/api/v2/write` 或 Telegraf 的 `influxdb_listener` / `http_listener_v2`）。
+
+use super::{bind_udp, resolve_udp, AttachedStreams, ResultSink, SinkInput, StreamInfo};
+use crate::types::result::PingResult;
+use crate::utils::conversion::unix_nanos;
+use crate::utils::http::{self, HttpEndpoint};
+use crate::utils::validation::i64_to_u64_positive;
+use pyo3::exceptions::{PyIOError, PyValueError};
+use pyo3::prelude::*;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::net::{SocketAddr, UdpSocket};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, Weak};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// HTTP 模式下未发送行的最大缓存数量，超出时丢弃最旧的行
+const MAX_BUFFERED_LINES: usize = 10_000;
+
+/// 行的发送方式
+enum Transport {
+    /// 每行一个 UDP 数据报
+    Udp { socket: UdpSocket, addr: SocketAddr },
+    /// 定期批量 POST
+    Http {
+        endpoint: HttpEndpoint,
+        token: Option<String>,
+        timeout: Duration,
+        buffer: Mutex<VecDeque<String>>,
+    },
+}
+
+impl Transport {
+    /// 根据 URL 创建发送方式：`udp://host:port` 或 `http(s)://host:port[/path]`
+    fn parse(url: &str, token: Option<String>, timeout: Duration) -> PyResult<Self> {
+        if let Some(addr) = url.strip_prefix("udp://") {
+            let addr = resolve_udp(addr)?;
+            let socket = bind_udp(addr)?;
+            return Ok(Self::Udp { socket, addr });
+        }
+
+        Ok(Self::Http {
+            endpoint: HttpEndpoint::parse(url, "/write")?,
+            token,
+            timeout,
+            buffer: Mutex::new(VecDeque::new()),
+        })
+    }
+
+    fn url(&self) -> String {
+        match self {
+            Self::Udp { addr, .. } => format!("udp://{addr}"),
+            Self::Http { endpoint, .. } => endpoint.url(),
+        }
+    }
+}
+
+/// 行协议测量名称转义
+fn escape_measurement(value: &str) -> String {
+    value.replace('\\', "\\\\").replace(',', "\\,").replace(' ', "\\ ")
+}
+
+/// 行协议标签键/值转义
+fn escape_tag(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(',', "\\,")
+        .replace('=', "\\=")
+        .replace(' ', "\\ ")
+}
+
+/// 行协议字符串字段转义
+fn escape_field(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// 向 HTTP 地址 POST 一批行
+async fn post_lines(
+    endpoint: &HttpEndpoint,
+    token: Option<&str>,
+    timeout: Duration,
+    lines: &[String],
+) -> Result<(), String> {
+    let body = lines.join("\n");
+    let authorization = token.map(|token| format!("Token {token}"));
+    let headers: Vec<(&str, &str)> = authorization
+        .as_deref()
+        .map(|value| ("Authorization", value))
+        .into_iter()
+        .collect();
+    let status = http::post(
+        endpoint,
+        "text/plain; charset=utf-8",
+        &headers,
+        body.as_bytes(),
+        timeout,
+    )
+    .await
+    .map_err(|e| format!("Failed to write to {}: {e}", endpoint.url()))?;
+    if (200..300).contains(&status) {
+        Ok(())
+    } else {
+        Err(format!("{} responded with HTTP {status}", endpoint.url()))
+    }
+}
+
+/// 写入 `rtt_ms` 字段，跳过行协议无法表示的 NaN 和无穷大
+fn write_rtt(line: &mut String, duration_ms: f64) {
+    if duration_ms.is_finite() {
+        let _ = write!(line, ",rtt_ms={duration_ms}");
+    }
+}
+
+/// 输出端的共享实现
+struct InfluxSinkInner {
+    measurement: String,
+    transport: Transport,
+    streams: AttachedStreams,
+    /// 最近一次发送失败的错误，在 `flush` 时报告
+    last_error: Mutex<Option<String>>,
+}
+
+impl InfluxSinkInner {
+    fn last_error(&self) -> MutexGuard<'_, Option<String>> {
+        self.last_error.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// 格式化为一行行协议（不含换行符）
+    fn format_line(&self, target: &str, result: &PingResult) -> String {
+        let mut line = escape_measurement(&self.measurement);
+        let _ = write!(line, ",target={}", escape_tag(target));
+        if let Some(info) = self.streams.get(target) {
+            if let Some(interface) = &info.interface {
+                let _ = write!(line, ",interface={}", escape_tag(interface));
+            }
+            let _ = write!(line, ",address_family={}", info.address_family);
+        }
+
+        let _ = write!(line, " type=\"{}\"", result.type_name());
+        match result {
+            PingResult::Pong { duration_ms, .. } => {
+                line.push_str(",success=true");
+                write_rtt(&mut line, *duration_ms);
+            }
+            PingResult::Timeout { .. } => line.push_str(",success=false"),
+            PingResult::PortUnreachable { duration_ms, .. } => {
+                line.push_str(",success=false");
+                write_rtt(&mut line, *duration_ms);
+            }
+            PingResult::Unknown { line: text } => {
+                let _ = write!(line, ",line=\"{}\"", escape_field(text));
+            }
+            PingResult::PingExited { exit_code, stderr } => {
+                let _ = write!(line, ",exit_code={exit_code}i,stderr=\"{}\"", escape_field(stderr));
+            }
+        }
+
+        let _ = write!(line, " {}", unix_nanos());
+        line
+    }
+
+    /// 发送 HTTP 缓存中的所有行，发送失败时放回缓存以便下次重试
+    async fn flush_http(&self) -> Result<(), String> {
+        let Transport::Http {
+            endpoint,
+            token,
+            timeout,
+            buffer,
+        } = &self.transport
+        else {
+            return Ok(());
+        };
+
+        let lines: Vec<String> = buffer
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .drain(..)
+            .collect();
+        if lines.is_empty() {
+            return Ok(());
+        }
+        let result = post_lines(endpoint, token.as_deref(), *timeout, &lines).await;
+        if result.is_err() {
+            // 放回缓存最前面，超出上限时丢弃最旧的行
+            let mut buffer = buffer.lock().unwrap_or_else(PoisonError::into_inner);
+            for line in lines.into_iter().rev() {
+                buffer.push_front(line);
+            }
+            while buffer.len() > MAX_BUFFERED_LINES {
+                buffer.pop_front();
+            }
+        }
+        result
+    }
+}
+
+impl ResultSink for InfluxSinkInner {
+    fn record(&self, target: &str, result: &PingResult) {
+        let line = self.format_line(target, result);
+        match &self.transport {
+            Transport::Udp { socket, addr } => {
+                if let Err(e) = socket.send_to(line.as_bytes(), addr) {
+                    *self.last_error() = Some(format!("Failed to send to udp://{addr}: {e}"));
+                }
+            }
+            Transport::Http { buffer, .. } => {
+                let mut buffer = buffer.lock().unwrap_or_else(PoisonError::into_inner);
+                if buffer.len() >= MAX_BUFFERED_LINES {
+                    buffer.pop_front();
+                }
+                buffer.push_back(line);
+            }
+        }
+    }
+
+    fn attach(&self, info: &StreamInfo) {
+        self.streams.insert(info);
+    }
+}
+
+impl Drop for InfluxSinkInner {
+    /// 在运行时中发送 HTTP 缓存中剩余的行（不等待发送完成，尽力而为）
+    fn drop(&mut self) {
+        let Transport::Http {
+            endpoint,
+            token,
+            timeout,
+            buffer,
+        } = &mut self.transport
+        else {
+            return;
+        };
+        let lines: Vec<String> = buffer
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .drain(..)
+            .collect();
+        if lines.is_empty() {
+            return;
+        }
+        let (endpoint, token, timeout) = (endpoint.clone(), token.take(), *timeout);
+        pyo3_async_runtimes::tokio::get_runtime().spawn(async move {
+            let _ = post_lines(&endpoint, token.as_deref(), timeout, &lines).await;
+        });
+    }
+}
+
+/// 定期发送 HTTP 缓存，所有引用释放后自动结束
+async fn flush_periodically(sink: Weak<InfluxSinkInner>, interval: Duration) {
+    let mut ticker = tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+    loop {
+        ticker.tick().await;
+        let Some(sink) = sink.upgrade() else {
+            break;
+        };
+        if let Err(e) = sink.flush_http().await {
+            *sink.last_error() = Some(e);
+        }
+    }
+}
+
+/// 以 `InfluxDB` 行协议输出流结果的输出端
+///
+/// 通过 `PingStream.add_sink()` / `AsyncPingStream.add_sink()` 挂载到流上。
+/// `udp://` 地址每个结果立即发送一个数据报；`http://` / `https://` 地址定期批量写入。
+#[pyclass(module = "ping_rs._ping_rs")]
+pub struct InfluxSink {
+    inner: Arc<InfluxSinkInner>,
+    task: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl InfluxSink {
+    /// 获取可挂载到流上的输出端
+    pub fn sink(&self) -> Arc<dyn ResultSink> {
+        self.inner.clone()
+    }
+
+    fn flush_inner(&self, py: Python<'_>) -> PyResult<()> {
+        let previous = self.inner.last_error().take();
+        let inner = self.inner.clone();
+        let result = py.detach(|| pyo3_async_runtimes::tokio::get_runtime().block_on(inner.flush_http()));
+        previous.map_or(result, Err).map_err(PyErr::new::<PyIOError, _>)
+    }
+}
+
+#[pymethods]
+impl InfluxSink {
+    /// 创建新的 `InfluxSink` 实例
+    ///
+    /// # Arguments
+    /// - `url`: `udp://host:port`，或 `http(s)://host:port[/path]`（路径为空时使用 `/write`）
+    /// - `measurement`: 测量名称（默认 `"ping"`）
+    /// - `token`: HTTP 模式下的 `Authorization: Token ...` 认证
+    /// - `flush_interval_ms`: HTTP 模式下的批量写入间隔（默认 1000ms）
+    /// - `timeout_ms`: HTTP 请求超时（默认 5000ms）
+    ///
+    /// # Errors
+    /// - `PyValueError`: If the URL is invalid, or an interval/timeout is not positive
+    /// - `PyIOError`: If the UDP socket cannot be bound
+    #[new]
+    #[pyo3(signature = (url, measurement="ping", token=None, flush_interval_ms=1000, timeout_ms=5000))]
+    pub fn new(
+        url: &str,
+        measurement: &str,
+        token: Option<String>,
+        flush_interval_ms: i64,
+        timeout_ms: i64,
+    ) -> PyResult<Self> {
+        let flush_interval = Duration::from_millis(i64_to_u64_positive(flush_interval_ms, "flush_interval_ms")?);
+        let timeout = Duration::from_millis(i64_to_u64_positive(timeout_ms, "timeout_ms")?);
+        if measurement.is_empty() {
+            return Err(PyErr::new::<PyValueError, _>("measurement must not be empty"));
+        }
+
+        let inner = Arc::new(InfluxSinkInner {
+            measurement: measurement.to_string(),
+            transport: Transport::parse(url, token, timeout)?,
+            streams: AttachedStreams::default(),
+            last_error: Mutex::new(None),
+        });
+        let task = matches!(inner.transport, Transport::Http { .. }).then(|| {
+            pyo3_async_runtimes::tokio::get_runtime().spawn(flush_periodically(Arc::downgrade(&inner), flush_interval))
+        });
+
+        Ok(Self {
+            inner,
+            task: Mutex::new(task),
+        })
+    }
+
+    /// 手动写入一个结果
+    #[pyo3(signature = (result, target=""))]
+    pub fn write(&self, result: SinkInput, target: &str) {
+        self.inner.record(target, &result.into_ping_result());
+    }
+
+    /// 立即发送缓存的行
+    ///
+    /// # Errors
+    /// - `PyIOError`: If a previous send or this flush failed
+    pub fn flush(&self, py: Python<'_>) -> PyResult<()> {
+        self.flush_inner(py)
+    }
+
+    /// 停止定期写入，并发送剩余的行
+    ///
+    /// # Errors
+    /// - `PyIOError`: If a previous send or the final flush failed
+    pub fn close(&self, py: Python<'_>) -> PyResult<()> {
+        if let Some(task) = self.task.lock().unwrap_or_else(PoisonError::into_inner).take() {
+            task.abort();
+        }
+        self.flush_inner(py)
+    }
+
+    /// 目标地址
+    #[getter]
+    pub fn url(&self) -> String {
+        self.inner.transport.url()
+    }
+
+    /// 上下文管理器协议：返回自身
+    pub fn __enter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    /// 上下文管理器协议：关闭输出端
+    ///
+    /// # Errors
+    /// - `PyIOError`: If the final flush failed
+    #[pyo3(signature = (*_args))]
+    pub fn __exit__(&self, py: Python<'_>, _args: &Bound<'_, pyo3::types::PyTuple>) -> PyResult<bool> {
+        self.close(py)?;
+        Ok(false)
+    }
+
+    /// Python `__repr__` method for string representation
+    pub fn __repr__(&self) -> String {
+        format!(
+            "InfluxSink(url='{}', measurement='{}')",
+            self.url(),
+            self.inner.measurement
+        )
+    }
+}
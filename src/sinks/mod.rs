@@ -0,0 +1,191 @@
+//! 流结果输出端
+//!
+//! `PingStream` / `AsyncPingStream` 每产生一个结果，都会依次交给已添加的输出端处理。
+
+pub mod influx;
+#[cfg(feature = "otlp")]
+pub mod otlp;
+pub mod recorder;
+pub mod statsd;
+
+use crate::protocols::dns::result::DnsPingResult;
+use crate::protocols::http::result::HttpPingResult;
+use crate::protocols::tls::result::TlsPingResult;
+use crate::types::result::PingResult;
+use pyo3::prelude::*;
+use std::collections::BTreeMap;
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs, UdpSocket};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use influx::InfluxSink;
+#[cfg(feature = "otlp")]
+use otlp::OtlpJsonSink;
+use recorder::Recorder;
+use statsd::StatsdSink;
+
+/// 输出端所挂载的流的信息
+#[derive(Clone, Debug)]
+pub struct StreamInfo {
+    /// 目标主机名或 IP 地址
+    pub target: String,
+    /// 网络接口
+    pub interface: Option<String>,
+    /// 地址族：`"ipv4"`、`"ipv6"`，未指定且目标不是 IP 地址时为 `"any"`
+    pub address_family: &'static str,
+}
+
+impl StreamInfo {
+    /// 根据流的参数创建
+    pub fn new(target: &str, interface: Option<String>, ipv4: bool, ipv6: bool) -> Self {
+        let address_family = if ipv4 {
+            "ipv4"
+        } else if ipv6 {
+            "ipv6"
+        } else {
+            match target.parse::<IpAddr>() {
+                Ok(IpAddr::V4(_)) => "ipv4",
+                Ok(IpAddr::V6(_)) => "ipv6",
+                Err(_) => "any",
+            }
+        };
+        Self {
+            target: target.to_string(),
+            interface,
+            address_family,
+        }
+    }
+}
+
+/// 按目标记录已挂载的流信息，供输出端生成标签或属性
+#[derive(Debug, Default)]
+pub struct AttachedStreams(Mutex<BTreeMap<String, StreamInfo>>);
+
+impl AttachedStreams {
+    /// 记录挂载的流
+    pub fn insert(&self, info: &StreamInfo) {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(info.target.clone(), info.clone());
+    }
+
+    /// 获取目标对应的流信息
+    pub fn get(&self, target: &str) -> Option<StreamInfo> {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(target)
+            .cloned()
+    }
+}
+
+/// 解析 `host:port` 形式的 UDP 目标地址
+///
+/// # Errors
+/// - `PyValueError`: If the address cannot be resolved
+pub fn resolve_udp(addr: &str) -> PyResult<SocketAddr> {
+    addr.to_socket_addrs()
+        .ok()
+        .and_then(|mut addrs| addrs.next())
+        .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Cannot resolve UDP address '{addr}'")))
+}
+
+/// 绑定与目标地址族一致的 UDP 套接字
+///
+/// # Errors
+/// - `PyIOError`: If the socket cannot be bound
+pub fn bind_udp(addr: SocketAddr) -> PyResult<UdpSocket> {
+    let local = if addr.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+    UdpSocket::bind(local)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("Failed to bind UDP socket: {e}")))
+}
+
+/// 结果输出端
+///
+/// 实现必须是线程安全的：同一个输出端可以同时挂载到多个流上。
+/// `record` 可以执行阻塞的文件或网络 I/O：同步流在调用线程中调用，异步流通过 `dispatch_async`
+/// 在阻塞线程池中调用，`Monitor` 在目标的会话任务中调用。
+pub trait ResultSink: Send + Sync {
+    /// 记录一个结果
+    fn record(&self, target: &str, result: &PingResult);
+
+    /// 挂载到流上时调用，可用于记录流的属性
+    fn attach(&self, _info: &StreamInfo) {}
+
+    /// 刷新缓冲区
+    fn flush(&self) {}
+}
+
+/// 输出端 `write()` 接受的结果
+///
+/// HTTP / TLS / DNS 探测结果按各自的 `to_ping_result()` 转换为 `PingResult` 后写入。
+#[derive(FromPyObject)]
+pub enum SinkInput {
+    /// ICMP / UDP 结果
+    Ping(PingResult),
+    /// HTTP 探测结果
+    Http(HttpPingResult),
+    /// TLS 握手探测结果
+    Tls(TlsPingResult),
+    /// DNS 查询探测结果
+    Dns(DnsPingResult),
+}
+
+impl SinkInput {
+    /// 转换为输出端记录的 `PingResult`
+    pub fn into_ping_result(self) -> PingResult {
+        match self {
+            Self::Ping(result) => result,
+            Self::Http(result) => result.to_ping_result(),
+            Self::Tls(result) => result.to_ping_result(),
+            Self::Dns(result) => result.to_ping_result(),
+        }
+    }
+}
+
+/// 将结果分发给所有输出端
+pub fn dispatch(sinks: &[Arc<dyn ResultSink>], target: &str, result: &PingResult) {
+    for sink in sinks {
+        sink.record(target, result);
+    }
+}
+
+/// 在阻塞线程池中将结果分发给所有输出端，避免阻塞的 I/O 占用异步运行时的工作线程
+pub async fn dispatch_async(sinks: &[Arc<dyn ResultSink>], target: &str, result: &PingResult) {
+    if sinks.is_empty() {
+        return;
+    }
+    let (sinks, target, result) = (sinks.to_vec(), target.to_string(), result.clone());
+    let _ = tokio::task::spawn_blocking(move || dispatch(&sinks, &target, &result)).await;
+}
+
+/// 刷新所有输出端
+pub fn flush_all(sinks: &[Arc<dyn ResultSink>]) {
+    for sink in sinks {
+        sink.flush();
+    }
+}
+
+/// 从 Python 对象中提取输出端
+///
+/// # Errors
+/// - `PyTypeError`: If the object is not a supported sink type
+pub fn extract_sink(sink: &Bound<PyAny>) -> PyResult<Arc<dyn ResultSink>> {
+    if let Ok(recorder) = sink.extract::<PyRef<'_, Recorder>>() {
+        return Ok(recorder.sink());
+    }
+    if let Ok(influx) = sink.extract::<PyRef<'_, InfluxSink>>() {
+        return Ok(influx.sink());
+    }
+    if let Ok(statsd) = sink.extract::<PyRef<'_, StatsdSink>>() {
+        return Ok(statsd.sink());
+    }
+    #[cfg(feature = "otlp")]
+    if let Ok(otlp) = sink.extract::<PyRef<'_, OtlpJsonSink>>() {
+        return Ok(otlp.sink());
+    }
+
+    Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+        "Expected sink to be a Recorder, InfluxSink, StatsdSink or OtlpJsonSink",
+    ))
+}
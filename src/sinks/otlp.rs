@@ -0,0 +1,324 @@
+//! OTLP/JSON 指标推送
+//!
+//! 复用 `MetricsRegistry` 按目标汇总结果，定期以 OTLP/JSON 编码经 HTTP(S) 推送到采集器的 `/v1/metrics`。
+//! 需要启用 `otlp` feature。这不是 OpenTelemetry SDK：指标直接编码为 OTLP/JSON，
+//! 不会注册为 SDK 的 instruments，也不能接入应用已有的 `MeterProvider`。
+
+use super::{AttachedStreams, ResultSink, SinkInput, StreamInfo};
+use crate::metrics::{MetricsRegistry, TargetMetrics, RTT_BUCKETS_SECONDS};
+use crate::types::result::PingResult;
+use crate::utils::conversion::unix_nanos;
+use crate::utils::http::{self, HttpEndpoint};
+use crate::utils::validation::i64_to_u64_positive;
+use pyo3::exceptions::PyIOError;
+use pyo3::prelude::*;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex, PoisonError, Weak};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// OTLP/HTTP 指标路径
+const METRICS_PATH: &str = "/v1/metrics";
+
+/// 累积聚合（`AGGREGATION_TEMPORALITY_CUMULATIVE`）
+const CUMULATIVE: u8 = 2;
+
+/// OTLP 字符串属性
+fn attribute(key: &str, value: &str) -> Value {
+    json!({ "key": key, "value": { "stringValue": value } })
+}
+
+/// 输出端的共享实现
+struct OtlpJsonSinkInner {
+    endpoint: HttpEndpoint,
+    service_name: String,
+    timeout: Duration,
+    registry: MetricsRegistry,
+    /// 按目标记录挂载时的流信息，用于生成属性
+    streams: AttachedStreams,
+    start_time_nanos: u128,
+    /// 最近一次后台导出失败的错误，在 `force_flush` 时报告
+    last_error: Mutex<Option<String>>,
+}
+
+impl OtlpJsonSinkInner {
+    fn attributes(&self, target: &str) -> Vec<Value> {
+        let mut attributes = vec![attribute("target", target)];
+        if let Some(info) = self.streams.get(target) {
+            if let Some(interface) = &info.interface {
+                attributes.push(attribute("interface", interface));
+            }
+            attributes.push(attribute("address_family", info.address_family));
+        }
+        attributes
+    }
+
+    /// 构造 `ExportMetricsServiceRequest` 的 JSON 编码
+    fn build_request(&self) -> Value {
+        let targets = self.registry.snapshot();
+        let start = self.start_time_nanos.to_string();
+        let now = unix_nanos().to_string();
+
+        let points = |value: &dyn Fn(&TargetMetrics) -> u64| -> Vec<Value> {
+            targets
+                .iter()
+                .map(|(target, metrics)| {
+                    json!({
+                        "attributes": self.attributes(target),
+                        "startTimeUnixNano": start,
+                        "timeUnixNano": now,
+                        "asInt": value(metrics).to_string(),
+                    })
+                })
+                .collect()
+        };
+        let counter = |name: &str, unit: &str, description: &str, value: &dyn Fn(&TargetMetrics) -> u64| {
+            json!({
+                "name": name,
+                "unit": unit,
+                "description": description,
+                "sum": {
+                    "aggregationTemporality": CUMULATIVE,
+                    "isMonotonic": true,
+                    "dataPoints": points(value),
+                },
+            })
+        };
+
+        let histogram_points: Vec<Value> = targets
+            .iter()
+            .map(|(target, metrics)| {
+                // 注册表中的桶计数是累计的，OTLP 需要每个桶各自的计数（最后一个为溢出桶）
+                let mut bucket_counts = Vec::with_capacity(RTT_BUCKETS_SECONDS.len() + 1);
+                let mut previous = 0;
+                for i in 0..RTT_BUCKETS_SECONDS.len() {
+                    let cumulative = metrics.rtt_bucket_counts.get(i).copied().unwrap_or(0);
+                    bucket_counts.push((cumulative - previous).to_string());
+                    previous = cumulative;
+                }
+                bucket_counts.push((metrics.rtt_count - previous).to_string());
+
+                json!({
+                    "attributes": self.attributes(target),
+                    "startTimeUnixNano": start,
+                    "timeUnixNano": now,
+                    "count": metrics.rtt_count.to_string(),
+                    "sum": metrics.rtt_sum_seconds,
+                    "bucketCounts": bucket_counts,
+                    "explicitBounds": RTT_BUCKETS_SECONDS,
+                })
+            })
+            .collect();
+
+        let metrics = vec![
+            json!({
+                "name": "ping.rtt",
+                "unit": "s",
+                "description": "Round-trip time of successful probes.",
+                "histogram": {
+                    "aggregationTemporality": CUMULATIVE,
+                    "dataPoints": histogram_points,
+                },
+            }),
+            counter("ping.packets.sent", "{packet}", "Probes sent to the target.", &|m| {
+                m.packets_sent
+            }),
+            counter(
+                "ping.packets.lost",
+                "{packet}",
+                "Probes to the target that timed out.",
+                &|m| m.packets_lost,
+            ),
+            counter(
+                "ping.process.exits",
+                "{exit}",
+                "Times the ping process for the target exited.",
+                &|m| m.process_exits,
+            ),
+            json!({
+                "name": "ping.up",
+                "unit": "1",
+                "description": "Whether the last probe to the target succeeded.",
+                "gauge": { "dataPoints": points(&|m| u64::from(m.up)) },
+            }),
+        ];
+
+        json!({
+            "resourceMetrics": [{
+                "resource": { "attributes": [attribute("service.name", &self.service_name)] },
+                "scopeMetrics": [{
+                    "scope": { "name": "ping-rs", "version": crate::get_ping_rs_version() },
+                    "metrics": metrics,
+                }],
+            }],
+        })
+    }
+
+    /// 推送一次当前指标
+    async fn export(&self) -> Result<(), String> {
+        let body = self.build_request().to_string();
+        let status = http::post(&self.endpoint, "application/json", &[], body.as_bytes(), self.timeout)
+            .await
+            .map_err(|e| format!("Failed to export metrics to {}: {e}", self.endpoint.url()))?;
+        if (200..300).contains(&status) {
+            Ok(())
+        } else {
+            Err(format!(
+                "Collector at {} responded with HTTP {status}",
+                self.endpoint.url()
+            ))
+        }
+    }
+}
+
+impl ResultSink for OtlpJsonSinkInner {
+    fn record(&self, target: &str, result: &PingResult) {
+        self.registry.record(target, result);
+    }
+
+    fn attach(&self, info: &StreamInfo) {
+        self.registry.register(&info.target);
+        self.streams.insert(info);
+    }
+}
+
+/// 定期导出，所有引用释放后自动结束
+async fn export_periodically(sink: Weak<OtlpJsonSinkInner>, interval: Duration) {
+    let mut ticker = tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+    loop {
+        ticker.tick().await;
+        let Some(sink) = sink.upgrade() else {
+            break;
+        };
+        let result = sink.export().await;
+        *sink.last_error.lock().unwrap_or_else(PoisonError::into_inner) = result.err();
+    }
+}
+
+/// 以 OTLP/JSON 指标推送流结果的输出端
+///
+/// 通过 `PingStream.add_sink()` / `AsyncPingStream.add_sink()` 挂载到流上，
+/// 按目标记录 RTT 直方图、发送/丢失计数、进程退出次数和 `up` 状态，
+/// 带有 `target` / `interface` / `address_family` 属性，定期推送到采集器。
+#[pyclass(module = "ping_rs._ping_rs")]
+pub struct OtlpJsonSink {
+    inner: Arc<OtlpJsonSinkInner>,
+    task: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl OtlpJsonSink {
+    /// 获取可挂载到流上的输出端
+    pub fn sink(&self) -> Arc<dyn ResultSink> {
+        self.inner.clone()
+    }
+}
+
+#[pymethods]
+impl OtlpJsonSink {
+    /// 创建新的 `OtlpJsonSink` 实例并开始定期导出
+    ///
+    /// # Arguments
+    /// - `endpoint`: 采集器地址，路径为空时使用 `/v1/metrics`（默认 `http://localhost:4318`）
+    /// - `export_interval_ms`: 导出间隔（默认 10000ms）
+    /// - `service_name`: 资源属性 `service.name`（默认 `"ping-rs"`）
+    /// - `timeout_ms`: 单次导出的超时时间（默认 10000ms）
+    ///
+    /// # Errors
+    /// - `PyValueError`: If the endpoint is not a valid `http://` or `https://` URL, or an interval/timeout is not positive
+    #[new]
+    #[pyo3(signature = (endpoint="http://localhost:4318", export_interval_ms=10000, service_name="ping-rs", timeout_ms=10000))]
+    pub fn new(endpoint: &str, export_interval_ms: i64, service_name: &str, timeout_ms: i64) -> PyResult<Self> {
+        let endpoint = HttpEndpoint::parse(endpoint, METRICS_PATH)?;
+        let interval = Duration::from_millis(i64_to_u64_positive(export_interval_ms, "export_interval_ms")?);
+        let timeout = Duration::from_millis(i64_to_u64_positive(timeout_ms, "timeout_ms")?);
+
+        let inner = Arc::new(OtlpJsonSinkInner {
+            endpoint,
+            service_name: service_name.to_string(),
+            timeout,
+            registry: MetricsRegistry::default(),
+            streams: AttachedStreams::default(),
+            start_time_nanos: unix_nanos(),
+            last_error: Mutex::new(None),
+        });
+        let task =
+            pyo3_async_runtimes::tokio::get_runtime().spawn(export_periodically(Arc::downgrade(&inner), interval));
+
+        Ok(Self {
+            inner,
+            task: Mutex::new(Some(task)),
+        })
+    }
+
+    /// 手动记录一个结果
+    #[pyo3(signature = (result, target=""))]
+    pub fn write(&self, result: SinkInput, target: &str) {
+        self.inner.record(target, &result.into_ping_result());
+    }
+
+    /// 立即导出当前指标
+    ///
+    /// # Errors
+    /// - `PyIOError`: If a previous periodic export or this export failed
+    pub fn force_flush(&self, py: Python<'_>) -> PyResult<()> {
+        let previous = self
+            .inner
+            .last_error
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        let inner = self.inner.clone();
+        let result = py.detach(|| pyo3_async_runtimes::tokio::get_runtime().block_on(inner.export()));
+        match previous.map_or(result, Err) {
+            Ok(()) => Ok(()),
+            Err(e) => Err(PyErr::new::<PyIOError, _>(e)),
+        }
+    }
+
+    /// 停止定期导出，并做最后一次导出
+    ///
+    /// # Errors
+    /// - `PyIOError`: If the final export failed
+    pub fn shutdown(&self, py: Python<'_>) -> PyResult<()> {
+        let task = self.task.lock().unwrap_or_else(PoisonError::into_inner).take();
+        match task {
+            Some(task) => {
+                task.abort();
+                self.force_flush(py)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// 采集器地址
+    #[getter]
+    pub fn endpoint(&self) -> String {
+        self.inner.endpoint.url()
+    }
+
+    /// 上下文管理器协议：返回自身
+    pub fn __enter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    /// 上下文管理器协议：停止导出
+    ///
+    /// # Errors
+    /// - `PyIOError`: If the final export failed
+    #[pyo3(signature = (*_args))]
+    pub fn __exit__(&self, py: Python<'_>, _args: &Bound<'_, pyo3::types::PyTuple>) -> PyResult<bool> {
+        self.shutdown(py)?;
+        Ok(false)
+    }
+
+    /// Python `__repr__` method for string representation
+    pub fn __repr__(&self) -> String {
+        let running = self
+            .task
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .is_some_and(|task| !task.is_finished());
+        format!("OtlpJsonSink(endpoint='{}', running={running})", self.endpoint())
+    }
+}
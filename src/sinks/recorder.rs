@@ -0,0 +1,377 @@
+use super::{ResultSink, SinkInput};
+use crate::types::result::PingResult;
+use crate::utils::conversion::unix_timestamp;
+use crate::utils::validation::i64_to_u64_positive;
+use pyo3::exceptions::{PyIOError, PyValueError};
+use pyo3::prelude::*;
+use serde::Serialize;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// CSV 文件的表头
+const CSV_HEADER: &str = "timestamp,target,type,duration_ms,exit_code,line\n";
+
+/// 记录文件格式
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RecordFormat {
+    /// 每行一个 JSON 对象
+    JsonLines,
+    /// 带表头的 CSV
+    Csv,
+}
+
+impl RecordFormat {
+    fn parse(value: &str) -> PyResult<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "jsonl" | "ndjson" => Ok(Self::JsonLines),
+            "csv" => Ok(Self::Csv),
+            other => Err(PyErr::new::<PyValueError, _>(format!(
+                "format must be 'jsonl' or 'csv', got '{other}'"
+            ))),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::JsonLines => "jsonl",
+            Self::Csv => "csv",
+        }
+    }
+}
+
+/// JSON Lines 中的一条记录，结果字段与 `PingResult.to_dict()` 一致
+#[derive(Serialize)]
+struct JsonRecord<'a> {
+    timestamp: f64,
+    target: &'a str,
+    #[serde(flatten)]
+    result: &'a PingResult,
+}
+
+/// 当前打开的记录文件
+struct RecorderState {
+    /// 为 None 表示已关闭
+    writer: Option<BufWriter<File>>,
+    /// 当前文件已写入的字节数
+    bytes_written: u64,
+    /// 当前文件的打开时间
+    opened_at: Instant,
+    /// 最近一次写入失败的错误，在 `flush` / `close` 时报告
+    last_error: Option<std::io::Error>,
+}
+
+/// 记录器的共享实现，可以同时挂载到多个流上
+struct RecorderSink {
+    path: PathBuf,
+    format: RecordFormat,
+    max_bytes: Option<u64>,
+    rotate_interval: Option<Duration>,
+    state: Mutex<RecorderState>,
+}
+
+impl RecorderSink {
+    fn lock(&self) -> MutexGuard<'_, RecorderState> {
+        // 写入过程中不会 panic，锁中毒时继续使用内部状态
+        self.state.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// 打开（或追加到）记录文件，新文件会写入 CSV 表头
+    fn open(path: &Path, format: RecordFormat) -> std::io::Result<(BufWriter<File>, u64)> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let mut bytes_written = file.metadata()?.len();
+        let mut writer = BufWriter::new(file);
+        if format == RecordFormat::Csv && bytes_written == 0 {
+            writer.write_all(CSV_HEADER.as_bytes())?;
+            bytes_written = CSV_HEADER.len() as u64;
+        }
+        Ok((writer, bytes_written))
+    }
+
+    fn format_record(&self, target: &str, result: &PingResult) -> std::io::Result<String> {
+        let timestamp = unix_timestamp();
+        match self.format {
+            RecordFormat::JsonLines => {
+                let mut line = serde_json::to_string(&JsonRecord {
+                    timestamp,
+                    target,
+                    result,
+                })?;
+                line.push('\n');
+                Ok(line)
+            }
+            RecordFormat::Csv => {
+                let duration_ms = result.duration_ms().map(|d| d.to_string()).unwrap_or_default();
+                let exit_code = result.exit_code().map(|c| c.to_string()).unwrap_or_default();
+                Ok(format!(
+                    "{timestamp},{},{},{duration_ms},{exit_code},{}\n",
+                    csv_escape(target),
+                    result.type_name(),
+                    csv_escape(&result.line())
+                ))
+            }
+        }
+    }
+
+    /// 判断写入下一条记录前是否需要轮转
+    fn should_rotate(&self, state: &RecorderState, next_len: usize) -> bool {
+        let header_len = match self.format {
+            RecordFormat::Csv => CSV_HEADER.len() as u64,
+            RecordFormat::JsonLines => 0,
+        };
+        let has_records = state.bytes_written > header_len;
+
+        let size_exceeded = self
+            .max_bytes
+            .is_some_and(|max| has_records && state.bytes_written + next_len as u64 > max);
+        let interval_elapsed = self
+            .rotate_interval
+            .is_some_and(|interval| has_records && state.opened_at.elapsed() >= interval);
+
+        size_exceeded || interval_elapsed
+    }
+
+    /// 关闭当前文件，重命名为 `<path>.<unix 毫秒时间戳>`，然后重新打开
+    ///
+    /// 重命名失败时继续写入当前文件；新文件打开失败时把已轮转的文件改回原路径并以追加模式重新打开，
+    /// 两种情况都返回错误。
+    fn rotate(&self, state: &mut RecorderState) -> std::io::Result<()> {
+        if let Some(writer) = state.writer.as_mut() {
+            writer.flush()?;
+            writer.get_ref().sync_all()?;
+        }
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let millis = (unix_timestamp() * 1000.0) as u64;
+        let mut rotated = self.path.clone().into_os_string();
+        rotated.push(format!(".{millis}"));
+        // 同一毫秒内多次轮转时追加序号，避免覆盖已轮转的文件
+        let mut candidate = PathBuf::from(&rotated);
+        let mut seq = 1;
+        while candidate.exists() {
+            let mut next = rotated.clone();
+            next.push(format!(".{seq}"));
+            candidate = PathBuf::from(next);
+            seq += 1;
+        }
+        std::fs::rename(&self.path, &candidate)?;
+        // 重命名成功后才关闭旧文件
+        state.writer = None;
+
+        match Self::open(&self.path, self.format) {
+            Ok((writer, bytes_written)) => {
+                state.writer = Some(writer);
+                state.bytes_written = bytes_written;
+                state.opened_at = Instant::now();
+                Ok(())
+            }
+            Err(e) => {
+                // 恢复原文件，下次写入时会再次尝试轮转
+                if std::fs::rename(&candidate, &self.path).is_ok() {
+                    if let Ok((writer, bytes_written)) = Self::open(&self.path, self.format) {
+                        state.writer = Some(writer);
+                        state.bytes_written = bytes_written;
+                    }
+                }
+                Err(e)
+            }
+        }
+    }
+
+    fn write_record(&self, state: &mut RecorderState, target: &str, result: &PingResult) -> std::io::Result<()> {
+        if state.writer.is_none() {
+            return Ok(());
+        }
+
+        let record = self.format_record(target, result)?;
+        // 轮转失败时仍写入当前（或恢复的）文件，再报告错误
+        let rotated = if self.should_rotate(state, record.len()) {
+            self.rotate(state)
+        } else {
+            Ok(())
+        };
+
+        if let Some(writer) = state.writer.as_mut() {
+            writer.write_all(record.as_bytes())?;
+            state.bytes_written += record.len() as u64;
+        }
+        rotated
+    }
+
+    fn flush_state(state: &mut RecorderState) -> std::io::Result<()> {
+        if let Some(error) = state.last_error.take() {
+            return Err(error);
+        }
+        if let Some(writer) = state.writer.as_mut() {
+            writer.flush()?;
+        }
+        Ok(())
+    }
+
+    fn close(&self) -> std::io::Result<()> {
+        let mut state = self.lock();
+        let flushed = Self::flush_state(&mut state);
+        if let Some(mut writer) = state.writer.take() {
+            writer.flush()?;
+            writer.get_ref().sync_all()?;
+        }
+        flushed
+    }
+}
+
+impl ResultSink for RecorderSink {
+    fn record(&self, target: &str, result: &PingResult) {
+        let mut state = self.lock();
+        if let Err(e) = self.write_record(&mut state, target, result) {
+            state.last_error = Some(e);
+        }
+    }
+
+    fn flush(&self) {
+        let mut state = self.lock();
+        if let Err(e) = Self::flush_state(&mut state) {
+            state.last_error = Some(e);
+        }
+    }
+}
+
+impl Drop for RecorderSink {
+    fn drop(&mut self) {
+        let _ = self.close();
+    }
+}
+
+/// CSV 字段转义
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// 将流结果追加写入 JSON Lines 或 CSV 文件的记录器
+///
+/// 通过 `PingStream.add_sink()` / `AsyncPingStream.add_sink()` 挂载到流上，
+/// 支持按文件大小或时间轮转，关闭时刷新并同步到磁盘。
+#[pyclass(module = "ping_rs._ping_rs")]
+pub struct Recorder {
+    inner: Arc<RecorderSink>,
+}
+
+impl Recorder {
+    /// 获取可挂载到流上的输出端
+    pub fn sink(&self) -> Arc<dyn ResultSink> {
+        self.inner.clone()
+    }
+}
+
+#[pymethods]
+impl Recorder {
+    /// 创建新的 `Recorder` 实例
+    ///
+    /// # Arguments
+    /// - `path`: 记录文件路径，已存在时追加写入
+    /// - `format`: `"jsonl"` 或 `"csv"`（默认 `"jsonl"`）
+    /// - `max_bytes`: 文件超过该大小时轮转（默认不按大小轮转）
+    /// - `rotate_interval_ms`: 文件打开超过该时长时轮转（默认不按时间轮转）
+    ///
+    /// 轮转时当前文件被重命名为 `<path>.<unix 毫秒时间戳>`。
+    ///
+    /// # Errors
+    /// - `PyValueError`: If `format` is unknown, or `max_bytes` / `rotate_interval_ms` is not positive
+    /// - `PyIOError`: If the file cannot be opened
+    #[new]
+    #[pyo3(signature = (path, format="jsonl", max_bytes=None, rotate_interval_ms=None))]
+    pub fn new(path: PathBuf, format: &str, max_bytes: Option<i64>, rotate_interval_ms: Option<i64>) -> PyResult<Self> {
+        let format = RecordFormat::parse(format)?;
+        let max_bytes = max_bytes
+            .map(|value| i64_to_u64_positive(value, "max_bytes"))
+            .transpose()?;
+        let rotate_interval = rotate_interval_ms
+            .map(|value| i64_to_u64_positive(value, "rotate_interval_ms").map(Duration::from_millis))
+            .transpose()?;
+
+        let (writer, bytes_written) = RecorderSink::open(&path, format)
+            .map_err(|e| PyErr::new::<PyIOError, _>(format!("Failed to open {}: {e}", path.display())))?;
+
+        Ok(Self {
+            inner: Arc::new(RecorderSink {
+                path,
+                format,
+                max_bytes,
+                rotate_interval,
+                state: Mutex::new(RecorderState {
+                    writer: Some(writer),
+                    bytes_written,
+                    opened_at: Instant::now(),
+                    last_error: None,
+                }),
+            }),
+        })
+    }
+
+    /// 手动写入一个结果
+    #[pyo3(signature = (result, target=""))]
+    pub fn write(&self, result: SinkInput, target: &str) {
+        self.inner.record(target, &result.into_ping_result());
+    }
+
+    /// 刷新缓冲区
+    ///
+    /// # Errors
+    /// - `PyIOError`: If a previous write or the flush failed
+    pub fn flush(&self) -> PyResult<()> {
+        let mut state = self.inner.lock();
+        RecorderSink::flush_state(&mut state).map_err(|e| PyErr::new::<PyIOError, _>(e.to_string()))
+    }
+
+    /// 刷新并关闭文件，之后的结果会被忽略
+    ///
+    /// # Errors
+    /// - `PyIOError`: If a previous write, the flush or the sync failed
+    pub fn close(&self) -> PyResult<()> {
+        self.inner
+            .close()
+            .map_err(|e| PyErr::new::<PyIOError, _>(e.to_string()))
+    }
+
+    /// 记录器是否已关闭
+    #[getter]
+    pub fn closed(&self) -> bool {
+        self.inner.lock().writer.is_none()
+    }
+
+    /// 记录文件路径
+    #[getter]
+    pub fn path(&self) -> PathBuf {
+        self.inner.path.clone()
+    }
+
+    /// 上下文管理器协议：返回自身
+    pub fn __enter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    /// 上下文管理器协议：关闭记录器
+    ///
+    /// # Errors
+    /// - `PyIOError`: If closing the file fails
+    #[pyo3(signature = (*_args))]
+    pub fn __exit__(&self, _args: &Bound<'_, pyo3::types::PyTuple>) -> PyResult<bool> {
+        self.close()?;
+        Ok(false)
+    }
+
+    /// Python `__repr__` method for string representation
+    pub fn __repr__(&self) -> String {
+        format!(
+            "Recorder(path='{}', format='{}', closed={})",
+            self.inner.path.display(),
+            self.inner.format.name(),
+            self.closed()
+        )
+    }
+}
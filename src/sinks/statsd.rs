@@ -0,0 +1,179 @@
+//! `StatsD` 输出端
+//!
+//! 每个结果转换为 `StatsD` 计时和计数器，经 UDP 立即发送。
+
+use super::{bind_udp, resolve_udp, AttachedStreams, ResultSink, SinkInput, StreamInfo};
+use crate::types::result::PingResult;
+use pyo3::exceptions::{PyIOError, PyValueError};
+use pyo3::prelude::*;
+use std::fmt::Write as _;
+use std::net::{SocketAddr, UdpSocket};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// 指标名称中只保留字母、数字、`-` 和 `_`，其余字符替换为 `_`
+fn sanitize(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// `DogStatsD` 标签值中不能出现 `,`、`|` 和 `#`
+fn sanitize_tag(value: &str) -> String {
+    value.replace([',', '|', '#'], "_")
+}
+
+/// 输出端的共享实现
+struct StatsdSinkInner {
+    socket: UdpSocket,
+    addr: SocketAddr,
+    prefix: String,
+    /// 为 true 时以 `DogStatsD` 标签携带目标，否则将目标写入指标名称
+    tags: bool,
+    streams: AttachedStreams,
+    /// 最近一次发送失败的错误，在 `flush` 时报告
+    last_error: Mutex<Option<String>>,
+}
+
+impl StatsdSinkInner {
+    fn last_error(&self) -> MutexGuard<'_, Option<String>> {
+        self.last_error.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// 生成一个结果对应的 `StatsD` 行
+    fn format_lines(&self, target: &str, result: &PingResult) -> Vec<String> {
+        let (name_prefix, suffix) = if self.tags {
+            let mut suffix = format!("|#target:{}", sanitize_tag(target));
+            if let Some(info) = self.streams.get(target) {
+                if let Some(interface) = &info.interface {
+                    let _ = write!(suffix, ",interface:{}", sanitize_tag(interface));
+                }
+                let _ = write!(suffix, ",address_family:{}", info.address_family);
+            }
+            (self.prefix.clone(), suffix)
+        } else {
+            (format!("{}.{}", self.prefix, sanitize(target)), String::new())
+        };
+
+        let metric = |name: &str, value: &str, kind: &str| format!("{name_prefix}.{name}:{value}|{kind}{suffix}");
+        match result {
+            // NaN 和无穷大不是有效的计时值，只计数
+            PingResult::Pong { duration_ms, .. } if duration_ms.is_finite() => {
+                vec![metric("rtt", &duration_ms.to_string(), "ms"), metric("sent", "1", "c")]
+            }
+            PingResult::Pong { .. } => vec![metric("sent", "1", "c")],
+            PingResult::Timeout { .. } | PingResult::PortUnreachable { .. } => {
+                vec![metric("sent", "1", "c"), metric("lost", "1", "c")]
+            }
+            PingResult::Unknown { .. } => Vec::new(),
+            PingResult::PingExited { .. } => vec![metric("exits", "1", "c")],
+        }
+    }
+}
+
+impl ResultSink for StatsdSinkInner {
+    fn record(&self, target: &str, result: &PingResult) {
+        let lines = self.format_lines(target, result);
+        if lines.is_empty() {
+            return;
+        }
+        // 同一结果的指标合并为一个数据报
+        if let Err(e) = self.socket.send_to(lines.join("\n").as_bytes(), self.addr) {
+            *self.last_error() = Some(format!("Failed to send to {}: {e}", self.addr));
+        }
+    }
+
+    fn attach(&self, info: &StreamInfo) {
+        self.streams.insert(info);
+    }
+}
+
+/// 以 `StatsD` 计时和计数器输出流结果的输出端
+///
+/// 通过 `PingStream.add_sink()` / `AsyncPingStream.add_sink()` 挂载到流上。
+/// 成功时发送 `<prefix>.rtt`（`ms`）和 `<prefix>.sent`，超时发送 `<prefix>.sent` 和 `<prefix>.lost`，
+/// ping 进程退出时发送 `<prefix>.exits`。
+#[pyclass(module = "ping_rs._ping_rs")]
+pub struct StatsdSink {
+    inner: Arc<StatsdSinkInner>,
+}
+
+impl StatsdSink {
+    /// 获取可挂载到流上的输出端
+    pub fn sink(&self) -> Arc<dyn ResultSink> {
+        self.inner.clone()
+    }
+}
+
+#[pymethods]
+impl StatsdSink {
+    /// 创建新的 `StatsdSink` 实例
+    ///
+    /// # Arguments
+    /// - `host`: `StatsD` 服务器地址（默认 `"127.0.0.1"`）
+    /// - `port`: `StatsD` 服务器端口（默认 8125）
+    /// - `prefix`: 指标名称前缀（默认 `"ping"`）
+    /// - `tags`: 为 `True` 时使用 `DogStatsD` 标签（`|#target:...`）携带目标，
+    ///   否则目标写入指标名称（`<prefix>.<target>.rtt`）
+    ///
+    /// # Errors
+    /// - `PyValueError`: If the address cannot be resolved or `prefix` is empty
+    /// - `PyIOError`: If the UDP socket cannot be bound
+    #[new]
+    #[pyo3(signature = (host="127.0.0.1", port=8125, prefix="ping", tags=false))]
+    pub fn new(host: &str, port: u16, prefix: &str, tags: bool) -> PyResult<Self> {
+        if prefix.is_empty() {
+            return Err(PyErr::new::<PyValueError, _>("prefix must not be empty"));
+        }
+        let addr = if host.contains(':') {
+            resolve_udp(&format!("[{host}]:{port}"))?
+        } else {
+            resolve_udp(&format!("{host}:{port}"))?
+        };
+        let socket = bind_udp(addr)?;
+
+        Ok(Self {
+            inner: Arc::new(StatsdSinkInner {
+                socket,
+                addr,
+                prefix: prefix.to_string(),
+                tags,
+                streams: AttachedStreams::default(),
+                last_error: Mutex::new(None),
+            }),
+        })
+    }
+
+    /// 手动写入一个结果
+    #[pyo3(signature = (result, target=""))]
+    pub fn write(&self, result: SinkInput, target: &str) {
+        self.inner.record(target, &result.into_ping_result());
+    }
+
+    /// 报告之前的发送错误（指标总是立即发送，没有缓冲区）
+    ///
+    /// # Errors
+    /// - `PyIOError`: If a previous send failed
+    pub fn flush(&self) -> PyResult<()> {
+        match self.inner.last_error().take() {
+            Some(e) => Err(PyErr::new::<PyIOError, _>(e)),
+            None => Ok(()),
+        }
+    }
+
+    /// Python `__repr__` method for string representation
+    pub fn __repr__(&self) -> String {
+        format!(
+            "StatsdSink(address='{}', prefix='{}', tags={})",
+            self.inner.addr,
+            self.inner.prefix,
+            if self.inner.tags { "True" } else { "False" }
+        )
+    }
+}
@@ -0,0 +1,152 @@
+use crate::types::result::PingResult;
+use crate::utils::conversion::unix_timestamp;
+use pyo3::exceptions::PyIndexError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyList};
+
+/// 批量 ping 结果的列式存储
+///
+/// 结果保存在 Rust 侧，列数据以 `array.array` 形式导出（支持缓冲区协议），
+/// 可以直接交给 `numpy.frombuffer` 或 `pandas`，无需为每个样本创建 Python 对象。
+#[pyclass(module = "ping_rs._ping_rs", sequence)]
+#[derive(Debug, Clone, Default)]
+pub struct PingResultBatch {
+    timestamps: Vec<f64>,
+    results: Vec<PingResult>,
+}
+
+impl PingResultBatch {
+    /// 追加一个结果，时间戳为当前时间
+    pub fn push(&mut self, result: PingResult) {
+        self.push_with_timestamp(result, unix_timestamp());
+    }
+
+    /// 追加一个带时间戳（Unix 秒）的结果
+    pub fn push_with_timestamp(&mut self, result: PingResult, timestamp: f64) {
+        self.timestamps.push(timestamp);
+        self.results.push(result);
+    }
+
+    /// 转换为结果列表
+    pub fn into_results(self) -> Vec<PingResult> {
+        self.results
+    }
+}
+
+#[pymethods]
+impl PingResultBatch {
+    /// 从结果列表创建批量结果
+    ///
+    /// # Arguments
+    /// - `results`: `PingResult` 列表
+    /// - `timestamps`: 每个结果对应的 Unix 时间戳（秒），默认为 NaN
+    ///
+    /// # Errors
+    /// - `PyValueError`: If `timestamps` length does not match `results`
+    #[new]
+    #[pyo3(signature = (results=Vec::new(), timestamps=None))]
+    pub fn new(results: Vec<PingResult>, timestamps: Option<Vec<f64>>) -> PyResult<Self> {
+        let timestamps = match timestamps {
+            Some(ts) if ts.len() != results.len() => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "timestamps length ({}) must match results length ({})",
+                    ts.len(),
+                    results.len()
+                )));
+            }
+            Some(ts) => ts,
+            None => vec![f64::NAN; results.len()],
+        };
+        Ok(Self { timestamps, results })
+    }
+
+    /// 结果的 Unix 时间戳（秒），`array.array('d')`
+    ///
+    /// # Errors
+    /// - `PyErr`: If the `array` module cannot be used
+    #[getter]
+    pub fn timestamps<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let bytes: Vec<u8> = self.timestamps.iter().flat_map(|v| v.to_ne_bytes()).collect();
+        new_array(py, "d", &bytes)
+    }
+
+    /// 往返延迟（毫秒），非 `Pong` / `PortUnreachable` 结果为 NaN，`array.array('d')`
+    ///
+    /// # Errors
+    /// - `PyErr`: If the `array` module cannot be used
+    #[getter]
+    pub fn rtts_ms<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let bytes: Vec<u8> = self
+            .results
+            .iter()
+            .flat_map(|r| r.duration_ms().unwrap_or(f64::NAN).to_ne_bytes())
+            .collect();
+        new_array(py, "d", &bytes)
+    }
+
+    /// 结果类型代码（见 `PingResult.type_code`），`array.array('B')`
+    ///
+    /// # Errors
+    /// - `PyErr`: If the `array` module cannot be used
+    #[getter]
+    pub fn type_codes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let bytes: Vec<u8> = self.results.iter().map(PingResult::type_code).collect();
+        new_array(py, "B", &bytes)
+    }
+
+    /// 成功（`Pong`）的结果数量
+    pub fn success_count(&self) -> usize {
+        self.results.iter().filter(|r| r.is_success()).count()
+    }
+
+    /// 转换为 `PingResult` 列表
+    pub fn to_list(&self) -> Vec<PingResult> {
+        self.results.clone()
+    }
+
+    /// Python `__len__` method
+    pub fn __len__(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Python `__getitem__` method，支持负索引
+    ///
+    /// # Errors
+    /// - `PyIndexError`: If the index is out of range
+    pub fn __getitem__(&self, index: isize) -> PyResult<PingResult> {
+        let len = self.results.len();
+        let resolved = if index < 0 {
+            len.checked_sub(index.unsigned_abs())
+        } else {
+            Some(index.unsigned_abs())
+        };
+        resolved
+            .and_then(|i| self.results.get(i))
+            .cloned()
+            .ok_or_else(|| PyErr::new::<PyIndexError, _>("PingResultBatch index out of range"))
+    }
+
+    /// Python `__iter__` method
+    ///
+    /// # Errors
+    /// - `PyErr`: If the iterator cannot be created
+    pub fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        Ok(PyList::new(py, self.results.clone())?.try_iter()?.into_any())
+    }
+
+    /// Python `__repr__` method for string representation
+    pub fn __repr__(&self) -> String {
+        format!(
+            "PingResultBatch(len={}, success={})",
+            self.results.len(),
+            self.success_count()
+        )
+    }
+}
+
+/// 使用原生字节序的字节创建 `array.array`
+fn new_array<'py>(py: Python<'py>, typecode: &str, bytes: &[u8]) -> PyResult<Bound<'py, PyAny>> {
+    let array = py.import("array")?.getattr("array")?.call1((typecode,))?;
+    array.call_method1("frombytes", (PyBytes::new(py, bytes),))?;
+    Ok(array)
+}
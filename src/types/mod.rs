@@ -1,2 +1,4 @@
+pub mod batch;
 pub mod options;
 pub mod result;
+pub mod stats;
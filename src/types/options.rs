@@ -1,14 +1,67 @@
+use crate::protocols::dns::resolver::DnsResolver;
+use crate::utils::backoff::BackoffPolicy;
+use crate::utils::validation::i64_to_u64_positive;
+use pinger::PingOptions;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::sync::Arc;
 use std::time::Duration;
 
+/// 主机名解析到多个地址时的处理方式
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ResolveMode {
+    /// 使用第一个地址
+    #[default]
+    First,
+    /// 并发 ping 每个 A / AAAA 地址，分别报告结果
+    All,
+    /// IPv6 优先、IPv4 回退（RFC 8305），使用最先响应的地址
+    HappyEyeballs,
+}
+
+impl ResolveMode {
+    /// 从模式名解析
+    ///
+    /// # Errors
+    /// - `PyValueError`: If `name` is not `first`, `all` or `happy_eyeballs`
+    pub fn from_name(name: &str) -> PyResult<Self> {
+        match name {
+            "first" => Ok(Self::First),
+            "all" => Ok(Self::All),
+            "happy_eyeballs" => Ok(Self::HappyEyeballs),
+            _ => Err(PyErr::new::<PyValueError, _>(format!(
+                "resolve_mode must be 'first', 'all' or 'happy_eyeballs', got '{name}'"
+            ))),
+        }
+    }
+
+    /// 解析只使用单个地址的模式（`first` 或 `happy_eyeballs`）
+    ///
+    /// # Errors
+    /// - `PyValueError`: If `name` is invalid or `all`
+    pub fn single_from_name(name: &str) -> PyResult<Self> {
+        match Self::from_name(name)? {
+            Self::All => Err(PyErr::new::<PyValueError, _>(
+                "resolve_mode='all' is only supported by ping_once() and ping_multiple()",
+            )),
+            mode => Ok(mode),
+        }
+    }
+}
+
 /// DNS 预解析配置选项
 ///
 /// 用于控制 DNS 主机名解析的行为
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub struct DnsPreResolveOptions {
     /// 是否启用 DNS 预解析（默认为 true）
     pub enable: bool,
     /// DNS 解析超时时间（默认为 None，表示使用 options.interval）
     pub timeout: Option<Duration>,
+    /// 自定义解析器（默认为 None，表示使用系统解析器）
+    pub resolver: Option<Arc<DnsResolver>>,
+    /// 多地址的处理方式（默认为 `First`）
+    pub mode: ResolveMode,
 }
 
 impl Default for DnsPreResolveOptions {
@@ -16,6 +69,109 @@ impl Default for DnsPreResolveOptions {
         Self {
             enable: true,
             timeout: None,
+            resolver: None,
+            mode: ResolveMode::First,
+        }
+    }
+}
+
+/// 流在 ping 进程退出后的自动重启策略
+#[derive(Clone, Copy, Debug)]
+pub struct RestartPolicy {
+    /// 重启前的退避等待
+    pub backoff: BackoffPolicy,
+    /// 连续重启的最大次数（None 表示不限）
+    pub max_restarts: Option<u32>,
+}
+
+impl RestartPolicy {
+    /// 重启等待时间的上限
+    const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+    /// 从流的参数创建，`restart_on_exit` 为 false 时返回 None
+    ///
+    /// 等待时间从 `restart_backoff_ms` 开始逐次翻倍，最多 60 秒。
+    ///
+    /// # Errors
+    /// - `PyValueError`: If `restart_backoff_ms` is not positive
+    pub fn from_args(
+        restart_on_exit: bool,
+        restart_backoff_ms: i64,
+        max_restarts: Option<u32>,
+    ) -> PyResult<Option<Self>> {
+        if !restart_on_exit {
+            return Ok(None);
+        }
+        let initial = Duration::from_millis(i64_to_u64_positive(restart_backoff_ms, "restart_backoff_ms")?);
+        Ok(Some(Self {
+            backoff: BackoffPolicy {
+                initial,
+                max: Self::MAX_BACKOFF.max(initial),
+                multiplier: 2.0,
+            },
+            max_restarts,
+        }))
+    }
+}
+
+/// 探测包的负载大小和禁止分片选项
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PacketOptions {
+    /// ICMP 负载大小（字节），None 使用 ping 的默认值
+    pub size: Option<u32>,
+    /// 是否设置禁止分片
+    pub dont_fragment: bool,
+}
+
+impl PacketOptions {
+    /// 负载大小的下限：更小的包中 ping 不携带时间戳，无法测量延迟
+    pub const MIN_SIZE: u32 = 16;
+    /// 负载大小的上限（Windows ping.exe 的限制）
+    pub const MAX_SIZE: u32 = 65_500;
+
+    /// 从 Python 参数创建
+    ///
+    /// # Errors
+    /// - `PyValueError`: If `size` is outside `[MIN_SIZE, MAX_SIZE]`
+    pub fn from_args(size: Option<i64>, dont_fragment: bool) -> PyResult<Self> {
+        let size = size
+            .map(|size| {
+                u32::try_from(size)
+                    .ok()
+                    .filter(|size| (Self::MIN_SIZE..=Self::MAX_SIZE).contains(size))
+                    .ok_or_else(|| {
+                        PyErr::new::<PyValueError, _>(format!(
+                            "size must be between {} and {} bytes, got {size}",
+                            Self::MIN_SIZE,
+                            Self::MAX_SIZE
+                        ))
+                    })
+            })
+            .transpose()?;
+        Ok(Self { size, dont_fragment })
+    }
+
+    /// 写入 ping 命令的附加参数（`PingOptions::raw_arguments`）
+    ///
+    /// Linux 使用 `-s` / `-M do`，macOS / FreeBSD 使用 `-s` / `-D`（`ping6` 改为 `-m`），
+    /// Windows 使用 `-l` / `-f`，见 `protocols::icmp::command`。都未设置时不修改 `options`。
+    pub fn apply(self, options: &mut PingOptions) {
+        let mut args = Vec::new();
+        if let Some(size) = self.size {
+            args.push(if cfg!(windows) { "-l" } else { "-s" }.to_string());
+            args.push(size.to_string());
+        }
+        if self.dont_fragment {
+            if cfg!(windows) {
+                args.push("-f".to_string());
+            } else if cfg!(any(target_os = "macos", target_os = "freebsd")) {
+                args.push("-D".to_string());
+            } else {
+                args.extend(["-M".to_string(), "do".to_string()]);
+            }
+        }
+        if !args.is_empty() {
+            options.raw_arguments = Some(args);
         }
     }
 }
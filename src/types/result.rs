@@ -1,10 +1,21 @@
+use crate::utils::locale::{parse_localized_line, LocalizedLine};
 use pinger::PingResult as RustPingResult;
+use pyo3::basic::CompareOp;
+use pyo3::exceptions::{PyKeyError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::PyDict;
+use pyo3::types::{PyBool, PyDict, PyTuple, PyType};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 
 /// Python 包装的 `PingResult` 枚举
-#[pyclass]
-#[derive(Debug, Clone)]
+///
+/// 序列化格式与 `to_dict` 一致，以 `type` 字段区分变体
+#[pyclass(module = "ping_rs._ping_rs")]
+#[allow(clippy::unsafe_derive_deserialize)] // unsafe 来自 pyo3 生成的方法，与反序列化无关
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
 pub enum PingResult {
     /// 成功的 ping 响应，包含延迟时间（毫秒）和原始行
     Pong {
@@ -30,6 +41,13 @@ pub enum PingResult {
         /// 标准错误输出
         stderr: String,
     },
+    /// 目标端口不可达（收到 ICMP port unreachable，仅 UDP 探测）
+    PortUnreachable {
+        /// 收到不可达消息的时间（毫秒）
+        duration_ms: f64,
+        /// 描述信息
+        line: String,
+    },
 }
 
 #[pymethods]
@@ -45,6 +63,9 @@ impl PingResult {
             Self::PingExited { exit_code, stderr } => {
                 format!("PingResult.PingExited(exit_code={exit_code}, stderr='{stderr}')")
             }
+            Self::PortUnreachable { duration_ms, line } => {
+                format!("PingResult.PortUnreachable(duration_ms={duration_ms}ms, line='{line}')")
+            }
         }
     }
 
@@ -53,11 +74,33 @@ impl PingResult {
         self.__repr__()
     }
 
-    /// 获取延迟时间（毫秒），如果不是 Pong 则返回 None
+    /// Python 比较协议：结构相等与全序比较
+    ///
+    /// 不同变体之间按 `Pong < Timeout < Unknown < PingExited < PortUnreachable` 排序，
+    /// 同一变体按字段依次比较；与非 `PingResult` 对象比较时返回 `NotImplemented`
+    pub fn __richcmp__(&self, other: &Bound<'_, PyAny>, op: CompareOp) -> Py<PyAny> {
+        let py = other.py();
+        let Ok(other) = other.extract::<PyRef<'_, Self>>() else {
+            return py.NotImplemented();
+        };
+        PyBool::new(py, op.matches(self.cmp(&other)))
+            .to_owned()
+            .into_any()
+            .unbind()
+    }
+
+    /// Python 哈希协议，与 `__eq__` 保持一致（浮点数按位模式参与哈希）
+    pub fn __hash__(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// 获取延迟时间（毫秒），如果不是 `Pong` 或 `PortUnreachable` 则返回 None
     #[getter]
     pub fn duration_ms(&self) -> Option<f64> {
         match self {
-            Self::Pong { duration_ms, .. } => Some(*duration_ms),
+            Self::Pong { duration_ms, .. } | Self::PortUnreachable { duration_ms, .. } => Some(*duration_ms),
             _ => None,
         }
     }
@@ -66,7 +109,7 @@ impl PingResult {
     #[getter]
     pub fn line(&self) -> String {
         match self {
-            Self::Pong { line, .. } => line.clone(),
+            Self::Pong { line, .. } | Self::PortUnreachable { line, .. } => line.clone(),
             Self::Timeout { line } => line.clone(),
             Self::Unknown { line } => line.clone(),
             Self::PingExited { stderr, .. } => stderr.clone(),
@@ -111,6 +154,11 @@ impl PingResult {
         matches!(self, Self::PingExited { .. })
     }
 
+    /// 检查是否为端口不可达
+    pub fn is_port_unreachable(&self) -> bool {
+        matches!(self, Self::PortUnreachable { .. })
+    }
+
     /// 获取 `PingResult` 的类型名称
     #[getter]
     pub fn type_name(&self) -> String {
@@ -119,6 +167,32 @@ impl PingResult {
             Self::Timeout { .. } => "Timeout".to_string(),
             Self::Unknown { .. } => "Unknown".to_string(),
             Self::PingExited { .. } => "PingExited".to_string(),
+            Self::PortUnreachable { .. } => "PortUnreachable".to_string(),
+        }
+    }
+
+    /// 解析一行 ping 命令输出
+    ///
+    /// 先按 C 语言环境的英文格式解析，无法识别时再尝试本地化输出（翻译后的关键字、逗号小数点），
+    /// 仍无法识别的行返回 `Unknown`
+    #[staticmethod]
+    pub fn parse_line(line: &str) -> Self {
+        crate::protocols::icmp::command::parse_line(line.to_string())
+            .unwrap_or_else(|| RustPingResult::Unknown(line.to_string()))
+            .into()
+    }
+
+    /// 获取 `PingResult` 的类型代码，用于列式导出和排序
+    ///
+    /// `Pong` = 0, `Timeout` = 1, `Unknown` = 2, `PingExited` = 3, `PortUnreachable` = 4
+    #[getter]
+    pub fn type_code(&self) -> u8 {
+        match self {
+            Self::Pong { .. } => 0,
+            Self::Timeout { .. } => 1,
+            Self::Unknown { .. } => 2,
+            Self::PingExited { .. } => 3,
+            Self::PortUnreachable { .. } => 4,
         }
     }
 
@@ -148,10 +222,172 @@ impl PingResult {
                 dict.set_item("exit_code", *exit_code)?;
                 dict.set_item("stderr", stderr.clone())?;
             }
+            Self::PortUnreachable { duration_ms, line } => {
+                dict.set_item("type", "PortUnreachable")?;
+                dict.set_item("duration_ms", *duration_ms)?;
+                dict.set_item("line", line.clone())?;
+            }
         }
 
         Ok(dict.into())
     }
+
+    /// 将 `PingResult` 序列化为 JSON 字符串
+    ///
+    /// # Errors
+    /// - `PyValueError`: If serialization fails (e.g. `duration_ms` is not a finite number)
+    pub fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string(self)
+            .map_err(|e| PyErr::new::<PyValueError, _>(format!("Failed to serialize PingResult: {e}")))
+    }
+
+    /// 从 JSON 字符串反序列化 `PingResult`
+    ///
+    /// # Errors
+    /// - `PyValueError`: If the JSON is malformed or does not describe a `PingResult`
+    #[classmethod]
+    pub fn from_json(_cls: &Bound<'_, PyType>, data: &str) -> PyResult<Self> {
+        serde_json::from_str(data)
+            .map_err(|e| PyErr::new::<PyValueError, _>(format!("Failed to deserialize PingResult: {e}")))
+    }
+
+    /// 从 `to_dict` 生成的字典构造 `PingResult`
+    ///
+    /// # Errors
+    /// - `PyKeyError`: If a required key is missing
+    /// - `PyValueError`: If the `type` key is not a known variant
+    /// - `PyTypeError`: If a value has the wrong type
+    #[classmethod]
+    pub fn from_dict(_cls: &Bound<'_, PyType>, data: &Bound<'_, PyDict>) -> PyResult<Self> {
+        let get = |key: &str| -> PyResult<Bound<'_, PyAny>> {
+            data.get_item(key)?
+                .ok_or_else(|| PyErr::new::<PyKeyError, _>(key.to_string()))
+        };
+
+        let type_name: String = get("type")?.extract()?;
+        match type_name.as_str() {
+            "Pong" => Ok(Self::Pong {
+                duration_ms: get("duration_ms")?.extract()?,
+                line: get("line")?.extract()?,
+            }),
+            "Timeout" => Ok(Self::Timeout {
+                line: get("line")?.extract()?,
+            }),
+            "Unknown" => Ok(Self::Unknown {
+                line: get("line")?.extract()?,
+            }),
+            "PingExited" => Ok(Self::PingExited {
+                exit_code: get("exit_code")?.extract()?,
+                stderr: get("stderr")?.extract()?,
+            }),
+            "PortUnreachable" => Ok(Self::PortUnreachable {
+                duration_ms: get("duration_ms")?.extract()?,
+                line: get("line")?.extract()?,
+            }),
+            other => Err(PyErr::new::<PyValueError, _>(format!(
+                "Unknown PingResult type: {other}"
+            ))),
+        }
+    }
+
+    /// Python pickle 协议：通过 JSON 重建，便于跨 `multiprocessing` 边界传递
+    ///
+    /// # Errors
+    /// - `PyValueError`: If serialization fails
+    pub fn __reduce__<'py>(&self, py: Python<'py>) -> PyResult<(Bound<'py, PyAny>, (String,))> {
+        let from_json = py.get_type::<Self>().getattr("from_json")?;
+        Ok((from_json, (self.to_json()?,)))
+    }
+
+    /// Python pickle 协议：返回变体构造函数的参数
+    ///
+    /// # Errors
+    /// - `PyErr`: If the argument tuple cannot be created
+    pub fn __getnewargs__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyTuple>> {
+        match self {
+            Self::Pong { duration_ms, line } | Self::PortUnreachable { duration_ms, line } => {
+                Ok((*duration_ms, line.clone()).into_pyobject(py)?)
+            }
+            Self::Timeout { line } | Self::Unknown { line } => Ok((line.clone(),).into_pyobject(py)?),
+            Self::PingExited { exit_code, stderr } => Ok((*exit_code, stderr.clone()).into_pyobject(py)?),
+        }
+    }
+}
+
+impl PingResult {
+    /// 对底层解析器无法识别的行做本地化补充解析
+    fn from_unknown_line(line: String) -> Self {
+        match parse_localized_line(&line) {
+            Some(LocalizedLine::Pong(duration_ms)) => Self::Pong { duration_ms, line },
+            Some(LocalizedLine::Timeout) => Self::Timeout { line },
+            None => Self::Unknown { line },
+        }
+    }
+}
+
+// 浮点数按位模式比较，使 `Eq` 与 `Hash` 保持一致（NaN 等于自身，0.0 与 -0.0 不相等）
+impl PartialEq for PingResult {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for PingResult {}
+
+impl PartialOrd for PingResult {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PingResult {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (
+                Self::Pong { duration_ms, line },
+                Self::Pong {
+                    duration_ms: other_duration_ms,
+                    line: other_line,
+                },
+            )
+            | (
+                Self::PortUnreachable { duration_ms, line },
+                Self::PortUnreachable {
+                    duration_ms: other_duration_ms,
+                    line: other_line,
+                },
+            ) => duration_ms
+                .total_cmp(other_duration_ms)
+                .then_with(|| line.cmp(other_line)),
+            (Self::Timeout { line }, Self::Timeout { line: other_line })
+            | (Self::Unknown { line }, Self::Unknown { line: other_line }) => line.cmp(other_line),
+            (
+                Self::PingExited { exit_code, stderr },
+                Self::PingExited {
+                    exit_code: other_exit_code,
+                    stderr: other_stderr,
+                },
+            ) => exit_code.cmp(other_exit_code).then_with(|| stderr.cmp(other_stderr)),
+            _ => self.type_code().cmp(&other.type_code()),
+        }
+    }
+}
+
+impl Hash for PingResult {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.type_code().hash(state);
+        match self {
+            Self::Pong { duration_ms, line } | Self::PortUnreachable { duration_ms, line } => {
+                duration_ms.to_bits().hash(state);
+                line.hash(state);
+            }
+            Self::Timeout { line } | Self::Unknown { line } => line.hash(state),
+            Self::PingExited { exit_code, stderr } => {
+                exit_code.hash(state);
+                stderr.hash(state);
+            }
+        }
+    }
 }
 
 impl From<RustPingResult> for PingResult {
@@ -162,7 +398,7 @@ impl From<RustPingResult> for PingResult {
                 line,
             },
             RustPingResult::Timeout(line) => Self::Timeout { line },
-            RustPingResult::Unknown(line) => Self::Unknown { line },
+            RustPingResult::Unknown(line) => Self::from_unknown_line(line),
             RustPingResult::PingExited(status, stderr) => Self::PingExited {
                 exit_code: status.code().unwrap_or(-1),
                 stderr,
@@ -0,0 +1,246 @@
+use crate::types::result::PingResult;
+use pyo3::prelude::*;
+use std::collections::VecDeque;
+
+/// 最近 N 次探测的滑动窗口统计，以及累计的发送/接收计数
+#[derive(Debug, Clone)]
+pub struct RollingStats {
+    window: usize,
+    /// 最近的探测，`None` 表示超时
+    samples: VecDeque<Option<f64>>,
+    packets_sent: u64,
+    packets_received: u64,
+}
+
+/// 滑动窗口内的延迟统计（毫秒）
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(clippy::struct_field_names)]
+pub struct RttSummary {
+    /// 最小值
+    pub min_ms: f64,
+    /// 平均值
+    pub avg_ms: f64,
+    /// 最大值
+    pub max_ms: f64,
+    /// 标准差
+    pub stddev_ms: f64,
+    /// 相邻样本差值绝对值的平均
+    pub jitter_ms: f64,
+}
+
+impl RollingStats {
+    /// 创建窗口大小为 `window` 的统计（至少为 1）
+    pub fn new(window: usize) -> Self {
+        let window = window.max(1);
+        Self {
+            window,
+            samples: VecDeque::with_capacity(window),
+            packets_sent: 0,
+            packets_received: 0,
+        }
+    }
+
+    /// 记录一个结果，只有 `Pong`、`Timeout` 和 `PortUnreachable`（按丢失计）计入统计
+    pub fn push(&mut self, result: &PingResult) {
+        let sample = match result {
+            PingResult::Pong { duration_ms, .. } => Some(*duration_ms),
+            PingResult::Timeout { .. } | PingResult::PortUnreachable { .. } => None,
+            PingResult::Unknown { .. } | PingResult::PingExited { .. } => return,
+        };
+        self.push_sample(sample);
+    }
+
+    /// 记录一次探测的延迟（毫秒），`None` 表示超时
+    pub fn push_sample(&mut self, sample: Option<f64>) {
+        self.packets_sent += 1;
+        if sample.is_some() {
+            self.packets_received += 1;
+        }
+        if self.samples.len() == self.window {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    /// 累计发送数
+    pub fn packets_sent(&self) -> u64 {
+        self.packets_sent
+    }
+
+    /// 累计接收数
+    pub fn packets_received(&self) -> u64 {
+        self.packets_received
+    }
+
+    /// 窗口内的丢包率（百分比），窗口为空时为 `None`
+    #[allow(clippy::cast_precision_loss)]
+    pub fn loss_pct(&self) -> Option<f64> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let lost = self.samples.iter().filter(|s| s.is_none()).count();
+        Some(lost as f64 * 100.0 / self.samples.len() as f64)
+    }
+
+    /// 窗口内最近一次成功的延迟
+    pub fn last_rtt_ms(&self) -> Option<f64> {
+        self.samples.iter().rev().find_map(|s| *s)
+    }
+
+    /// 窗口内的延迟统计，没有成功样本时为 `None`
+    #[allow(clippy::cast_precision_loss)]
+    pub fn rtt_summary(&self) -> Option<RttSummary> {
+        let rtts: Vec<f64> = self.samples.iter().filter_map(|s| *s).collect();
+        if rtts.is_empty() {
+            return None;
+        }
+
+        let count = rtts.len() as f64;
+        let min_ms = rtts.iter().copied().fold(f64::INFINITY, f64::min);
+        let max_ms = rtts.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let avg_ms = rtts.iter().sum::<f64>() / count;
+        let variance = rtts.iter().map(|r| (r - avg_ms).powi(2)).sum::<f64>() / count;
+        let jitter_ms = if rtts.len() > 1 {
+            rtts.windows(2)
+                .map(|pair| match pair {
+                    [a, b] => (b - a).abs(),
+                    _ => 0.0,
+                })
+                .sum::<f64>()
+                / (count - 1.0)
+        } else {
+            0.0
+        };
+
+        Some(RttSummary {
+            min_ms,
+            avg_ms,
+            max_ms,
+            stddev_ms: variance.sqrt(),
+            jitter_ms,
+        })
+    }
+}
+
+/// 单个目标的统计快照
+#[pyclass(module = "ping_rs._ping_rs", frozen)]
+#[derive(Debug, Clone)]
+pub struct PingStats {
+    /// 目标主机名或 IP 地址
+    #[pyo3(get)]
+    pub target: String,
+    /// 状态：`"up"`、`"down"` 或尚未收到结果时为 `"unknown"`
+    #[pyo3(get)]
+    pub state: String,
+    /// 累计发送数
+    #[pyo3(get)]
+    pub packets_sent: u64,
+    /// 累计接收数
+    #[pyo3(get)]
+    pub packets_received: u64,
+    /// 窗口内的丢包率（百分比）
+    #[pyo3(get)]
+    pub loss_pct: Option<f64>,
+    /// 窗口内的最小延迟（毫秒）
+    #[pyo3(get)]
+    pub min_ms: Option<f64>,
+    /// 窗口内的平均延迟（毫秒）
+    #[pyo3(get)]
+    pub avg_ms: Option<f64>,
+    /// 窗口内的最大延迟（毫秒）
+    #[pyo3(get)]
+    pub max_ms: Option<f64>,
+    /// 窗口内延迟的标准差（毫秒）
+    #[pyo3(get)]
+    pub stddev_ms: Option<f64>,
+    /// 窗口内的抖动（毫秒）
+    #[pyo3(get)]
+    pub jitter_ms: Option<f64>,
+    /// 最近一次成功的延迟（毫秒）
+    #[pyo3(get)]
+    pub last_rtt_ms: Option<f64>,
+    /// 最近一个结果
+    #[pyo3(get)]
+    pub latest: Option<PingResult>,
+    /// 最近一个结果的 Unix 时间戳（秒）
+    #[pyo3(get)]
+    pub last_update: Option<f64>,
+    /// ping 进程的重启次数
+    #[pyo3(get)]
+    pub restarts: u64,
+    /// DNS 解析失败次数
+    #[pyo3(get)]
+    pub dns_failures: u64,
+}
+
+impl PingStats {
+    /// 从滑动窗口统计创建快照，其余字段为初始值
+    pub fn from_rolling(target: &str, stats: &RollingStats) -> Self {
+        let summary = stats.rtt_summary();
+        Self {
+            target: target.to_string(),
+            state: "unknown".to_string(),
+            packets_sent: stats.packets_sent(),
+            packets_received: stats.packets_received(),
+            loss_pct: stats.loss_pct(),
+            min_ms: summary.map(|s| s.min_ms),
+            avg_ms: summary.map(|s| s.avg_ms),
+            max_ms: summary.map(|s| s.max_ms),
+            stddev_ms: summary.map(|s| s.stddev_ms),
+            jitter_ms: summary.map(|s| s.jitter_ms),
+            last_rtt_ms: stats.last_rtt_ms(),
+            latest: None,
+            last_update: None,
+            restarts: 0,
+            dns_failures: 0,
+        }
+    }
+}
+
+#[pymethods]
+impl PingStats {
+    /// 累计丢失数
+    #[getter]
+    pub fn packets_lost(&self) -> u64 {
+        self.packets_sent - self.packets_received
+    }
+
+    /// 转换为字典
+    ///
+    /// # Errors
+    /// - `PyErr`: If a value cannot be converted to a Python object
+    pub fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, pyo3::types::PyDict>> {
+        let dict = pyo3::types::PyDict::new(py);
+        dict.set_item("target", &self.target)?;
+        dict.set_item("state", &self.state)?;
+        dict.set_item("packets_sent", self.packets_sent)?;
+        dict.set_item("packets_received", self.packets_received)?;
+        dict.set_item("packets_lost", self.packets_lost())?;
+        dict.set_item("loss_pct", self.loss_pct)?;
+        dict.set_item("min_ms", self.min_ms)?;
+        dict.set_item("avg_ms", self.avg_ms)?;
+        dict.set_item("max_ms", self.max_ms)?;
+        dict.set_item("stddev_ms", self.stddev_ms)?;
+        dict.set_item("jitter_ms", self.jitter_ms)?;
+        dict.set_item("last_rtt_ms", self.last_rtt_ms)?;
+        dict.set_item("latest", self.latest.clone())?;
+        dict.set_item("last_update", self.last_update)?;
+        dict.set_item("restarts", self.restarts)?;
+        dict.set_item("dns_failures", self.dns_failures)?;
+        Ok(dict)
+    }
+
+    /// Python `__repr__` method for string representation
+    pub fn __repr__(&self) -> String {
+        let fmt = |v: Option<f64>| v.map_or_else(|| "None".to_string(), |v| format!("{v:.3}"));
+        format!(
+            "PingStats(target='{}', state='{}', sent={}, received={}, loss_pct={}, avg_ms={})",
+            self.target,
+            self.state,
+            self.packets_sent,
+            self.packets_received,
+            fmt(self.loss_pct),
+            fmt(self.avg_ms)
+        )
+    }
+}
@@ -0,0 +1,48 @@
+//! 重启等待时间的指数退避
+
+use std::time::Duration;
+
+/// 指数退避策略
+///
+/// 第 `attempt` 次（从 0 开始）重试前等待 `initial * multiplier^attempt`，不超过 `max`。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BackoffPolicy {
+    /// 首次重试前的等待时间
+    pub initial: Duration,
+    /// 等待时间上限
+    pub max: Duration,
+    /// 每次重试的倍数
+    pub multiplier: f64,
+}
+
+impl BackoffPolicy {
+    /// 固定等待时间，不做退避
+    pub fn fixed(delay: Duration) -> Self {
+        Self {
+            initial: delay,
+            max: delay,
+            multiplier: 1.0,
+        }
+    }
+
+    /// 第 `attempt` 次重试前的等待时间
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = self.multiplier.powi(i32::try_from(attempt).unwrap_or(i32::MAX));
+        let delay = self.initial.as_secs_f64() * factor;
+        if delay.is_finite() && delay < self.max.as_secs_f64() {
+            Duration::from_secs_f64(delay)
+        } else {
+            self.max
+        }
+    }
+}
+
+impl Default for BackoffPolicy {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_secs(1),
+            max: Duration::from_secs(60),
+            multiplier: 2.0,
+        }
+    }
+}
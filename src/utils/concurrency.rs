@@ -0,0 +1,113 @@
+//! 进程级 ping 并发上限
+//!
+//! 每个 ping 会话（一个 ping 子进程）在启动前取得一个许可，许可由会话的接收端持有，接收端被丢弃时释放。
+//! 达到上限时排队等待，可选的排队超时到期后返回错误。
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// 并发上限的配置
+#[derive(Debug, Default)]
+struct Limit {
+    semaphore: Option<Arc<Semaphore>>,
+    max: Option<usize>,
+    queue_timeout: Option<Duration>,
+}
+
+/// 排队等待许可超时
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueTimeout(pub Duration);
+
+static LIMIT: Mutex<Limit> = Mutex::new(Limit {
+    semaphore: None,
+    max: None,
+    queue_timeout: None,
+});
+
+/// 正在运行的 ping 会话数
+static IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
+
+fn limit() -> MutexGuard<'static, Limit> {
+    LIMIT.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// 一个 ping 会话的许可，丢弃时释放并减少正在运行的会话数
+#[derive(Debug)]
+pub struct SessionPermit {
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+impl SessionPermit {
+    fn new(permit: Option<OwnedSemaphorePermit>) -> Self {
+        IN_FLIGHT.fetch_add(1, Ordering::Relaxed);
+        Self { _permit: permit }
+    }
+}
+
+impl Drop for SessionPermit {
+    fn drop(&mut self) {
+        IN_FLIGHT.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// 设置最大并发 ping 会话数，`max` 为 None 时取消上限
+///
+/// 新的上限只约束之后启动的会话，已经运行的会话不受影响。
+pub fn set_max_concurrent(max: Option<usize>, queue_timeout: Option<Duration>) {
+    let mut limit = limit();
+    limit.semaphore = max.map(|max| Arc::new(Semaphore::new(max)));
+    limit.max = max;
+    limit.queue_timeout = queue_timeout;
+}
+
+/// 当前的最大并发会话数
+pub fn max_concurrent() -> Option<usize> {
+    limit().max
+}
+
+/// 正在运行的 ping 会话数
+pub fn in_flight() -> usize {
+    IN_FLIGHT.load(Ordering::Relaxed)
+}
+
+/// 异步等待许可（未设置上限时立即返回）
+///
+/// # Errors
+/// 排队时间超过 `queue_timeout` 时返回 `QueueTimeout`
+pub async fn acquire() -> Result<SessionPermit, QueueTimeout> {
+    let (semaphore, queue_timeout) = {
+        let limit = limit();
+        (limit.semaphore.clone(), limit.queue_timeout)
+    };
+    let Some(semaphore) = semaphore else {
+        return Ok(SessionPermit::new(None));
+    };
+
+    // 信号量不会被关闭，`acquire_owned` 只在关闭时失败
+    let permit = match queue_timeout {
+        Some(timeout) => tokio::time::timeout(timeout, semaphore.acquire_owned())
+            .await
+            .map_err(|_| QueueTimeout(timeout))?
+            .ok(),
+        None => semaphore.acquire_owned().await.ok(),
+    };
+    Ok(SessionPermit::new(permit))
+}
+
+/// 阻塞等待许可（未设置上限时立即返回）
+///
+/// # Errors
+/// 排队时间超过 `queue_timeout` 时返回 `QueueTimeout`
+pub fn acquire_blocking() -> Result<SessionPermit, QueueTimeout> {
+    let semaphore = limit().semaphore.clone();
+    match semaphore {
+        // 有空闲许可时无需进入运行时
+        Some(semaphore) => match semaphore.try_acquire_owned() {
+            Ok(permit) => Ok(SessionPermit::new(Some(permit))),
+            Err(_) => pyo3_async_runtimes::tokio::get_runtime().block_on(acquire()),
+        },
+        None => Ok(SessionPermit::new(None)),
+    }
+}
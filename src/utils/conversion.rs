@@ -1,7 +1,8 @@
 use pinger::PingOptions;
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use std::net::IpAddr;
-use std::time::Duration;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// 从 Python 对象中提取 IP 地址字符串
 pub fn extract_target(target: &Bound<PyAny>) -> PyResult<String> {
@@ -20,6 +21,147 @@ pub fn extract_target(target: &Bound<PyAny>) -> PyResult<String> {
     ))
 }
 
+/// 一次展开的最大目标数
+pub const MAX_EXPANDED_TARGETS: u128 = 65_536;
+
+/// 检查展开后的目标数量
+fn check_expanded_len(len: u128, spec: &str) -> PyResult<()> {
+    if len > MAX_EXPANDED_TARGETS {
+        return Err(PyErr::new::<PyValueError, _>(format!(
+            "'{spec}' expands to {len} addresses, more than the limit of {MAX_EXPANDED_TARGETS}"
+        )));
+    }
+    Ok(())
+}
+
+/// 展开 CIDR 网段中的主机地址
+///
+/// 与 Python 的 `ip_network(...).hosts()` 一致：IPv4 排除网络地址和广播地址（/31、/32 除外），
+/// IPv6 排除子网路由器任播地址（/127、/128 除外）。主机位不为零时按网段处理。
+fn expand_cidr(spec: &str, address: &str, prefix: &str) -> PyResult<Vec<String>> {
+    let invalid = || PyErr::new::<PyValueError, _>(format!("Invalid CIDR network '{spec}'"));
+    let address: IpAddr = address.parse().map_err(|_| invalid())?;
+    let prefix: u32 = prefix.parse().map_err(|_| invalid())?;
+
+    match address {
+        IpAddr::V4(address) => {
+            if prefix > 32 {
+                return Err(invalid());
+            }
+            let size = 1u128 << (32 - prefix);
+            check_expanded_len(size, spec)?;
+            let network = u32::from(address) & u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+            let last = network | (u32::MAX.checked_shr(prefix).unwrap_or(0));
+            let (first, last) = if prefix >= 31 {
+                (network, last)
+            } else {
+                (network + 1, last - 1)
+            };
+            Ok((first..=last).map(|ip| Ipv4Addr::from(ip).to_string()).collect())
+        }
+        IpAddr::V6(address) => {
+            if prefix > 128 {
+                return Err(invalid());
+            }
+            let size = 1u128.checked_shl(128 - prefix).unwrap_or(u128::MAX);
+            check_expanded_len(size, spec)?;
+            let network = u128::from(address) & u128::MAX.checked_shl(128 - prefix).unwrap_or(0);
+            let last = network | (u128::MAX.checked_shr(prefix).unwrap_or(0));
+            let first = if prefix >= 127 { network } else { network + 1 };
+            Ok((first..=last).map(|ip| Ipv6Addr::from(ip).to_string()).collect())
+        }
+    }
+}
+
+/// 展开地址范围 `start-end`，`end` 也可以只写 IPv4 的最后一段（如 `10.0.0.1-50`）
+///
+/// 两端都不是 IP 地址时返回 None（例如含 `-` 的主机名）。
+fn expand_range(spec: &str, start: &str, end: &str) -> PyResult<Option<Vec<String>>> {
+    let Ok(start) = start.trim().parse::<IpAddr>() else {
+        return Ok(None);
+    };
+    let end = end.trim();
+    let end = match (start, end.parse::<IpAddr>()) {
+        (_, Ok(end)) => end,
+        (IpAddr::V4(start), Err(_)) => match end.parse::<u8>() {
+            Ok(last) => {
+                let [a, b, c, _] = start.octets();
+                IpAddr::V4(Ipv4Addr::new(a, b, c, last))
+            }
+            Err(_) => return Ok(None),
+        },
+        (IpAddr::V6(_), Err(_)) => return Ok(None),
+    };
+
+    let invalid = |reason: &str| PyErr::new::<PyValueError, _>(format!("Invalid address range '{spec}': {reason}"));
+    match (start, end) {
+        (IpAddr::V4(start), IpAddr::V4(end)) => {
+            let (start, end) = (u32::from(start), u32::from(end));
+            if start > end {
+                return Err(invalid("start is greater than end"));
+            }
+            check_expanded_len(u128::from(end - start) + 1, spec)?;
+            Ok(Some((start..=end).map(|ip| Ipv4Addr::from(ip).to_string()).collect()))
+        }
+        (IpAddr::V6(start), IpAddr::V6(end)) => {
+            let (start, end) = (u128::from(start), u128::from(end));
+            if start > end {
+                return Err(invalid("start is greater than end"));
+            }
+            check_expanded_len((end - start).saturating_add(1), spec)?;
+            Ok(Some((start..=end).map(|ip| Ipv6Addr::from(ip).to_string()).collect()))
+        }
+        _ => Err(invalid("start and end must be the same address family")),
+    }
+}
+
+/// 将目标描述展开为地址列表
+///
+/// 支持 CIDR 网段（`10.0.0.0/24`、`fd00::/120`）、地址范围（`10.0.0.1-10.0.0.50`、`10.0.0.1-50`），
+/// 其他字符串原样作为单个目标（IP 地址或主机名）。
+///
+/// # Errors
+/// - `PyValueError`: If the network or range is invalid, or expands to more than `MAX_EXPANDED_TARGETS` addresses
+pub fn expand_target_spec(spec: &str) -> PyResult<Vec<String>> {
+    let spec = spec.trim();
+    if let Some((address, prefix)) = spec.split_once('/') {
+        return expand_cidr(spec, address, prefix);
+    }
+    if let Some((start, end)) = spec.split_once('-') {
+        if let Some(targets) = expand_range(spec, start, end)? {
+            return Ok(targets);
+        }
+    }
+    Ok(vec![spec.to_string()])
+}
+
+/// 从 Python 对象中提取一组目标
+///
+/// 支持 `extract_target` 接受的单个目标、`IPv4Network` / `IPv6Network`、CIDR 或范围字符串，
+/// 以及由它们组成的列表或元组。结果按出现顺序去重。
+///
+/// # Errors
+/// - `PyValueError`: If a network or range is invalid, or the targets expand to too many addresses
+/// - `PyTypeError`: If an item cannot be converted to a target
+pub fn extract_targets(targets: &Bound<PyAny>) -> PyResult<Vec<String>> {
+    let mut expanded = Vec::new();
+    if targets.is_instance_of::<pyo3::types::PyList>() || targets.is_instance_of::<pyo3::types::PyTuple>() {
+        for item in targets.try_iter()? {
+            expanded.extend(extract_targets(&item?)?);
+        }
+    } else if targets.hasattr("network_address")? && targets.hasattr("prefixlen")? {
+        // ipaddress.IPv4Network / IPv6Network
+        expanded = expand_target_spec(&targets.str()?.to_cow()?)?;
+    } else {
+        expanded = expand_target_spec(&extract_target(targets)?)?;
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    expanded.retain(|target| seen.insert(target.clone()));
+    check_expanded_len(expanded.len() as u128, "targets")?;
+    Ok(expanded)
+}
+
 /// 创建 `PingOptions` 配置
 ///
 /// # 参数
@@ -51,3 +193,15 @@ pub fn create_ping_options(
         PingOptions::new(target, interval, interface)
     }
 }
+
+/// 当前时间的 Unix 时间戳（秒）
+pub fn unix_timestamp() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(f64::NAN, |d| d.as_secs_f64())
+}
+
+/// 当前时间的 Unix 时间戳（纳秒）
+pub fn unix_nanos() -> u128 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_nanos())
+}
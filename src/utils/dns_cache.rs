@@ -0,0 +1,198 @@
+//! 进程级 DNS 预解析缓存
+//!
+//! 所有 ping 的预解析共享同一个有界缓存，避免轮询时每次都重新解析主机名。
+//! 缓存默认关闭（不改变主机名解析的行为），通过 `set_dns_cache()` 启用。
+//! 肯定结果按记录的 TTL 过期（系统解析器不提供 TTL，使用最大缓存时间），
+//! 否定结果（域名不存在或没有地址）按 SOA 的否定 TTL 过期，两者都不超过配置的上限。
+//! 解析超时和临时失败（服务器故障、网络不可用等）不缓存。
+
+use crate::protocols::dns::resolver::DnsResolver;
+use pinger::target::IPVersion;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+/// 默认的肯定结果最大缓存时间
+const DEFAULT_MAX_AGE: Duration = Duration::from_secs(30);
+/// 默认的否定结果最大缓存时间
+const DEFAULT_NEGATIVE_MAX_AGE: Duration = Duration::from_secs(5);
+
+/// 缓存的解析结果：地址列表或错误信息
+pub type Answer = Result<Vec<IpAddr>, String>;
+
+/// 缓存键
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Key {
+    /// 自定义解析器，None 表示系统解析器
+    resolver: Option<DnsResolver>,
+    /// 小写的域名
+    name: String,
+    /// 要求的 IP 版本
+    version: u8,
+    /// 是否是系统解析器返回的全部地址（自定义解析器总是返回全部地址）
+    all: bool,
+}
+
+impl Key {
+    /// 创建缓存键，`all` 表示需要全部地址而不只是第一个
+    pub fn new(name: &str, version: IPVersion, resolver: Option<&DnsResolver>, all: bool) -> Self {
+        Self {
+            resolver: resolver.cloned(),
+            name: name.to_ascii_lowercase(),
+            version: match version {
+                IPVersion::V4 => 4,
+                IPVersion::V6 => 6,
+                IPVersion::Any => 0,
+            },
+            all: all && resolver.is_none(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Entry {
+    answer: Answer,
+    expires: Instant,
+}
+
+/// 缓存的当前配置和累计统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DnsCacheStats {
+    /// 最大条目数，0 表示禁用
+    pub max_entries: usize,
+    /// 肯定结果的最大缓存时间
+    pub max_age: Duration,
+    /// 否定结果的最大缓存时间
+    pub negative_max_age: Duration,
+    /// 当前条目数（包括尚未清理的过期条目）
+    pub entries: usize,
+    /// 命中次数
+    pub hits: u64,
+    /// 未命中次数
+    pub misses: u64,
+}
+
+struct Cache {
+    entries: HashMap<Key, Entry>,
+    max_entries: usize,
+    max_age: Duration,
+    negative_max_age: Duration,
+}
+
+impl Cache {
+    /// 腾出一个位置：先清理过期条目，仍然已满时淘汰最早过期的条目
+    fn make_room(&mut self, now: Instant) {
+        if self.entries.len() < self.max_entries {
+            return;
+        }
+        self.entries.retain(|_, entry| entry.expires > now);
+        while self.entries.len() >= self.max_entries {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.expires)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+    }
+}
+
+static CACHE: LazyLock<Mutex<Cache>> = LazyLock::new(|| {
+    Mutex::new(Cache {
+        entries: HashMap::new(),
+        // 默认关闭
+        max_entries: 0,
+        max_age: DEFAULT_MAX_AGE,
+        negative_max_age: DEFAULT_NEGATIVE_MAX_AGE,
+    })
+});
+static HITS: AtomicU64 = AtomicU64::new(0);
+static MISSES: AtomicU64 = AtomicU64::new(0);
+
+fn cache() -> MutexGuard<'static, Cache> {
+    CACHE.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// 设置缓存大小和最大缓存时间，`max_entries` 为 0 时禁用缓存。修改设置会清空缓存。
+pub fn configure(max_entries: usize, max_age: Duration, negative_max_age: Duration) {
+    *cache() = Cache {
+        entries: HashMap::new(),
+        max_entries,
+        max_age,
+        negative_max_age,
+    };
+}
+
+/// 查找未过期的结果
+pub fn get(key: &Key) -> Option<Answer> {
+    let mut cache = cache();
+    if cache.max_entries == 0 {
+        return None;
+    }
+    let now = Instant::now();
+    let answer = match cache.entries.get(key) {
+        Some(entry) if entry.expires > now => Some(entry.answer.clone()),
+        Some(_) => {
+            cache.entries.remove(key);
+            None
+        }
+        None => None,
+    };
+    let counter = if answer.is_some() { &HITS } else { &MISSES };
+    counter.fetch_add(1, Ordering::Relaxed);
+    answer
+}
+
+/// 缓存解析结果，`ttl` 为 None 时使用最大缓存时间，TTL 为 0 时不缓存
+pub fn insert(key: Key, answer: Answer, ttl: Option<Duration>) {
+    let mut cache = cache();
+    let max_age = if answer.is_ok() {
+        cache.max_age
+    } else {
+        cache.negative_max_age
+    };
+    let ttl = ttl.map_or(max_age, |ttl| ttl.min(max_age));
+    if cache.max_entries == 0 || ttl.is_zero() {
+        return;
+    }
+    let now = Instant::now();
+    if !cache.entries.contains_key(&key) {
+        cache.make_room(now);
+    }
+    cache.entries.insert(
+        key,
+        Entry {
+            answer,
+            expires: now + ttl,
+        },
+    );
+}
+
+/// 清空缓存
+pub fn clear() {
+    cache().entries.clear();
+}
+
+/// 当前配置和累计统计
+pub fn stats() -> DnsCacheStats {
+    let cache = cache();
+    DnsCacheStats {
+        max_entries: cache.max_entries,
+        max_age: cache.max_age,
+        negative_max_age: cache.negative_max_age,
+        entries: cache.entries.len(),
+        hits: HITS.load(Ordering::Relaxed),
+        misses: MISSES.load(Ordering::Relaxed),
+    }
+}
+
+/// 清零累计统计
+pub fn reset_stats() {
+    HITS.store(0, Ordering::Relaxed);
+    MISSES.store(0, Ordering::Relaxed);
+}
@@ -0,0 +1,170 @@
+//! 最小的 HTTP/1.1 客户端
+//!
+//! `post` 用于向采集器推送指标，支持 `http://` 和 `https://`（校验证书，见 `tls`），避免引入完整的 HTTP 客户端依赖。
+//! `HttpEndpoint` 也用于解析 HTTP 探测的地址。
+
+use super::tls;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::fmt::Write as _;
+use std::io::{Error, ErrorKind};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// 解析后的 HTTP 地址
+#[derive(Clone, Debug)]
+pub struct HttpEndpoint {
+    /// 主机名或 IP 地址（IPv6 不带方括号）
+    pub host: String,
+    /// 端口
+    pub port: u16,
+    /// 请求路径（含查询字符串）
+    pub path: String,
+    /// 是否为 `https://`
+    pub tls: bool,
+}
+
+impl HttpEndpoint {
+    /// 解析 `http://` 或 `https://` 地址，路径为空时使用 `default_path`
+    ///
+    /// # Errors
+    /// - `PyValueError`: If the URL is not a valid `http://` or `https://` URL
+    pub fn parse(url: &str, default_path: &str) -> PyResult<Self> {
+        let invalid = |reason: &str| PyErr::new::<PyValueError, _>(format!("Invalid endpoint '{url}': {reason}"));
+
+        let (rest, tls) = if let Some(rest) = url.strip_prefix("http://") {
+            (rest, false)
+        } else if let Some(rest) = url.strip_prefix("https://") {
+            (rest, true)
+        } else {
+            return Err(invalid("only http:// and https:// URLs are supported"));
+        };
+        let (authority, path) = match rest.find('/') {
+            Some(idx) => rest.split_at(idx),
+            None => (rest, ""),
+        };
+        let path = if path.is_empty() || path == "/" {
+            default_path.to_string()
+        } else {
+            path.to_string()
+        };
+
+        let (host, port) = if let Some(bracketed) = authority.strip_prefix('[') {
+            // IPv6 地址：[::1]:4318
+            let (host, after) = bracketed.split_once(']').ok_or_else(|| invalid("unclosed '['"))?;
+            (host, after.strip_prefix(':'))
+        } else {
+            match authority.rsplit_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (authority, None),
+            }
+        };
+        if host.is_empty() {
+            return Err(invalid("missing host"));
+        }
+        let port = match port {
+            Some(port) => port.parse::<u16>().map_err(|_| invalid("invalid port"))?,
+            None if tls => 443,
+            None => 80,
+        };
+
+        Ok(Self {
+            host: host.to_string(),
+            port,
+            path,
+            tls,
+        })
+    }
+
+    /// `Host` 请求头的值
+    pub fn authority(&self) -> String {
+        if self.host.contains(':') {
+            format!("[{}]:{}", self.host, self.port)
+        } else {
+            format!("{}:{}", self.host, self.port)
+        }
+    }
+
+    /// 完整的 URL
+    pub fn url(&self) -> String {
+        let scheme = if self.tls { "https" } else { "http" };
+        format!("{scheme}://{}{}", self.authority(), self.path)
+    }
+}
+
+/// 发送 POST 请求，返回响应状态码
+///
+/// 每次请求使用独立的连接（`Connection: close`），`https://` 地址校验服务器证书，整个请求受 `timeout` 限制。
+///
+/// # Errors
+/// - Connection, TLS handshake, write or read failures, a timeout, or a malformed response
+pub async fn post(
+    endpoint: &HttpEndpoint,
+    content_type: &str,
+    headers: &[(&str, &str)],
+    body: &[u8],
+    timeout: Duration,
+) -> std::io::Result<u16> {
+    let mut head = format!(
+        "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n",
+        endpoint.path,
+        endpoint.authority(),
+        body.len()
+    );
+    for (name, value) in headers {
+        let _ = write!(head, "{name}: {value}\r\n");
+    }
+    head.push_str("\r\n");
+
+    let request = async {
+        let stream = TcpStream::connect((endpoint.host.as_str(), endpoint.port)).await?;
+        if endpoint.tls {
+            let connector = tls::connector(true, &[b"http/1.1"])?;
+            let stream = connector.connect(tls::server_name(&endpoint.host)?, stream).await?;
+            send(stream, head.as_bytes(), body).await
+        } else {
+            send(stream, head.as_bytes(), body).await
+        }
+    };
+
+    tokio::time::timeout(timeout, request)
+        .await
+        .map_err(|_| Error::new(ErrorKind::TimedOut, format!("request to {} timed out", endpoint.url())))?
+}
+
+/// 写入请求并读取完整响应，确保服务端处理完毕后再关闭连接
+async fn send<S>(mut stream: S, head: &[u8], body: &[u8]) -> std::io::Result<u16>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    stream.write_all(head).await?;
+    stream.write_all(body).await?;
+    stream.flush().await?;
+
+    let mut response = Vec::new();
+    match stream.read_to_end(&mut response).await {
+        // 未发送 close_notify 就关闭的 TLS 连接按正常结束处理
+        Ok(_) => {}
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof && !response.is_empty() => {}
+        Err(e) => return Err(e),
+    }
+    parse_status(&response)
+}
+
+/// 解析响应状态行，例如 `HTTP/1.1 200 OK`
+///
+/// # Errors
+/// - The response does not start with an HTTP status line
+pub fn parse_status(response: &[u8]) -> std::io::Result<u16> {
+    let text = String::from_utf8_lossy(response);
+    let status_line = text.lines().next().unwrap_or_default();
+    let mut parts = status_line.split_whitespace();
+    match (parts.next(), parts.next().map(str::parse::<u16>)) {
+        (Some(version), Some(Ok(code))) if version.starts_with("HTTP/") => Ok(code),
+        _ => Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("malformed HTTP response: {status_line:?}"),
+        )),
+    }
+}
@@ -0,0 +1,114 @@
+//! 探测时间抖动，避免大量流同步发送
+//!
+//! 启动偏移在 `[0, start_jitter]` 内均匀分布；ping 间隔在 `interval * (1 ± pct%)` 内均匀分布，
+//! 并取整到 100ms 的倍数（ping 命令的 `-i` 参数只保留一位小数）。
+//! 指定种子时结果可复现，便于测试。
+//!
+//! 间隔只能在启动 ping 进程时设置，因此每次（重新）启动时抽取一次，同一进程内的探测间隔固定，
+//! 流之间的错开主要依靠启动偏移。`interval * pct%` 小于 50ms 时抖动会被取整抵消。
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// `SplitMix64` 伪随机数生成器
+#[derive(Clone, Debug)]
+pub struct SplitMix64(u64);
+
+impl SplitMix64 {
+    /// 使用指定种子创建
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// 使用当前时间和进程内计数器生成的种子创建
+    pub fn from_entropy() -> Self {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.subsec_nanos().into());
+        let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+        Self::new(nanos ^ count.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+    }
+
+    /// 下一个 64 位随机数
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// `[0, 1)` 内均匀分布的随机数
+    #[allow(clippy::cast_precision_loss)]
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// 启动偏移和间隔抖动配置
+#[derive(Clone, Debug)]
+pub struct Jitter {
+    start: Duration,
+    interval_pct: f64,
+    rng: SplitMix64,
+}
+
+impl Default for Jitter {
+    fn default() -> Self {
+        Self {
+            start: Duration::ZERO,
+            interval_pct: 0.0,
+            rng: SplitMix64::new(0),
+        }
+    }
+}
+
+impl Jitter {
+    /// 创建抖动配置，`seed` 为 None 时使用随机种子
+    ///
+    /// # Errors
+    /// - `PyValueError`: If `start_jitter_ms` is negative, or `interval_jitter_pct` is not in `[0, 100)`
+    pub fn new(start_jitter_ms: i64, interval_jitter_pct: f64, seed: Option<u64>) -> PyResult<Self> {
+        let start = u64::try_from(start_jitter_ms).map_err(|_| {
+            PyErr::new::<PyValueError, _>(format!(
+                "start_jitter_ms must be a non-negative integer, got {start_jitter_ms}"
+            ))
+        })?;
+        if !(0.0..100.0).contains(&interval_jitter_pct) {
+            return Err(PyErr::new::<PyValueError, _>(format!(
+                "interval_jitter_pct must be in [0, 100), got {interval_jitter_pct}"
+            )));
+        }
+        Ok(Self {
+            start: Duration::from_millis(start),
+            interval_pct: interval_jitter_pct,
+            rng: seed.map_or_else(SplitMix64::from_entropy, SplitMix64::new),
+        })
+    }
+
+    /// 下一个启动偏移
+    pub fn start_delay(&mut self) -> Duration {
+        if self.start.is_zero() {
+            return Duration::ZERO;
+        }
+        self.start.mul_f64(self.rng.next_f64())
+    }
+
+    /// 对 `interval_ms` 加上抖动，结果取整到 100ms 的倍数且不小于 100ms
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    pub fn interval_ms(&mut self, interval_ms: u64) -> u64 {
+        if self.interval_pct <= 0.0 {
+            return interval_ms;
+        }
+        let factor = 1.0 + (self.rng.next_f64() * 2.0 - 1.0) * self.interval_pct / 100.0;
+        let jittered = (interval_ms as f64 * factor / 100.0).round() as u64 * 100;
+        jittered.max(100)
+    }
+}
@@ -0,0 +1,91 @@
+//! 本地化 ping 输出的兼容解析
+//!
+//! Unix 上的 ping 进程以 `LC_ALL=C` 启动（见 `protocols::icmp::command`），输出总是英文格式。
+//! 这里只作为兜底：对英文解析无法识别的 `Unknown` 行做一次与语言无关的补充解析，
+//! 用于不受语言环境变量影响的 ping 实现（例如 Windows 的 ping.exe）输出的翻译文本或逗号小数点
+//! （例如 `Zeit=12,3 ms`、`时间=0.045 毫秒`、`時間 <1ms`）。
+
+/// 延迟单位（毫秒）的各语言写法
+const MS_UNITS: &[&str] = &["ms", "мс", "毫秒", "ミリ秒"];
+
+/// 各语言中表示请求超时的关键字（小写）
+const TIMEOUT_KEYWORDS: &[&str] = &[
+    "timed out",
+    "timeout",
+    "zeitüberschreitung",
+    "délai d'attente",
+    "tiempo de espera agotado",
+    "tempo esgotado",
+    "esgotado o tempo limite",
+    "превышен интервал ожидания",
+    "超时",
+    "逾時",
+    "タイムアウト",
+    "시간이 초과",
+];
+
+/// 补充解析的结果
+#[derive(Debug, Clone, PartialEq)]
+pub enum LocalizedLine {
+    /// 识别到往返延迟（毫秒）
+    Pong(f64),
+    /// 识别到超时
+    Timeout,
+}
+
+/// 解析本地化的 ping 输出行
+///
+/// 延迟识别规则与语言无关：查找紧跟在 `=` 或 `<` 之后、并以毫秒单位结尾的数字，
+/// 数字可以使用 `.` 或 `,` 作为小数点。这样 `icmp_seq=1`、`ttl=64` 之类的字段不会被误判。
+/// 包含多个延迟的行是统计摘要（例如 Windows 的 `Minimum = 1ms, Maximum = 2ms, Average = 1ms`），不识别。
+pub fn parse_localized_line(line: &str) -> Option<LocalizedLine> {
+    let mut rtts = rtt_values_ms(line);
+    match (rtts.next(), rtts.next()) {
+        (Some(duration_ms), None) => return Some(LocalizedLine::Pong(duration_ms)),
+        (Some(_), Some(_)) => return None,
+        _ => {}
+    }
+
+    let lower = line.to_lowercase();
+    if is_probe_line(&lower) && TIMEOUT_KEYWORDS.iter().any(|keyword| lower.contains(keyword)) {
+        return Some(LocalizedLine::Timeout);
+    }
+
+    None
+}
+
+/// 是否是单个探测的输出行
+///
+/// 带有序号（`icmp_seq`）的行，或者像 Windows 的 `Request timed out.` 一样只有一句提示、
+/// 不含冒号和数字的行。这样 `ping: sendmsg: Connection timed out` 之类的错误信息和统计行不会被当作超时。
+fn is_probe_line(lower: &str) -> bool {
+    lower.contains("icmp_seq") || !lower.contains(|c: char| c == ':' || c.is_ascii_digit())
+}
+
+/// 依次返回行内所有形如延迟（毫秒）的数值
+fn rtt_values_ms(line: &str) -> impl Iterator<Item = f64> + '_ {
+    line.char_indices().filter_map(|(idx, ch)| {
+        if ch != '=' && ch != '<' {
+            return None;
+        }
+
+        // 跳过运算符后的空白，例如 `時間 <1ms` 或 `Zeit= 12,3 ms`
+        let rest = line.get(idx + ch.len_utf8()..)?.trim_start();
+        let number_len = rest
+            .char_indices()
+            .take_while(|(_, c)| c.is_ascii_digit() || *c == '.' || *c == ',')
+            .last()
+            .map_or(0, |(i, c)| i + c.len_utf8());
+        if number_len == 0 {
+            return None;
+        }
+
+        let number = rest.get(..number_len)?;
+        let unit = rest.get(number_len..).unwrap_or_default().trim_start();
+        if !MS_UNITS.iter().any(|u| unit.starts_with(u)) {
+            return None;
+        }
+
+        number.replace(',', ".").parse::<f64>().ok()
+    })
+}
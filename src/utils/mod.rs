@@ -1,2 +1,10 @@
+pub mod backoff;
+pub mod concurrency;
 pub mod conversion;
+pub mod dns_cache;
+pub mod http;
+pub mod jitter;
+pub mod locale;
+pub mod rate_limit;
+pub mod tls;
 pub mod validation;
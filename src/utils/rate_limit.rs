@@ -0,0 +1,163 @@
+//! 进程级 ping 速率限制
+//!
+//! 所有 `Pinger`、`AsyncPinger`、流和监控会话在启动 ping 进程前都从同一个令牌桶取令牌，
+//! 每启动一个 ping 进程消耗一个令牌。令牌不足时等待，并记录被限速的次数和时间。
+//!
+//! 限制的是每秒启动的 ping 进程数，而不是每秒发送的数据包数：进程启动后按自己的间隔持续发送。
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+/// 令牌桶
+#[derive(Debug)]
+struct TokenBucket {
+    /// 每秒补充的令牌数
+    rate: f64,
+    /// 桶容量
+    burst: f64,
+    /// 当前令牌数，为负时表示已预约的等待
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    /// 取一个令牌，返回需要等待的时间
+    fn reserve(&mut self, now: Instant) -> Option<Duration> {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.burst);
+        self.updated = now;
+        self.tokens -= 1.0;
+        (self.tokens < 0.0).then(|| Duration::from_secs_f64(-self.tokens / self.rate))
+    }
+}
+
+/// 速率限制的当前配置和累计统计
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RateLimitStats {
+    /// 每秒允许启动的 ping 进程数，未启用时为 None
+    pub spawns_per_second: Option<f64>,
+    /// 突发容量，未启用时为 None
+    pub burst: Option<u32>,
+    /// 已取得的令牌数
+    pub acquired: u64,
+    /// 需要等待的次数
+    pub throttled: u64,
+    /// 累计等待时间
+    pub throttled_time: Duration,
+}
+
+/// 进程级速率限制器
+struct RateLimiter {
+    bucket: Mutex<Option<TokenBucket>>,
+    acquired: AtomicU64,
+    throttled: AtomicU64,
+    throttled_nanos: AtomicU64,
+}
+
+static LIMITER: RateLimiter = RateLimiter {
+    bucket: Mutex::new(None),
+    acquired: AtomicU64::new(0),
+    throttled: AtomicU64::new(0),
+    throttled_nanos: AtomicU64::new(0),
+};
+
+impl RateLimiter {
+    fn bucket(&self) -> MutexGuard<'_, Option<TokenBucket>> {
+        self.bucket.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// 取一个令牌并记录统计，返回需要等待的时间
+    fn reserve(&self) -> Option<Duration> {
+        let wait = self.bucket().as_mut()?.reserve(Instant::now());
+        self.acquired.fetch_add(1, Ordering::Relaxed);
+        if let Some(wait) = wait {
+            self.throttled.fetch_add(1, Ordering::Relaxed);
+            let nanos = u64::try_from(wait.as_nanos()).unwrap_or(u64::MAX);
+            self.throttled_nanos.fetch_add(nanos, Ordering::Relaxed);
+        }
+        wait
+    }
+
+    /// 归还一个未使用的令牌（等待被取消时）
+    fn refund(&self) {
+        if let Some(bucket) = self.bucket().as_mut() {
+            bucket.tokens = (bucket.tokens + 1.0).min(bucket.burst);
+        }
+        // 统计可能已被清零，不能减到负数
+        let _ = self
+            .acquired
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1));
+    }
+}
+
+/// 等待令牌期间持有的预约，未完成等待就被丢弃时归还令牌
+struct Reservation {
+    completed: bool,
+}
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        if !self.completed {
+            LIMITER.refund();
+        }
+    }
+}
+
+/// 设置进程级速率限制，`spawns_per_second` 为 None 时取消限制
+///
+/// `burst` 为 None 时等于 `spawns_per_second`（至少为 1）。修改设置会重置令牌桶（桶满）。
+pub fn set_limit(spawns_per_second: Option<f64>, burst: Option<u32>) {
+    *LIMITER.bucket() = spawns_per_second.map(|rate| {
+        let burst = burst.map_or(rate.ceil(), f64::from).max(1.0);
+        TokenBucket {
+            rate,
+            burst,
+            tokens: burst,
+            updated: Instant::now(),
+        }
+    });
+}
+
+/// 当前配置和累计统计
+pub fn stats() -> RateLimitStats {
+    let (spawns_per_second, burst) = LIMITER.bucket().as_ref().map_or((None, None), |bucket| {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let burst = bucket.burst as u32;
+        (Some(bucket.rate), Some(burst))
+    });
+    RateLimitStats {
+        spawns_per_second,
+        burst,
+        acquired: LIMITER.acquired.load(Ordering::Relaxed),
+        throttled: LIMITER.throttled.load(Ordering::Relaxed),
+        throttled_time: Duration::from_nanos(LIMITER.throttled_nanos.load(Ordering::Relaxed)),
+    }
+}
+
+/// 清零累计统计
+pub fn reset_stats() {
+    LIMITER.acquired.store(0, Ordering::Relaxed);
+    LIMITER.throttled.store(0, Ordering::Relaxed);
+    LIMITER.throttled_nanos.store(0, Ordering::Relaxed);
+}
+
+/// 阻塞直到取得令牌（未启用限制时立即返回）
+///
+/// 会阻塞当前线程，从 Python 调用时需要先释放 GIL（`py.detach`）。
+pub fn acquire_blocking() {
+    if let Some(wait) = LIMITER.reserve() {
+        std::thread::sleep(wait);
+    }
+}
+
+/// 异步等待直到取得令牌（未启用限制时立即返回）
+///
+/// 等待中的 future 被取消时归还预约的令牌，不占用后续调用者的配额。
+pub async fn acquire() {
+    if let Some(wait) = LIMITER.reserve() {
+        let mut reservation = Reservation { completed: false };
+        tokio::time::sleep(wait).await;
+        reservation.completed = true;
+    }
+}
@@ -0,0 +1,160 @@
+//! TLS 客户端配置
+//!
+//! 基于 rustls（ring 加密后端）和内置的 Mozilla 根证书，不依赖系统的 OpenSSL。
+
+use std::io::{Error, ErrorKind};
+use std::sync::Arc;
+use tokio_rustls::rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use tokio_rustls::rustls::crypto::{ring, verify_tls12_signature, verify_tls13_signature, CryptoProvider};
+use tokio_rustls::rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use tokio_rustls::rustls::{ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme};
+use tokio_rustls::TlsConnector;
+
+/// 不校验证书链和主机名，但仍然校验握手签名（用于自签名证书）
+#[derive(Debug)]
+struct NoCertificateVerification(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for NoCertificateVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, tokio_rustls::rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, tokio_rustls::rustls::Error> {
+        verify_tls12_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, tokio_rustls::rustls::Error> {
+        verify_tls13_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+/// 创建 TLS 连接器
+///
+/// `verify` 为 false 时接受任意证书；`alpn` 为空时不发送 ALPN 扩展。
+///
+/// # Errors
+/// - The crypto provider does not support the default protocol versions
+pub fn connector(verify: bool, alpn: &[&[u8]]) -> std::io::Result<TlsConnector> {
+    let provider = Arc::new(ring::default_provider());
+    let builder = ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .map_err(|e| Error::other(e.to_string()))?;
+    let mut config = if verify {
+        let roots = RootCertStore {
+            roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+        };
+        builder.with_root_certificates(roots).with_no_client_auth()
+    } else {
+        builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(NoCertificateVerification(provider)))
+            .with_no_client_auth()
+    };
+    config.alpn_protocols = alpn.iter().map(|protocol| protocol.to_vec()).collect();
+    Ok(TlsConnector::from(Arc::new(config)))
+}
+
+/// 用于 SNI 和证书校验的服务器名，IP 地址不发送 SNI
+///
+/// # Errors
+/// - `host` is neither a valid DNS name nor an IP address
+pub fn server_name(host: &str) -> std::io::Result<ServerName<'static>> {
+    ServerName::try_from(host.to_string()).map_err(|e| {
+        Error::new(
+            ErrorKind::InvalidInput,
+            format!("invalid TLS server name '{host}': {e}"),
+        )
+    })
+}
+
+/// 读取一个 DER 编码的 TLV，返回标签、内容和剩余数据
+fn read_tlv(input: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, rest) = input.split_first()?;
+    let (&first, rest) = rest.split_first()?;
+    let (len, rest) = if first < 0x80 {
+        (usize::from(first), rest)
+    } else {
+        let count = usize::from(first & 0x7f);
+        if count == 0 || count > 4 {
+            return None;
+        }
+        let (bytes, rest) = rest.split_at_checked(count)?;
+        let len = bytes.iter().fold(0usize, |len, &byte| (len << 8) | usize::from(byte));
+        (len, rest)
+    };
+    let (content, rest) = rest.split_at_checked(len)?;
+    Some((tag, content, rest))
+}
+
+/// 解析 `UTCTime`（`YYMMDDHHMMSSZ`）或 `GeneralizedTime`（`YYYYMMDDHHMMSSZ`）为 Unix 时间戳（秒）
+fn parse_x509_time(tag: u8, content: &[u8]) -> Option<i64> {
+    let text = std::str::from_utf8(content).ok()?.strip_suffix('Z')?;
+    let (year, rest) = match tag {
+        0x17 => {
+            let year: i64 = text.get(..2)?.parse().ok()?;
+            (if year < 50 { 2000 + year } else { 1900 + year }, text.get(2..)?)
+        }
+        0x18 => (text.get(..4)?.parse().ok()?, text.get(4..)?),
+        _ => return None,
+    };
+    if rest.len() != 10 || !rest.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let field = |start: usize| rest.get(start..start + 2).and_then(|s| s.parse::<i64>().ok());
+    let (month, day) = (field(0)?, field(2)?);
+    let seconds = field(4)? * 3600 + field(6)? * 60 + field(8)?;
+
+    // 公历日期到 1970-01-01 的天数（Howard Hinnant 的 days_from_civil 算法）
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+    Some(days * 86_400 + seconds)
+}
+
+/// 提取 DER 编码证书的过期时间（`notAfter`，Unix 时间戳，秒），无法解析时返回 None
+pub fn certificate_not_after(der: &[u8]) -> Option<i64> {
+    let (0x30, certificate, _) = read_tlv(der)? else {
+        return None;
+    };
+    let (0x30, tbs, _) = read_tlv(certificate)? else {
+        return None;
+    };
+    // 跳过可选的 [0] version、serialNumber、signature 和 issuer
+    let (tag, _, mut rest) = read_tlv(tbs)?;
+    if tag == 0xa0 {
+        (_, _, rest) = read_tlv(rest)?;
+    }
+    for _ in 0..2 {
+        (_, _, rest) = read_tlv(rest)?;
+    }
+    let (0x30, validity, _) = read_tlv(rest)? else {
+        return None;
+    };
+    let (_, _, validity) = read_tlv(validity)?;
+    let (tag, not_after, _) = read_tlv(validity)?;
+    parse_x509_time(tag, not_after)
+}
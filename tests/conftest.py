@@ -35,3 +35,133 @@ def interval_ms(request: pytest.FixtureRequest):
 def count(request: pytest.FixtureRequest):
     """ping 次数"""
     return request.config.getoption("--count")
+
+
+class StubDnsServer:
+    """本地 DNS 桩服务器，同时监听 UDP 和 TCP
+
+    `records` 把域名（小写、不带结尾的点）映射到 IP 地址列表，按查询类型返回 A 或 AAAA 记录；
+    未知域名返回带 SOA 授权记录的 NXDOMAIN。`drop_qtypes` 中类型的查询不作应答。
+    `queries` 记录收到的 `(name, qtype, transport)`。
+    """
+
+    def __init__(
+        self,
+        records: dict[str, list[str]] | None = None,
+        ttl: int = 300,
+        truncate_udp: bool = False,
+        drop_qtypes: frozenset[int] = frozenset(),
+    ):
+        import socket
+        import threading
+
+        self.records = records if records is not None else {}
+        self.ttl = ttl
+        self.truncate_udp = truncate_udp
+        self.drop_qtypes = drop_qtypes
+        self.queries: list[tuple[str, int, str]] = []
+        self.udp = socket.socket(socket.AF_INET, socket.SOCK_DGRAM)
+        self.udp.bind(("127.0.0.1", 0))
+        self.port = self.udp.getsockname()[1]
+        self.tcp = socket.socket(socket.AF_INET, socket.SOCK_STREAM)
+        self.tcp.setsockopt(socket.SOL_SOCKET, socket.SO_REUSEADDR, 1)
+        self.tcp.bind(("127.0.0.1", self.port))
+        self.tcp.listen()
+        self.udp.settimeout(0.1)
+        self.tcp.settimeout(0.1)
+        self._stop = threading.Event()
+        self._threads = [
+            threading.Thread(target=self._serve_udp, daemon=True),
+            threading.Thread(target=self._serve_tcp, daemon=True),
+        ]
+        for thread in self._threads:
+            thread.start()
+
+    @property
+    def address(self) -> str:
+        return f"127.0.0.1:{self.port}"
+
+    def close(self):
+        self._stop.set()
+        for thread in self._threads:
+            thread.join()
+        self.udp.close()
+        self.tcp.close()
+
+    def _answer(self, query: bytes, transport: str) -> bytes | None:
+        import ipaddress
+        import struct
+
+        # 解析问题中的域名
+        pos, labels = 12, []
+        while query[pos]:
+            length = query[pos]
+            labels.append(query[pos + 1 : pos + 1 + length].decode())
+            pos += 1 + length
+        question = query[12 : pos + 5]
+        qtype = struct.unpack("!H", query[pos + 1 : pos + 3])[0]
+        name = ".".join(labels).lower()
+        self.queries.append((name, qtype, transport))
+        if qtype in self.drop_qtypes:
+            return None
+
+        flags = 0x8180
+        answers, authority = [], []
+        if name in self.records:
+            for ip in self.records[name]:
+                address = ipaddress.ip_address(ip)
+                if (address.version, qtype) in ((4, 1), (6, 28)):
+                    answers.append(
+                        b"\xc0\x0c" + struct.pack("!HHIH", qtype, 1, self.ttl, len(address.packed)) + address.packed
+                    )
+        else:
+            flags |= 3
+            soa = b"\x00\x00" + struct.pack("!IIIII", 1, 3600, 600, 86400, 60)
+            authority.append(b"\xc0\x0c" + struct.pack("!HHIH", 6, 1, self.ttl, len(soa)) + soa)
+        if transport == "udp" and self.truncate_udp:
+            flags |= 0x0200
+            answers = []
+        header = query[:2] + struct.pack("!HHHHH", flags, 1, len(answers), len(authority), 0)
+        return header + question + b"".join(answers) + b"".join(authority)
+
+    def _serve_udp(self):
+        import socket
+
+        while not self._stop.is_set():
+            try:
+                data, addr = self.udp.recvfrom(512)
+            except socket.timeout:
+                continue
+            response = self._answer(data, "udp")
+            if response is not None:
+                self.udp.sendto(response, addr)
+
+    def _serve_tcp(self):
+        import socket
+        import struct
+
+        while not self._stop.is_set():
+            try:
+                conn, _ = self.tcp.accept()
+            except socket.timeout:
+                continue
+            with conn:
+                conn.settimeout(1.0)
+                try:
+                    length = struct.unpack("!H", conn.recv(2))[0]
+                    data = b""
+                    while len(data) < length:
+                        data += conn.recv(length - len(data))
+                    response = self._answer(data, "tcp")
+                    if response is not None:
+                        conn.sendall(struct.pack("!H", len(response)) + response)
+                except (OSError, struct.error):
+                    pass
+
+
+@pytest.fixture
+def dns_server():
+    """本地 DNS 桩服务器：`example.test` 解析为 `10.0.0.1` 和 `::1`，`localhost.test` 解析为 `127.0.0.1`"""
+    server = StubDnsServer({"example.test": ["10.0.0.1", "::1"], "localhost.test": ["127.0.0.1"]})
+    yield server
+    server.close()
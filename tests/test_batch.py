@@ -0,0 +1,104 @@
+"""
+PingResultBatch 列式导出测试
+"""
+
+import asyncio
+import math
+import time
+
+import pytest
+from ping_rs import AsyncPinger, AsyncPingStream, Pinger, PingResult, PingResultBatch, create_ping_stream
+from ping_rs.core_schema import TargetType
+
+
+def test_batch_columns():
+    """测试列数据与类型代码"""
+    results = [
+        PingResult.Pong(1.5, "a"),
+        PingResult.Timeout("b"),
+        PingResult.Unknown("c"),
+        PingResult.PingExited(1, "d"),
+    ]
+    batch = PingResultBatch(results, timestamps=[1.0, 2.0, 3.0, 4.0])
+
+    assert len(batch) == 4
+    assert batch.success_count() == 1
+    assert list(batch.timestamps) == [1.0, 2.0, 3.0, 4.0]
+    assert list(batch.type_codes) == [r.type_code for r in results] == [0, 1, 2, 3]
+
+    rtts = batch.rtts_ms
+    assert rtts.typecode == "d"
+    assert rtts[0] == 1.5
+    assert all(math.isnan(v) for v in rtts[1:])
+
+    # 列数据支持缓冲区协议
+    assert memoryview(rtts).format == "d"
+    assert memoryview(batch.type_codes).nbytes == 4
+
+
+def test_batch_sequence_protocol():
+    """测试序列协议"""
+    results = [PingResult.Pong(1.0, "a"), PingResult.Timeout("b")]
+    batch = PingResultBatch(results)
+
+    assert batch[0] == results[0]
+    assert batch[-1] == results[1]
+    assert list(batch) == results
+    assert batch.to_list() == results
+    assert all(math.isnan(v) for v in batch.timestamps)
+    with pytest.raises(IndexError):
+        _ = batch[2]
+    with pytest.raises(ValueError):
+        _ = PingResultBatch(results, timestamps=[1.0])
+
+
+def test_ping_multiple_batch(target: TargetType):
+    """测试同步批量 ping"""
+    before = time.time()
+    batch = Pinger(target, interval_ms=200).ping_multiple_batch(count=3)
+
+    assert len(batch) == 3
+    assert batch.success_count() == 3
+    assert all(before <= ts <= time.time() for ts in batch.timestamps)
+    assert all(rtt >= 0 for rtt in batch.rtts_ms)
+
+
+@pytest.mark.asyncio
+async def test_ping_multiple_batch_async(target: TargetType):
+    """测试异步批量 ping"""
+    batch = await AsyncPinger(target, interval_ms=200).ping_multiple_batch(count=3)
+
+    assert len(batch) == 3
+    assert list(batch.type_codes) == [0, 0, 0]
+
+
+def test_stream_drain(target: TargetType):
+    """测试从流中批量取出结果"""
+    stream = create_ping_stream(target, interval_ms=200, count=3)
+    time.sleep(1.0)
+
+    batch = stream.drain()
+    assert 1 <= len(batch) <= 3
+    assert len(stream.drain(max_items=0)) == 0
+
+
+@pytest.mark.asyncio
+async def test_async_stream_drain(target: TargetType):
+    """测试从异步流中批量取出结果"""
+    stream = AsyncPingStream(target, interval_ms=200, max_count=3)
+    # 第一次调用启动 ping 进程，不等待结果
+    assert isinstance(await stream.drain(), PingResultBatch)
+    await asyncio.sleep(1.0)
+
+    batch = await stream.drain()
+    assert 1 <= len(batch) <= 3
+    assert all(code == 0 for code in batch.type_codes)
+    assert len(await stream.drain(max_items=0)) == 0
+
+    # 与迭代共享计数，达到 max_count 后结束
+    remaining = [result async for result in stream]
+    assert len(batch) + len(remaining) == 3
+
+
+if __name__ == "__main__":
+    _ = pytest.main(["-xvs", __file__])
@@ -0,0 +1,88 @@
+"""
+ping 并发上限测试
+"""
+
+import asyncio
+import threading
+import time
+
+import pytest
+from ping_rs import (
+    PingStream,
+    get_inflight_pings,
+    get_max_concurrent_pings,
+    ping_once_async,
+    set_max_concurrent_pings,
+)
+from ping_rs.core_schema import TargetType
+
+
+@pytest.fixture(autouse=True)
+def reset_concurrency_limit():
+    """每个测试前后取消并发上限"""
+    set_max_concurrent_pings(None)
+    yield
+    set_max_concurrent_pings(None)
+
+
+def wait_for_inflight(expected: int, timeout: float = 5.0) -> int:
+    """等待正在运行的会话数降到 expected"""
+    deadline = time.monotonic() + timeout
+    while get_inflight_pings() > expected and time.monotonic() < deadline:
+        time.sleep(0.05)
+    return get_inflight_pings()
+
+
+def test_inflight_count(target: TargetType):
+    """测试流运行期间计入正在运行的会话数"""
+    before = get_inflight_pings()
+    stream = PingStream(target, interval_ms=200)
+    assert stream.recv() is not None
+    assert get_inflight_pings() == before + 1
+    del stream
+    assert wait_for_inflight(before) == before
+
+
+def test_queue_timeout(target: TargetType):
+    """测试达到上限后排队超时"""
+    set_max_concurrent_pings(1, queue_timeout_ms=300)
+    assert get_max_concurrent_pings() == 1
+
+    stream = PingStream(target, interval_ms=200)
+    with pytest.raises(TimeoutError):
+        PingStream(target, interval_ms=200)
+    del stream
+
+
+def test_queue_releases_gil(target: TargetType):
+    """测试排队等待时释放 GIL，其他线程可以结束会话并释放许可"""
+    set_max_concurrent_pings(1, queue_timeout_ms=5000)
+    holder = {"stream": PingStream(target, interval_ms=200)}
+
+    def release():
+        time.sleep(0.5)
+        holder.pop("stream")
+
+    thread = threading.Thread(target=release)
+    thread.start()
+    stream = PingStream(target, interval_ms=200)
+    thread.join()
+    assert stream.recv() is not None
+
+
+@pytest.mark.asyncio
+async def test_async_pings_are_queued(target: TargetType):
+    """测试超过上限的异步 ping 排队执行而不是失败"""
+    set_max_concurrent_pings(2)
+    results = await asyncio.gather(*(ping_once_async(target) for _ in range(6)))
+    assert len(results) == 6
+    assert all(result.is_success() for result in results)
+
+
+def test_invalid_args():
+    """测试无效参数"""
+    with pytest.raises(ValueError):
+        set_max_concurrent_pings(0)
+    with pytest.raises(ValueError):
+        set_max_concurrent_pings(1, queue_timeout_ms=0)
+    assert get_max_concurrent_pings() is None
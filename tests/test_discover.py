@@ -0,0 +1,38 @@
+"""
+存活主机发现测试
+"""
+
+import pytest
+from ping_rs import HostResult, PingResult, discover, discover_async
+
+
+def test_discover_returns_alive_only():
+    """测试只返回存活的地址"""
+    hosts = discover(["127.0.0.1", "192.0.2.1", "127.0.0.2"], timeout_ms=500, retries=0)
+    assert [host.target for host in hosts] == ["127.0.0.1", "127.0.0.2"]
+    assert all(isinstance(host, HostResult) and host.alive for host in hosts)
+    assert all(host.rtt_ms is not None for host in hosts)
+
+
+def test_discover_stops_at_first_reply():
+    """测试收到第一个响应后停止探测"""
+    hosts = discover("127.0.0.0/30", timeout_ms=500, retries=3)
+    assert len(hosts) == 2
+    for host in hosts:
+        assert len(host.results) == 1
+        assert isinstance(host.results[0], PingResult.Pong)
+
+
+@pytest.mark.asyncio
+async def test_discover_async():
+    """测试异步发现"""
+    hosts = await discover_async("127.0.0.1-2", timeout_ms=500)
+    assert [host.target for host in hosts] == ["127.0.0.1", "127.0.0.2"]
+
+
+def test_discover_invalid_args():
+    """测试无效参数"""
+    with pytest.raises(ValueError):
+        discover("127.0.0.1", timeout_ms=150)
+    with pytest.raises(OverflowError):
+        discover("127.0.0.1", retries=-1)
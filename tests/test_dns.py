@@ -0,0 +1,87 @@
+"""
+DNS 查询探测测试
+"""
+
+import pytest
+from ping_rs import DnsPinger, DnsPingResult, PingResult
+
+from .conftest import StubDnsServer
+
+
+def test_dns_query_a(dns_server):
+    """测试 A 记录查询"""
+    result = DnsPinger("example.test", dns_server.address).ping_once()
+    assert isinstance(result, DnsPingResult)
+    assert result.is_success()
+    assert result.rcode == "NOERROR"
+    assert result.answer_count == 1
+    assert result.addresses == ["10.0.0.1"]
+    assert result.record_type == "A"
+    assert result.protocol == "udp"
+    assert result.server == dns_server.address
+    assert result.duration_ms >= 0
+    assert dns_server.queries == [("example.test", 1, "udp")]
+
+
+def test_dns_query_aaaa_tcp(dns_server):
+    """测试通过 TCP 查询 AAAA 记录"""
+    result = DnsPinger("example.test.", dns_server.address, record_type="aaaa", protocol="tcp").ping_once()
+    assert result.addresses == ["::1"]
+    assert dns_server.queries == [("example.test", 28, "tcp")]
+
+
+def test_dns_nxdomain(dns_server):
+    """测试 NXDOMAIN 仍然是一次成功的往返"""
+    result = DnsPinger("missing.test", dns_server.address).ping_once()
+    assert result.rcode == "NXDOMAIN"
+    assert result.answer_count == 0
+    assert not result.is_success()
+    ping_result = result.to_ping_result()
+    assert isinstance(ping_result, PingResult.Pong)
+    assert "NXDOMAIN" in ping_result.line
+
+
+def test_dns_truncated_falls_back_to_tcp():
+    """测试 UDP 响应被截断时改用 TCP"""
+    server = StubDnsServer({"big.test": ["10.0.0.2"]}, truncate_udp=True)
+    try:
+        result = DnsPinger("big.test", server.address).ping_once()
+        assert result.addresses == ["10.0.0.2"]
+        assert [transport for _, _, transport in server.queries] == ["udp", "tcp"]
+    finally:
+        server.close()
+
+
+def test_dns_timeout():
+    """测试无响应的服务器"""
+    result = DnsPinger("example.test", "192.0.2.1", timeout_ms=300).ping_once()
+    assert result.timed_out
+    assert result.rcode is None
+    assert result.to_ping_result().is_timeout()
+
+
+def test_dns_ping_multiple(dns_server):
+    """测试多次查询"""
+    results = DnsPinger("example.test", dns_server.address, interval_ms=50).ping_multiple(3)
+    assert [r.rcode for r in results] == ["NOERROR"] * 3
+
+
+@pytest.mark.asyncio
+async def test_dns_async(dns_server):
+    """测试异步查询"""
+    pinger = DnsPinger("example.test", dns_server.address, interval_ms=50)
+    assert (await pinger.ping_once_async()).is_success()
+    assert len(await pinger.ping_multiple_async(count=2)) == 2
+    assert "record_type='A'" in repr(pinger)
+
+
+def test_dns_invalid_args():
+    """测试无效参数"""
+    with pytest.raises(ValueError):
+        DnsPinger("example.test", "not-an-ip")
+    with pytest.raises(ValueError):
+        DnsPinger("example.test", "127.0.0.1", record_type="BOGUS")
+    with pytest.raises(ValueError):
+        DnsPinger("example.test", "127.0.0.1", protocol="doh")
+    with pytest.raises(ValueError):
+        DnsPinger("example.test", "127.0.0.1", timeout_ms=0)
@@ -0,0 +1,158 @@
+"""
+DNS 预解析缓存测试
+"""
+
+import subprocess
+import sys
+import time
+
+import pytest
+from ping_rs import DnsResolver, PingResult, Pinger, clear_dns_cache, get_dns_cache_stats, ping_once, set_dns_cache
+
+from .conftest import StubDnsServer
+
+
+@pytest.fixture(autouse=True)
+def default_cache():
+    """每个测试前启用默认设置，测试后恢复默认的关闭状态"""
+    set_dns_cache()
+    yield
+    set_dns_cache(max_entries=0)
+
+
+def test_cache_disabled_by_default():
+    """测试新进程中缓存默认关闭"""
+    code = "import ping_rs; print(ping_rs.get_dns_cache_stats()['max_entries'])"
+    output = subprocess.run([sys.executable, "-c", code], capture_output=True, text=True, check=True).stdout
+    assert output.strip() == "0"
+
+
+def test_cache_hit(dns_server):
+    """测试重复 ping 只解析一次"""
+    resolver = DnsResolver([dns_server.address])
+    get_dns_cache_stats(reset=True)
+    pinger = Pinger("localhost.test", dns_resolver=resolver)
+    for _ in range(3):
+        assert pinger.ping_once().is_success()
+    assert len(dns_server.queries) == 1
+
+    stats = get_dns_cache_stats()
+    assert stats["hits"] == 2
+    assert stats["misses"] == 1
+    assert stats["entries"] == 1
+
+
+def test_cache_expires_with_ttl():
+    """测试按记录的 TTL 过期"""
+    server = StubDnsServer({"short.test": ["127.0.0.1"]}, ttl=1)
+    try:
+        resolver = DnsResolver([server.address])
+        assert ping_once("short.test", dns_resolver=resolver).is_success()
+        assert ping_once("short.test", dns_resolver=resolver).is_success()
+        assert len(server.queries) == 1
+
+        time.sleep(1.2)
+        assert ping_once("short.test", dns_resolver=resolver).is_success()
+        assert len(server.queries) == 2
+    finally:
+        server.close()
+
+
+def test_cache_max_age():
+    """测试 max_age_ms 限制缓存时间"""
+    set_dns_cache(max_age_ms=200)
+    server = StubDnsServer({"long.test": ["127.0.0.1"]})
+    try:
+        resolver = DnsResolver([server.address])
+        ping_once("long.test", dns_resolver=resolver)
+        time.sleep(0.3)
+        ping_once("long.test", dns_resolver=resolver)
+        assert len(server.queries) == 2
+    finally:
+        server.close()
+
+
+def test_negative_cache(dns_server):
+    """测试不存在的域名被缓存"""
+    resolver = DnsResolver([dns_server.address])
+    for _ in range(2):
+        result = ping_once("missing.test", dns_resolver=resolver)
+        assert isinstance(result, PingResult.PingExited)
+        assert "missing.test" in result.stderr
+    assert len(dns_server.queries) == 1
+
+
+def test_negative_cache_max_age(dns_server):
+    """测试否定结果按 negative_max_age_ms 过期"""
+    set_dns_cache(negative_max_age_ms=200)
+    resolver = DnsResolver([dns_server.address])
+    ping_once("missing.test", dns_resolver=resolver)
+    time.sleep(0.3)
+    ping_once("missing.test", dns_resolver=resolver)
+    assert len(dns_server.queries) == 2
+
+
+def test_timeout_not_cached():
+    """测试解析超时不被缓存"""
+    resolver = DnsResolver(["192.0.2.1"])
+    get_dns_cache_stats(reset=True)
+    ping_once("localhost.test", timeout_ms=300, dns_resolver=resolver)
+    assert get_dns_cache_stats()["entries"] == 0
+
+
+def test_clear_dns_cache(dns_server):
+    """测试清空缓存后重新解析"""
+    resolver = DnsResolver([dns_server.address])
+    ping_once("localhost.test", dns_resolver=resolver)
+    clear_dns_cache()
+    assert get_dns_cache_stats()["entries"] == 0
+    ping_once("localhost.test", dns_resolver=resolver)
+    assert len(dns_server.queries) == 2
+
+
+def test_cache_disabled(dns_server):
+    """测试 max_entries=0 禁用缓存"""
+    set_dns_cache(max_entries=0)
+    resolver = DnsResolver([dns_server.address])
+    ping_once("localhost.test", dns_resolver=resolver)
+    ping_once("localhost.test", dns_resolver=resolver)
+    assert len(dns_server.queries) == 2
+    assert get_dns_cache_stats()["entries"] == 0
+
+
+def test_cache_bounded():
+    """测试条目数不超过 max_entries"""
+    set_dns_cache(max_entries=2)
+    names = ["a.test", "b.test", "c.test"]
+    server = StubDnsServer({name: ["127.0.0.1"] for name in names})
+    try:
+        resolver = DnsResolver([server.address])
+        for name in names:
+            ping_once(name, dns_resolver=resolver)
+        assert get_dns_cache_stats()["entries"] == 2
+    finally:
+        server.close()
+
+
+def test_cache_system_resolver():
+    """测试系统解析器的结果也被缓存"""
+    get_dns_cache_stats(reset=True)
+    assert ping_once("localhost", ipv4=True).is_success()
+    assert ping_once("localhost", ipv4=True).is_success()
+    assert get_dns_cache_stats()["hits"] >= 1
+
+
+def test_set_dns_cache():
+    """测试设置和无效参数"""
+    set_dns_cache(max_entries=10, max_age_ms=1000, negative_max_age_ms=100)
+    stats = get_dns_cache_stats()
+    assert stats["max_entries"] == 10
+    assert stats["max_age_ms"] == 1000
+    assert stats["negative_max_age_ms"] == 100
+
+    with pytest.raises(ValueError):
+        set_dns_cache(max_age_ms=0)
+    with pytest.raises(ValueError):
+        set_dns_cache(negative_max_age_ms=-1)
+    with pytest.raises(OverflowError):
+        set_dns_cache(max_entries=-1)
@@ -0,0 +1,140 @@
+"""
+自定义 DNS 预解析解析器测试
+"""
+
+import time
+
+import pytest
+from ping_rs import AsyncPingStream, DnsResolver, Monitor, PingResult, PingStream, ping_once, ping_once_async
+
+from .conftest import StubDnsServer
+
+
+def test_resolver_nameserver(dns_server):
+    """测试通过指定的 DNS 服务器预解析"""
+    resolver = DnsResolver([dns_server.address])
+    result = ping_once("localhost.test", dns_resolver=resolver)
+    assert result.is_success()
+    assert ("localhost.test", 1, "udp") in dns_server.queries
+
+
+def test_resolver_tcp(dns_server):
+    """测试通过 TCP 查询"""
+    resolver = DnsResolver([dns_server.address], protocol="tcp")
+    assert ping_once("localhost.test", dns_resolver=resolver).is_success()
+    assert {transport for _, _, transport in dns_server.queries} == {"tcp"}
+
+
+def test_resolver_search_domains(dns_server):
+    """测试不带点的名称先加搜索域查询"""
+    resolver = DnsResolver([dns_server.address], search_domains=["test"])
+    assert ping_once("localhost", timeout_ms=2000, dns_resolver=resolver).is_success()
+    assert dns_server.queries[0] == ("localhost.test", 1, "udp")
+
+
+def test_resolver_hosts_override(dns_server):
+    """测试 hosts 覆盖表优先于 DNS 查询"""
+    resolver = DnsResolver([dns_server.address], hosts={"example.test": "127.0.0.1"})
+    assert ping_once("Example.Test.", dns_resolver=resolver).is_success()
+    assert dns_server.queries == []
+
+
+def test_resolver_hosts_without_nameservers():
+    """测试只有 hosts 覆盖表（其余名称使用系统解析器）"""
+    resolver = DnsResolver(hosts={"my-service": ["127.0.0.1", "::1"]})
+    assert ping_once("my-service", ipv4=True, dns_resolver=resolver).is_success()
+
+
+def test_resolver_nxdomain(dns_server):
+    """测试 NXDOMAIN 返回 PingExited"""
+    resolver = DnsResolver([dns_server.address])
+    result = ping_once("missing.test", dns_resolver=resolver)
+    assert isinstance(result, PingResult.PingExited)
+    assert "missing.test" in result.stderr
+
+
+def test_resolver_falls_back_to_next_server(dns_server):
+    """测试第一个服务器无响应时尝试下一个"""
+    resolver = DnsResolver(["192.0.2.1", dns_server.address])
+    assert ping_once("localhost.test", timeout_ms=2000, dns_resolver=resolver).is_success()
+
+
+def test_resolver_keeps_a_records_when_aaaa_fails():
+    """测试 AAAA 查询无应答时仍使用 A 记录"""
+    server = StubDnsServer({"localhost.test": ["127.0.0.1"]}, drop_qtypes=frozenset({28}))
+    try:
+        resolver = DnsResolver([server.address])
+        assert ping_once("localhost.test", timeout_ms=1000, dns_resolver=resolver).is_success()
+        assert ("localhost.test", 28, "udp") in server.queries
+    finally:
+        server.close()
+
+
+def test_resolver_unreachable_server():
+    """测试所有服务器都无响应"""
+    resolver = DnsResolver(["192.0.2.1"])
+    result = ping_once("localhost.test", timeout_ms=500, dns_resolver=resolver)
+    assert isinstance(result, PingResult.PingExited)
+
+
+@pytest.mark.asyncio
+async def test_resolver_async(dns_server):
+    """测试异步 ping 和异步流"""
+    resolver = DnsResolver([dns_server.address])
+    assert (await ping_once_async("localhost.test", dns_resolver=resolver)).is_success()
+
+    stream = AsyncPingStream("localhost.test", interval_ms=100, max_count=2, dns_resolver=resolver)
+    results = [result async for result in stream]
+    assert all(result.is_success() for result in results)
+
+
+def test_resolver_stream(dns_server):
+    """测试同步流"""
+    resolver = DnsResolver([dns_server.address])
+    stream = PingStream("localhost.test", interval_ms=100, max_count=2, dns_resolver=resolver)
+    assert all(result.is_success() for result in stream)
+
+
+def test_resolver_monitor(dns_server):
+    """测试 Monitor 每次启动前使用解析器"""
+    resolver = DnsResolver([dns_server.address])
+    with Monitor() as monitor:
+        monitor.add_target("localhost.test", interval_ms=200, dns_resolver=resolver)
+        deadline = time.monotonic() + 10
+        stats = monitor.get_stats("localhost.test")
+        while stats.packets_received < 2 and time.monotonic() < deadline:
+            time.sleep(0.1)
+            stats = monitor.get_stats("localhost.test")
+        assert stats.packets_received >= 2
+        assert stats.dns_failures == 0
+
+
+def test_resolver_properties():
+    """测试属性和 repr"""
+    resolver = DnsResolver(
+        ["127.0.0.1", "[::1]:5353"],
+        protocol="TCP",
+        search_domains=["corp.example."],
+        hosts={"A.Example": ["10.0.0.1", "::1"]},
+    )
+    assert resolver.nameservers == ["127.0.0.1:53", "[::1]:5353"]
+    assert resolver.protocol == "tcp"
+    assert resolver.search_domains == ["corp.example"]
+    assert resolver.hosts == {"a.example": ["10.0.0.1", "::1"]}
+    assert "protocol='tcp'" in repr(resolver)
+
+    default = DnsResolver()
+    assert default.nameservers == []
+    assert default.protocol == "udp"
+
+
+def test_resolver_invalid_args():
+    """测试无效参数"""
+    with pytest.raises(ValueError):
+        DnsResolver(["not-an-ip"])
+    with pytest.raises(ValueError):
+        DnsResolver(protocol="doh")
+    with pytest.raises(ValueError):
+        DnsResolver(search_domains=["."])
+    with pytest.raises(ValueError):
+        DnsResolver(hosts={"a.example": "999.0.0.1"})
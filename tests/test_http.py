@@ -0,0 +1,142 @@
+"""
+HTTP 探测测试
+"""
+
+import socket
+import threading
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+
+import pytest
+from ping_rs import HttpPinger, HttpPingResult, HttpPingStream, PingResult
+
+
+class Handler(BaseHTTPRequestHandler):
+    """根路径返回 200，`/missing` 返回 404，`/slow` 延迟响应"""
+
+    def _respond(self, body: bytes):
+        if self.path == "/slow":
+            threading.Event().wait(1.0)
+        status = 404 if self.path == "/missing" else 200
+        self.send_response(status)
+        self.send_header("Content-Length", str(len(body)))
+        self.send_header("X-Echo", self.headers.get("X-Test", ""))
+        self.end_headers()
+        return body
+
+    def do_HEAD(self):
+        self._respond(b"hello")
+
+    def do_GET(self):
+        self.wfile.write(self._respond(b"hello"))
+
+    def log_message(self, *args):
+        pass
+
+
+@pytest.fixture
+def http_server():
+    """本地 HTTP 服务器，返回基础 URL"""
+    server = ThreadingHTTPServer(("127.0.0.1", 0), Handler)
+    thread = threading.Thread(target=server.serve_forever, daemon=True)
+    thread.start()
+    yield f"http://127.0.0.1:{server.server_address[1]}"
+    server.shutdown()
+    server.server_close()
+
+
+def test_http_head(http_server):
+    """测试 HEAD 请求的计时分解"""
+    result = HttpPinger(http_server).ping_once()
+    assert isinstance(result, HttpPingResult)
+    assert result.status == 200
+    assert result.is_success()
+    assert result.method == "HEAD"
+    assert result.address == "127.0.0.1"
+    assert result.tls_ms is None
+    assert result.error is None and not result.timed_out
+    for phase in (result.dns_ms, result.connect_ms, result.ttfb_ms):
+        assert phase is not None and 0 <= phase <= result.total_ms
+    assert "status=200" in repr(result)
+
+
+def test_http_get_with_headers(http_server):
+    """测试 GET 请求和自定义请求头"""
+    result = HttpPinger(f"{http_server}/missing", method="get", headers={"X-Test": "1"}).ping_once()
+    assert result.method == "GET"
+    assert result.status == 404
+    assert not result.is_success()
+    # 4xx 仍然是一次成功的往返
+    assert result.to_ping_result().is_success()
+
+
+def test_http_to_ping_result(http_server):
+    """测试转换为 PingResult"""
+    result = HttpPinger(http_server).ping_once().to_ping_result()
+    assert isinstance(result, PingResult.Pong)
+    assert "HTTP 200" in result.line
+
+
+def test_http_timeout(http_server):
+    """测试超时"""
+    result = HttpPinger(f"{http_server}/slow", timeout_ms=200).ping_once()
+    assert result.timed_out
+    assert result.status is None
+    assert result.connect_ms is not None
+    assert result.ttfb_ms is None
+    assert result.to_ping_result().is_timeout()
+
+
+def test_http_connection_refused():
+    """测试连接被拒绝"""
+    sock = socket.socket()
+    sock.bind(("127.0.0.1", 0))
+    port = sock.getsockname()[1]
+    sock.close()
+    result = HttpPinger(f"http://127.0.0.1:{port}/").ping_once()
+    assert result.status is None
+    assert result.error
+    assert not result.timed_out
+    assert result.connect_ms is None
+    assert result.to_ping_result().is_exited()
+
+
+def test_http_ping_multiple(http_server):
+    """测试多次请求"""
+    results = HttpPinger(http_server, interval_ms=50).ping_multiple(3)
+    assert [r.status for r in results] == [200, 200, 200]
+
+
+@pytest.mark.asyncio
+async def test_http_async(http_server):
+    """测试异步请求"""
+    pinger = HttpPinger(http_server, interval_ms=50)
+    assert (await pinger.ping_once_async()).status == 200
+    results = await pinger.ping_multiple_async(count=2)
+    assert len(results) == 2
+
+
+def test_http_stream_sync(http_server):
+    """测试同步迭代流"""
+    stream = HttpPingStream(http_server, interval_ms=50, max_count=3)
+    assert [r.status for r in stream] == [200, 200, 200]
+    assert not stream.is_active()
+
+
+@pytest.mark.asyncio
+async def test_http_stream_async(http_server):
+    """测试异步迭代流"""
+    stream = HttpPingStream(http_server, interval_ms=50, max_count=2)
+    results = [result async for result in stream]
+    assert len(results) == 2
+
+
+def test_http_invalid_args():
+    """测试无效参数"""
+    with pytest.raises(ValueError):
+        HttpPinger("ftp://example.com")
+    with pytest.raises(ValueError):
+        HttpPinger("http://example.com", method="POST")
+    with pytest.raises(ValueError):
+        HttpPinger("http://example.com", headers={"X-Bad": "a\r\nb"})
+    with pytest.raises(ValueError):
+        HttpPingStream("http://example.com", timeout_ms=0)
@@ -0,0 +1,83 @@
+"""
+启动偏移和间隔抖动测试
+"""
+
+import time
+
+import pytest
+from ping_rs import AsyncPingStream, Monitor, PingStream, create_ping_stream
+from ping_rs.core_schema import TargetType
+
+# 种子 0 的第一个随机数约为 0.883
+SEED = 0
+
+
+def test_start_jitter_defers_start(target: TargetType):
+    """测试设置启动偏移后 ping 进程延迟启动"""
+    start = time.monotonic()
+    stream = PingStream(target, interval_ms=200, start_jitter_ms=10000, jitter_seed=SEED)
+    # 构造时不等待
+    assert time.monotonic() - start < 1.0
+    assert stream.is_active()
+    assert stream.try_recv() is None
+
+
+def test_start_jitter_blocking_recv(target: TargetType):
+    """测试阻塞接收会等待启动偏移"""
+    stream = create_ping_stream(target, interval_ms=200, start_jitter_ms=500, jitter_seed=SEED)
+    start = time.monotonic()
+    assert stream.recv() is not None
+    # 偏移约 441ms
+    assert time.monotonic() - start >= 0.4
+
+
+@pytest.mark.asyncio
+async def test_async_start_jitter(target: TargetType):
+    """测试异步流首次迭代等待启动偏移"""
+    start = time.monotonic()
+    stream = AsyncPingStream(target, interval_ms=200, max_count=1, start_jitter_ms=500, jitter_seed=SEED)
+    results = [result async for result in stream]
+    assert len(results) == 1
+    assert time.monotonic() - start >= 0.4
+
+
+def test_interval_jitter(target: TargetType):
+    """测试间隔抖动的流正常产生结果"""
+    stream = PingStream(target, interval_ms=500, max_count=2, interval_jitter_pct=50, jitter_seed=SEED)
+    assert len(list(stream)) == 2
+
+
+
+def test_interval_jitter_on_restart():
+    """测试重启时重新抽取间隔抖动，流仍按 max_restarts 重启"""
+    stream = PingStream(
+        "nonexistent.invalid",
+        interval_ms=200,
+        dns_resolve_timeout_ms=1000,
+        restart_on_exit=True,
+        restart_backoff_ms=100,
+        max_restarts=2,
+        interval_jitter_pct=50,
+        jitter_seed=SEED,
+    )
+    assert len(list(stream)) == 3
+
+
+def test_monitor_jitter(target: TargetType):
+    """测试监控目标的启动偏移"""
+    with Monitor(jitter_seed=SEED) as monitor:
+        monitor.add_target(target, interval_ms=200, start_jitter_ms=10000)
+        time.sleep(0.5)
+        assert monitor.get_stats(target).packets_sent == 0
+
+
+def test_jitter_invalid_args():
+    """测试无效的抖动参数"""
+    with pytest.raises(ValueError):
+        PingStream("127.0.0.1", start_jitter_ms=-1)
+    with pytest.raises(ValueError):
+        PingStream("127.0.0.1", interval_jitter_pct=100)
+    with pytest.raises(ValueError):
+        AsyncPingStream("127.0.0.1", interval_jitter_pct=-1)
+    with Monitor() as monitor, pytest.raises(ValueError):
+        monitor.add_target("127.0.0.1", start_jitter_ms=-1)
@@ -0,0 +1,90 @@
+"""
+本地化 ping 输出解析测试
+"""
+
+import sys
+
+import pytest
+from ping_rs import PingResult, ping_once
+from ping_rs.core_schema import TargetType
+
+# 非英语系统下 ping 命令的典型输出
+PONG_FIXTURES = [
+    ("64 Bytes von 127.0.0.1: icmp_seq=1 ttl=64 Zeit=12,3 ms", 12.3),
+    ("64 octets de 127.0.0.1 : icmp_seq=1 ttl=64 temps=0,045 ms", 0.045),
+    ("64 字节，来自 127.0.0.1: icmp_seq=1 ttl=64 时间=0.045 毫秒", 0.045),
+    ("来自 127.0.0.1 的回复: 字节=32 时间=3ms TTL=128", 3.0),
+    ("127.0.0.1 からの応答: バイト数 =32 時間 <1ms TTL=128", 1.0),
+    ("Ответ от 127.0.0.1: число байт=32 время=5мс TTL=128", 5.0),
+    ("64 bytes from 127.0.0.1: icmp_seq=1 ttl=64 time=0.045 ms", 0.045),
+]
+
+TIMEOUT_FIXTURES = [
+    "Zeitüberschreitung der Anforderung.",
+    "请求超时。",
+    "Délai d'attente de la demande dépassé.",
+    "要求がタイムアウトしました。",
+    "Request timeout for icmp_seq 1",
+    "no answer yet for icmp_seq=2",
+]
+
+
+@pytest.mark.parametrize(("line", "expected_ms"), PONG_FIXTURES)
+def test_parse_localized_pong(line: str, expected_ms: float):
+    """测试本地化延迟行（包括逗号小数点）被识别为 Pong"""
+    result = PingResult.parse_line(line)
+    assert result.is_success()
+    assert result.duration_ms == pytest.approx(expected_ms)
+    assert result.line == line
+
+
+@pytest.mark.parametrize("line", TIMEOUT_FIXTURES)
+def test_parse_localized_timeout(line: str):
+    """测试本地化超时行被识别为 Timeout"""
+    result = PingResult.parse_line(line)
+    assert result.is_timeout()
+
+
+@pytest.mark.parametrize(
+    "line",
+    [
+        "PING 127.0.0.1 (127.0.0.1) 56(84) Bytes an Daten.",
+        "64 Bytes von 127.0.0.1: icmp_seq=1 ttl=64",
+        "ping: sendmsg: Connection timed out",
+        "4 packets transmitted, 0 received, 100% packet loss, time 3000ms, timeout",
+        "",
+    ],
+)
+def test_parse_unrecognized_line(line: str):
+    """测试无法识别的行仍然返回 Unknown"""
+    result = PingResult.parse_line(line)
+    assert result.is_unknown()
+
+
+@pytest.mark.parametrize(
+    "line",
+    [
+        "    Minimum = 1ms, Maximum = 2ms, Average = 1ms",
+        "    Minimum = 1ms, Maximum = 2ms, Mittelwert = 1ms",
+        "    最短 = 3ms，最长 = 5ms，平均 = 4ms",
+        "    Минимальное = 5мсек, Максимальное = 6 мсек, Среднее = 5 мсек",
+    ],
+)
+def test_parse_summary_line(line: str):
+    """测试 Windows 统计摘要行不会被识别为 Pong"""
+    result = PingResult.parse_line(line)
+    assert result.is_unknown()
+
+
+@pytest.mark.skipif(sys.platform == "win32", reason="Windows 使用原生 ICMP 实现，不解析命令输出")
+def test_child_locale_is_c(target: TargetType, monkeypatch: pytest.MonkeyPatch):
+    """测试 ping 进程不继承本进程的语言环境，输出按英文格式解析"""
+    monkeypatch.setenv("LC_ALL", "de_DE.UTF-8")
+    monkeypatch.setenv("LANG", "de_DE.UTF-8")
+    result = ping_once(target, timeout_ms=2000)
+    assert result.is_success()
+    assert "bytes from" in result.line
+
+
+if __name__ == "__main__":
+    _ = pytest.main(["-xvs", __file__])
@@ -0,0 +1,68 @@
+"""
+Prometheus 指标导出测试
+"""
+
+import time
+import urllib.error
+import urllib.request
+
+import pytest
+from ping_rs import MetricsServer, start_metrics_server
+from ping_rs.core_schema import TargetType
+
+
+def scrape(server: MetricsServer, path: str = "/metrics") -> str:
+    """抓取本地导出器"""
+    with urllib.request.urlopen(f"http://127.0.0.1:{server.port}{path}", timeout=5) as response:
+        assert response.status == 200
+        return response.read().decode()
+
+
+def wait_for(server: MetricsServer, needle: str, timeout: float = 10.0) -> str:
+    """等待指标中出现指定内容"""
+    deadline = time.monotonic() + timeout
+    body = scrape(server)
+    while needle not in body and time.monotonic() < deadline:
+        time.sleep(0.2)
+        body = scrape(server)
+    return body
+
+
+def test_metrics_server_scrape(target: TargetType):
+    """测试抓取 RTT 直方图、计数器和 up 状态"""
+    with start_metrics_server(0, [target], interval_ms=200, host="127.0.0.1") as server:
+        assert server.port > 0
+        assert server.is_running()
+
+        body = wait_for(server, f'ping_up{{target="{target}"}} 1')
+        assert "# TYPE ping_rtt_seconds histogram" in body
+        assert f'ping_up{{target="{target}"}} 1' in body
+        assert f'ping_rtt_seconds_bucket{{target="{target}",le="+Inf"}}' in body
+        assert f'ping_packets_sent_total{{target="{target}"}}' in body
+        assert f'ping_packets_lost_total{{target="{target}"}}' in body
+        assert f'ping_rtt_seconds_count{{target="{target}"}}' in server.render()
+
+    assert not server.is_running()
+
+
+def test_metrics_server_dns_failure():
+    """测试无法解析的主机名计入 DNS 失败"""
+    host = "nonexistent.invalid"
+    with start_metrics_server(0, [host], interval_ms=200, host="127.0.0.1", dns_resolve_timeout_ms=1000) as server:
+        body = wait_for(server, f'ping_dns_failures_total{{target="{host}"}} 1')
+        assert f'ping_up{{target="{host}"}} 0' in body
+        assert f'ping_dns_failures_total{{target="{host}"}} 0' not in body
+
+
+def test_metrics_server_not_found():
+    """测试未知路径返回 404"""
+    with start_metrics_server(0, [], host="127.0.0.1") as server:
+        with pytest.raises(urllib.error.HTTPError) as exc_info:
+            scrape(server, "/unknown")
+        assert exc_info.value.code == 404
+
+
+def test_metrics_server_invalid_interval(target: TargetType):
+    """测试无效的 interval_ms"""
+    with pytest.raises(ValueError):
+        start_metrics_server(0, [target], interval_ms=150)
@@ -0,0 +1,98 @@
+"""
+Monitor 多目标监控测试
+"""
+
+import time
+
+import pytest
+from ping_rs import Monitor, PingResult, PingStats, Recorder
+from ping_rs.core_schema import TargetType
+
+
+def wait_for_stats(monitor: Monitor, target: str, predicate, timeout: float = 10.0) -> PingStats:
+    """等待目标统计满足条件"""
+    deadline = time.monotonic() + timeout
+    stats = monitor.get_stats(target)
+    while not predicate(stats) and time.monotonic() < deadline:
+        time.sleep(0.1)
+        stats = monitor.get_stats(target)
+    return stats
+
+
+def test_monitor_stats(target: TargetType):
+    """测试目标统计"""
+    with Monitor(window=10) as monitor:
+        monitor.add_target(target, interval_ms=200)
+        assert str(target) in monitor
+        assert len(monitor) == 1
+        assert monitor.get_stats(target).state == "unknown"
+
+        stats = wait_for_stats(monitor, str(target), lambda s: s.packets_sent >= 3)
+        assert stats.state == "up"
+        assert stats.packets_received >= 3
+        assert stats.packets_lost == stats.packets_sent - stats.packets_received
+        assert stats.loss_pct == 0.0
+        assert stats.min_ms is not None and stats.max_ms is not None
+        assert stats.min_ms <= stats.avg_ms <= stats.max_ms
+        assert stats.jitter_ms is not None and stats.jitter_ms >= 0
+        assert isinstance(stats.latest, PingResult.Pong)
+        assert stats.last_update is not None
+        assert stats.to_dict()["state"] == "up"
+
+        all_stats = monitor.get_all_stats()
+        assert list(all_stats) == [str(target)]
+
+    assert not monitor.is_running()
+    assert len(monitor) == 0
+
+
+def test_monitor_multiple_targets(target: TargetType):
+    """测试多个目标互不影响，DNS 失败的目标会持续重试"""
+    with Monitor(restart_backoff_ms=100, max_backoff_ms=200) as monitor:
+        monitor.add_target(target, interval_ms=200)
+        monitor.add_target("nonexistent.invalid", interval_ms=200, dns_resolve_timeout_ms=1000)
+
+        bad = wait_for_stats(monitor, "nonexistent.invalid", lambda s: s.dns_failures >= 2)
+        assert bad.state == "down"
+        # 没有启动过 ping 进程，DNS 重试不计入重启次数
+        assert bad.restarts == 0
+
+        good = wait_for_stats(monitor, str(target), lambda s: s.packets_received >= 1)
+        assert good.state == "up"
+
+
+def test_monitor_add_remove(target: TargetType):
+    """测试添加和移除目标"""
+    with Monitor() as monitor:
+        monitor.add_target(target)
+        with pytest.raises(ValueError):
+            monitor.add_target(target)
+
+        assert monitor.remove_target(target)
+        assert not monitor.remove_target(target)
+        assert monitor.targets() == []
+        with pytest.raises(KeyError):
+            monitor.get_stats(target)
+
+
+def test_monitor_sink(tmp_path, target: TargetType):
+    """测试监控结果写入输出端"""
+    path = tmp_path / "monitor.jsonl"
+    with Recorder(path) as recorder, Monitor() as monitor:
+        monitor.add_sink(recorder)
+        monitor.add_target(target, interval_ms=200)
+        wait_for_stats(monitor, str(target), lambda s: s.packets_sent >= 2)
+        monitor.stop()
+        recorder.flush()
+
+    assert len(path.read_text().splitlines()) >= 2
+
+
+def test_monitor_invalid_args():
+    """测试无效参数"""
+    with pytest.raises(ValueError):
+        Monitor(window=0)
+    with pytest.raises(ValueError):
+        Monitor(backoff_multiplier=0.5)
+    with pytest.raises(ValueError):
+        Monitor().add_target("127.0.0.1", interval_ms=150)
@@ -0,0 +1,134 @@
+"""
+OTLP/JSON 指标推送测试（需要以 `otlp` feature 构建）
+"""
+
+import json
+import threading
+import time
+from collections.abc import Iterator
+from http.server import BaseHTTPRequestHandler, HTTPServer
+from typing import Any
+
+import pytest
+from ping_rs import OtlpJsonSink, PingResult, PingStream
+from ping_rs.core_schema import TargetType
+
+if OtlpJsonSink is None:
+    pytest.skip("ping-rs built without the otlp feature", allow_module_level=True)
+
+
+class MockCollector(HTTPServer):
+    """记录收到的 OTLP 请求的本地采集器"""
+
+    def __init__(self):
+        super().__init__(("127.0.0.1", 0), CollectorHandler)
+        self.requests: list[tuple[str, dict[str, Any]]] = []
+
+    @property
+    def endpoint(self) -> str:
+        return f"http://127.0.0.1:{self.server_address[1]}"
+
+
+class CollectorHandler(BaseHTTPRequestHandler):
+    server: MockCollector
+
+    def do_POST(self):
+        length = int(self.headers["Content-Length"])
+        body = json.loads(self.rfile.read(length))
+        self.server.requests.append((self.path, body))
+        self.send_response(200)
+        self.send_header("Content-Length", "0")
+        self.end_headers()
+
+    def log_message(self, *args: Any):
+        pass
+
+
+@pytest.fixture
+def collector() -> Iterator[MockCollector]:
+    server = MockCollector()
+    thread = threading.Thread(target=server.serve_forever, daemon=True)
+    thread.start()
+    yield server
+    server.shutdown()
+    server.server_close()
+
+
+def metrics_by_name(body: dict[str, Any]) -> dict[str, dict[str, Any]]:
+    """按名称索引请求中的指标"""
+    metrics = body["resourceMetrics"][0]["scopeMetrics"][0]["metrics"]
+    return {metric["name"]: metric for metric in metrics}
+
+
+def attributes(point: dict[str, Any]) -> dict[str, str]:
+    return {attr["key"]: attr["value"]["stringValue"] for attr in point["attributes"]}
+
+
+def test_otlp_manual_export(collector: MockCollector):
+    """测试手动写入结果后导出"""
+    with OtlpJsonSink(collector.endpoint, export_interval_ms=60000) as sink:
+        sink.write(PingResult.Pong(1.5, "a"), target="10.0.0.1")
+        sink.write(PingResult.Timeout("b"), target="10.0.0.1")
+        sink.force_flush()
+
+    path, body = collector.requests[0]
+    assert path == "/v1/metrics"
+    metrics = metrics_by_name(body)
+
+    rtt = metrics["ping.rtt"]["histogram"]["dataPoints"][0]
+    assert attributes(rtt)["target"] == "10.0.0.1"
+    assert rtt["count"] == "1"
+    assert len(rtt["bucketCounts"]) == len(rtt["explicitBounds"]) + 1
+    assert sum(int(c) for c in rtt["bucketCounts"]) == 1
+
+    assert metrics["ping.packets.sent"]["sum"]["dataPoints"][0]["asInt"] == "2"
+    assert metrics["ping.packets.lost"]["sum"]["dataPoints"][0]["asInt"] == "1"
+    assert metrics["ping.up"]["gauge"]["dataPoints"][0]["asInt"] == "0"
+
+
+def test_otlp_stream_attributes(collector: MockCollector, target: TargetType):
+    """测试挂载到流上时带有流属性"""
+    sink = OtlpJsonSink(collector.endpoint, export_interval_ms=60000)
+    stream = PingStream(target, interval_ms=200, max_count=2, ipv4=True)
+    stream.add_sink(sink)
+    results = list(stream)
+    assert len(results) == 2
+    sink.shutdown()
+
+    _, body = collector.requests[-1]
+    point = metrics_by_name(body)["ping.packets.sent"]["sum"]["dataPoints"][0]
+    attrs = attributes(point)
+    assert attrs["target"] == str(target)
+    assert attrs["address_family"] == "ipv4"
+    assert point["asInt"] == "2"
+
+
+def test_otlp_periodic_export(collector: MockCollector):
+    """测试定期导出"""
+    sink = OtlpJsonSink(collector.endpoint, export_interval_ms=100)
+    sink.write(PingResult.Pong(1.0, "a"), target="host")
+    deadline = time.monotonic() + 5
+    while not collector.requests and time.monotonic() < deadline:
+        time.sleep(0.05)
+    sink.shutdown()
+    assert collector.requests
+
+
+def test_otlp_export_error():
+    """测试采集器不可达时报告错误"""
+    sink = OtlpJsonSink("http://127.0.0.1:1", export_interval_ms=60000, timeout_ms=1000)
+    with pytest.raises(OSError):
+        sink.force_flush()
+
+
+def test_otlp_https_endpoint():
+    """测试 https:// 采集器地址"""
+    sink = OtlpJsonSink("https://collector:4318", export_interval_ms=60000)
+    assert sink.endpoint == "https://collector:4318/v1/metrics"
+    sink.shutdown()
+
+
+def test_otlp_invalid_endpoint():
+    """测试无效的采集器地址"""
+    with pytest.raises(ValueError):
+        OtlpJsonSink("udp://collector:4318")
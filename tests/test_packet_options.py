@@ -0,0 +1,45 @@
+"""
+负载大小和禁止分片参数测试
+"""
+
+import sys
+
+import pytest
+from ping_rs import AsyncPinger, AsyncPingStream, Pinger, PingStream, create_ping_stream
+
+
+def test_pinger_size():
+    """测试指定负载大小：回环接口可以通过大包"""
+    pinger = Pinger("127.0.0.1", size=1400, dont_fragment=True)
+    result = pinger.ping_once()
+    assert result.is_success()
+    if sys.platform != "win32":
+        # ping 报告的字节数是负载加 ICMP 头部
+        assert "1408 bytes from" in result.line
+
+
+@pytest.mark.asyncio
+async def test_async_pinger_size():
+    """测试异步版本的负载大小参数"""
+    pinger = AsyncPinger("127.0.0.1", size=100)
+    result = await pinger.ping_once()
+    assert result.is_success()
+
+
+def test_stream_size():
+    """测试流的负载大小和禁止分片参数"""
+    stream = create_ping_stream("127.0.0.1", interval_ms=200, count=2, size=64, dont_fragment=True)
+    results = list(stream)
+    assert len(results) == 2
+    assert all(result.is_success() for result in results)
+
+
+def test_invalid_size():
+    """测试超出范围的负载大小"""
+    for cls in (Pinger, AsyncPinger, PingStream, AsyncPingStream):
+        with pytest.raises(ValueError):
+            cls("127.0.0.1", size=8)
+        with pytest.raises(ValueError):
+            cls("127.0.0.1", size=70_000)
+        with pytest.raises(ValueError):
+            cls("127.0.0.1", size=-1)
@@ -0,0 +1,59 @@
+"""
+路径 MTU 探测测试
+"""
+
+import time
+
+import pytest
+from ping_rs import PathMtuResult, discover_path_mtu, discover_path_mtu_async
+
+
+def test_path_mtu_loopback():
+    """测试本机地址：回环接口的 MTU 大于搜索上界"""
+    result = discover_path_mtu("127.0.0.1", min=576, max=1500, timeout_ms=500)
+    assert isinstance(result, PathMtuResult)
+    assert result.address == "127.0.0.1"
+    assert result.mtu == 1500
+    assert result.payload_size == 1500 - 28
+    assert result.probes >= 1
+    assert "mtu=1500" in repr(result)
+
+
+def test_path_mtu_min_equals_max():
+    """测试上下界相同时只探测一次"""
+    result = discover_path_mtu("127.0.0.1", min=1000, max=1000, timeout_ms=500)
+    assert result.mtu == 1000
+    assert result.probes == 1
+
+
+@pytest.mark.asyncio
+async def test_path_mtu_async():
+    """测试异步版本"""
+    result = await discover_path_mtu_async("localhost", max=2000, timeout_ms=500, ipv4=True)
+    assert result.mtu == 2000
+
+
+def test_path_mtu_unreachable():
+    """测试无响应的地址：下界也无法通过"""
+    result = discover_path_mtu("192.0.2.1", timeout_ms=500, retries=0)
+    assert result.mtu is None
+    assert result.payload_size is None
+
+
+def test_path_mtu_invalid_args():
+    """测试无效参数"""
+    with pytest.raises(ValueError):
+        discover_path_mtu("127.0.0.1", min=1500, max=1000)
+    with pytest.raises(ValueError):
+        discover_path_mtu("127.0.0.1", min=10)
+    with pytest.raises(ValueError):
+        discover_path_mtu("127.0.0.1", max=70000)
+
+
+def test_path_mtu_sub_second_timeout():
+    """测试小于 1 秒的超时不会被取整到整秒"""
+    start = time.monotonic()
+    result = discover_path_mtu("192.0.2.1", timeout_ms=200, retries=0)
+    assert result.mtu is None
+    assert result.probes == 1
+    assert time.monotonic() - start < 0.9
@@ -0,0 +1,66 @@
+"""
+持续路径监控（AsyncPathStream）测试
+"""
+
+import pytest
+from ping_rs import AsyncPathStream, PathHopStats, PathReport
+
+
+@pytest.mark.asyncio
+async def test_path_stream_loopback():
+    """测试本机地址：每轮一跳，统计逐轮累计"""
+    stream = AsyncPathStream("127.0.0.1", interval_ms=200, timeout_ms=500, max_cycles=3)
+    reports = [report async for report in stream]
+    assert len(reports) == 3
+    assert [report.cycles for report in reports] == [1, 2, 3]
+
+    final = stream.report()
+    assert isinstance(final, PathReport)
+    assert final.reached
+    assert final.address == "127.0.0.1"
+    assert len(final.hops) == 1
+    hop = final.hops[0]
+    assert isinstance(hop, PathHopStats)
+    assert hop.hop == 1
+    assert hop.address == "127.0.0.1"
+    assert hop.packets_sent == 3
+    assert hop.packets_received + hop.packets_lost == 3
+
+
+@pytest.mark.asyncio
+async def test_path_stream_report_text():
+    """测试 mtr 风格的文本报告"""
+    stream = AsyncPathStream("127.0.0.1", timeout_ms=500, max_cycles=1)
+    async for _ in stream:
+        pass
+    text = str(stream.report())
+    assert text.startswith("HOST: 127.0.0.1")
+    assert "Loss%" in text
+    assert "1.|-- 127.0.0.1" in text
+
+
+def test_path_stream_empty_report():
+    """测试尚未探测时的报告"""
+    report = AsyncPathStream("127.0.0.1").report()
+    assert report.cycles == 0
+    assert report.hops == []
+    assert report.address is None
+    assert not report.reached
+
+
+@pytest.mark.asyncio
+async def test_path_stream_unresolvable():
+    """测试无法解析的主机名"""
+    stream = AsyncPathStream("nonexistent.invalid", timeout_ms=500)
+    with pytest.raises(RuntimeError):
+        await stream.__anext__()
+
+
+def test_path_stream_invalid_args():
+    """测试无效参数"""
+    with pytest.raises(ValueError):
+        AsyncPathStream("127.0.0.1", max_hops=0)
+    with pytest.raises(ValueError):
+        AsyncPathStream("127.0.0.1", window=0)
+    with pytest.raises(ValueError):
+        AsyncPathStream("127.0.0.1", interval_ms=0)
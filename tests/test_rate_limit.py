@@ -0,0 +1,102 @@
+"""
+全局速率限制测试
+"""
+
+import asyncio
+import threading
+import time
+
+import pytest
+from ping_rs import Pinger, get_rate_limit_stats, ping_once_async, set_global_rate_limit
+from ping_rs.core_schema import TargetType
+
+
+@pytest.fixture(autouse=True)
+def reset_rate_limit():
+    """每个测试前后取消限制并清零统计"""
+    set_global_rate_limit(None)
+    get_rate_limit_stats(reset=True)
+    yield
+    set_global_rate_limit(None)
+    get_rate_limit_stats(reset=True)
+
+
+def test_rate_limit_disabled_by_default(target: TargetType):
+    """测试默认不限速"""
+    Pinger(target).ping_once()
+    stats = get_rate_limit_stats()
+    assert stats["spawns_per_second"] is None
+    assert stats["burst"] is None
+    assert stats["throttled"] == 0
+
+
+def test_rate_limit_throttles(target: TargetType):
+    """测试超过突发容量后等待令牌"""
+    set_global_rate_limit(5, burst=1)
+    stats = get_rate_limit_stats()
+    assert stats["spawns_per_second"] == 5
+    assert stats["burst"] == 1
+
+    pinger = Pinger(target)
+    start = time.monotonic()
+    for _ in range(3):
+        pinger.ping_once()
+    elapsed = time.monotonic() - start
+
+    # 第一个令牌立即可用，之后每个等待约 200ms
+    assert elapsed >= 0.35
+    stats = get_rate_limit_stats(reset=True)
+    assert stats["acquired"] == 3
+    assert stats["throttled"] >= 1
+    assert stats["throttled_time_ms"] > 0
+    assert get_rate_limit_stats()["acquired"] == 0
+
+
+def test_rate_limit_wait_releases_gil(target: TargetType):
+    """测试同步 ping 等待令牌时释放 GIL，其他 Python 线程可以继续运行"""
+    set_global_rate_limit(1, burst=1)
+    pinger = Pinger(target)
+    pinger.ping_once()
+
+    ticks = []
+    thread = threading.Thread(target=pinger.ping_once)
+    thread.start()
+    while thread.is_alive():
+        ticks.append(time.monotonic())
+        time.sleep(0.01)
+    thread.join()
+    # 等待约 1 秒期间主线程持续运行
+    assert len(ticks) > 20
+
+
+@pytest.mark.asyncio
+async def test_rate_limit_async(target: TargetType):
+    """测试异步 ping 同样受限速"""
+    set_global_rate_limit(10, burst=1)
+    for _ in range(2):
+        await ping_once_async(target)
+    stats = get_rate_limit_stats()
+    assert stats["acquired"] == 2
+    assert stats["throttled"] >= 1
+
+
+@pytest.mark.asyncio
+async def test_rate_limit_cancelled_wait_returns_token(target: TargetType):
+    """测试取消等待令牌的异步 ping 后令牌被归还"""
+    set_global_rate_limit(1, burst=1)
+    await ping_once_async(target)
+    with pytest.raises(asyncio.TimeoutError):
+        await asyncio.wait_for(ping_once_async(target), timeout=0.1)
+    # 给运行时一点时间丢弃被取消的 future
+    await asyncio.sleep(0.1)
+    assert get_rate_limit_stats()["acquired"] == 1
+
+
+def test_rate_limit_invalid_args():
+    """测试无效参数"""
+    with pytest.raises(ValueError):
+        set_global_rate_limit(0)
+    with pytest.raises(ValueError):
+        set_global_rate_limit(float("inf"))
+    with pytest.raises(ValueError):
+        set_global_rate_limit(10, burst=0)
@@ -0,0 +1,158 @@
+"""
+Recorder 结果记录测试
+"""
+
+import csv
+import gc
+import json
+import os
+import sys
+from pathlib import Path
+
+import pytest
+from ping_rs import AsyncPingStream, PingResult, PingStream, Recorder
+from ping_rs.core_schema import TargetType
+
+
+def test_recorder_jsonl(tmp_path: Path):
+    """测试手动写入 JSON Lines"""
+    path = tmp_path / "trace.jsonl"
+    with Recorder(path) as recorder:
+        recorder.write(PingResult.Pong(1.5, "a"), target="127.0.0.1")
+        recorder.write(PingResult.Timeout("b"), target="127.0.0.1")
+    assert recorder.closed
+
+    records = [json.loads(line) for line in path.read_text().splitlines()]
+    assert len(records) == 2
+    assert records[0]["type"] == "Pong"
+    assert records[0]["duration_ms"] == 1.5
+    assert records[0]["target"] == "127.0.0.1"
+    assert records[1]["type"] == "Timeout"
+    assert all(record["timestamp"] > 0 for record in records)
+
+
+def test_recorder_csv(tmp_path: Path):
+    """测试 CSV 格式及字段转义"""
+    path = tmp_path / "trace.csv"
+    with Recorder(path, format="csv") as recorder:
+        recorder.write(PingResult.Pong(2.0, 'line, with "quotes"'), target="host")
+        recorder.write(PingResult.PingExited(1, "err"), target="host")
+
+    with path.open(newline="") as f:
+        rows = list(csv.DictReader(f))
+    assert [row["type"] for row in rows] == ["Pong", "PingExited"]
+    assert rows[0]["line"] == 'line, with "quotes"'
+    assert rows[0]["duration_ms"] == "2"
+    assert rows[1]["exit_code"] == "1"
+
+
+def test_recorder_appends(tmp_path: Path):
+    """测试重新打开时追加写入，CSV 表头只写一次"""
+    path = tmp_path / "trace.csv"
+    for _ in range(2):
+        with Recorder(path, format="csv") as recorder:
+            recorder.write(PingResult.Timeout("t"))
+
+    lines = path.read_text().splitlines()
+    assert lines[0].startswith("timestamp,")
+    assert len(lines) == 3
+
+
+def test_recorder_size_rotation(tmp_path: Path):
+    """测试按大小轮转"""
+    path = tmp_path / "trace.jsonl"
+    with Recorder(path, max_bytes=200) as recorder:
+        for i in range(10):
+            recorder.write(PingResult.Pong(float(i), "x" * 50))
+
+    files = sorted(tmp_path.iterdir())
+    assert len(files) > 1
+    total = sum(len(f.read_text().splitlines()) for f in files)
+    assert total == 10
+    assert all(f.stat().st_size <= 200 for f in files)
+
+
+@pytest.mark.skipif(sys.platform == "win32" or os.geteuid() == 0, reason="requires POSIX permissions as non-root")
+def test_recorder_rotation_failure_keeps_writing(tmp_path: Path):
+    """测试轮转失败时继续写入原文件并报告错误"""
+    directory = tmp_path / "logs"
+    directory.mkdir()
+    path = directory / "trace.jsonl"
+    recorder = Recorder(path, max_bytes=200)
+    directory.chmod(0o500)
+    try:
+        for i in range(10):
+            recorder.write(PingResult.Pong(float(i), "x" * 50))
+        with pytest.raises(OSError):
+            recorder.flush()
+        recorder.close()
+    finally:
+        directory.chmod(0o700)
+
+    assert [f.name for f in directory.iterdir()] == ["trace.jsonl"]
+    assert len(path.read_text().splitlines()) == 10
+
+
+def test_recorder_invalid_args(tmp_path: Path):
+    """测试非法参数"""
+    with pytest.raises(ValueError):
+        _ = Recorder(tmp_path / "x", format="xml")  # pyright: ignore[reportArgumentType]
+    with pytest.raises(ValueError):
+        _ = Recorder(tmp_path / "x", max_bytes=0)
+    with pytest.raises(OSError):
+        _ = Recorder(tmp_path / "missing" / "x.jsonl")
+    with pytest.raises(TypeError):
+        PingStream("127.0.0.1", max_count=1).add_sink(object())  # pyright: ignore[reportArgumentType]
+
+
+def test_stream_sink(tmp_path: Path, target: TargetType):
+    """测试同步流写入记录器"""
+    path = tmp_path / "stream.jsonl"
+    recorder = Recorder(path)
+    stream = PingStream(target, interval_ms=200, max_count=3)
+    stream.add_sink(recorder)
+
+    results = list(stream)
+    recorder.close()
+
+    records = [json.loads(line) for line in path.read_text().splitlines()]
+    assert len(records) == len(results) == 3
+    assert all(record["target"] == str(target) for record in records)
+
+
+@pytest.mark.asyncio
+async def test_async_stream_sink(tmp_path: Path, target: TargetType):
+    """测试异步流写入记录器"""
+    path = tmp_path / "stream.csv"
+    recorder = Recorder(path, format="csv")
+    stream = AsyncPingStream(target, interval_ms=200, max_count=3)
+    stream.add_sink(recorder)
+
+    results = [result async for result in stream]
+    recorder.close()
+
+    with path.open(newline="") as f:
+        rows = list(csv.DictReader(f))
+    assert len(rows) == len(results) == 3
+    assert all(row["type"] == "Pong" for row in rows)
+
+
+
+@pytest.mark.asyncio
+async def test_async_stream_drop_flushes(tmp_path: Path, target: TargetType):
+    """测试异步流被回收时刷新输出端，记录器仍打开时文件中已有全部结果"""
+    path = tmp_path / "stream.jsonl"
+    recorder = Recorder(path)
+    stream = AsyncPingStream(target, interval_ms=200, max_count=2)
+    stream.add_sink(recorder)
+
+    results = [result async for result in stream]
+    del stream
+    gc.collect()
+
+    assert len(path.read_text().splitlines()) == len(results) == 2
+    recorder.close()
+
+
+if __name__ == "__main__":
+    _ = pytest.main(["-xvs", __file__])
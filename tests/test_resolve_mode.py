@@ -0,0 +1,114 @@
+"""
+多地址解析模式（resolve_mode）测试
+"""
+
+import pytest
+from ping_rs import (
+    DnsResolver,
+    Monitor,
+    Pinger,
+    PingResult,
+    PingStream,
+    SweepResult,
+    ping_multiple,
+    ping_multiple_async,
+    ping_once,
+    ping_once_async,
+)
+
+from .conftest import StubDnsServer
+
+MULTI = DnsResolver(hosts={"multi.test": ["127.0.0.1", "127.0.0.2"]})
+
+
+def test_resolve_mode_all():
+    """测试 all 模式并发 ping 每个地址"""
+    result = ping_once("multi.test", resolve_mode="all", dns_resolver=MULTI)
+    assert isinstance(result, SweepResult)
+    assert [host.target for host in result.hosts] == ["127.0.0.1", "127.0.0.2"]
+    assert result.alive == ["127.0.0.1", "127.0.0.2"]
+    assert all(len(host.results) == 1 for host in result.hosts)
+
+
+def test_resolve_mode_all_interleaves_families():
+    """测试 all 模式按 RFC 8305 交替排列地址族，IPv6 在前"""
+    server = StubDnsServer({"dual.test": ["127.0.0.1", "127.0.0.2", "::1"]})
+    try:
+        resolver = DnsResolver([server.address])
+        result = ping_once("dual.test", resolve_mode="all", dns_resolver=resolver)
+        assert [host.target for host in result.hosts] == ["::1", "127.0.0.1", "127.0.0.2"]
+        assert "127.0.0.1" in result.alive
+
+        result = ping_once("dual.test", ipv4=True, resolve_mode="all", dns_resolver=resolver)
+        assert [host.target for host in result.hosts] == ["127.0.0.1", "127.0.0.2"]
+    finally:
+        server.close()
+
+
+def test_resolve_mode_all_multiple():
+    """测试 all 模式下每个地址发送 count 次"""
+    result = ping_multiple("multi.test", count=2, interval_ms=100, resolve_mode="all", dns_resolver=MULTI)
+    assert len(result) == 2
+    assert all(len(host.results) == 2 for host in result.hosts)
+
+    # timeout_ms 限制每个地址的探测次数
+    result = ping_multiple(
+        "multi.test", count=10, interval_ms=100, timeout_ms=300, resolve_mode="all", dns_resolver=MULTI
+    )
+    assert all(len(host.results) == 3 for host in result.hosts)
+
+
+def test_resolve_mode_all_ip_target():
+    """测试 IP 目标只 ping 自身"""
+    result = ping_once("127.0.0.1", resolve_mode="all")
+    assert [host.target for host in result.hosts] == ["127.0.0.1"]
+
+
+def test_resolve_mode_all_resolution_failure(dns_server):
+    """测试解析失败时返回目标本身的 PingExited"""
+    resolver = DnsResolver([dns_server.address])
+    result = ping_once("missing.test", resolve_mode="all", dns_resolver=resolver)
+    assert [host.target for host in result.hosts] == ["missing.test"]
+    assert isinstance(result.hosts[0].results[0], PingResult.PingExited)
+    assert result.dead == ["missing.test"]
+
+
+@pytest.mark.asyncio
+async def test_resolve_mode_all_async():
+    """测试异步 all 模式"""
+    result = await ping_once_async("multi.test", resolve_mode="all", dns_resolver=MULTI)
+    assert result.alive == ["127.0.0.1", "127.0.0.2"]
+
+    result = await ping_multiple_async("multi.test", count=2, interval_ms=100, resolve_mode="all", dns_resolver=MULTI)
+    assert all(len(host.results) == 2 for host in result.hosts)
+
+
+def test_resolve_mode_happy_eyeballs_fallback():
+    """测试首选地址无响应时回退到下一个地址"""
+    resolver = DnsResolver(hosts={"fallback.test": ["192.0.2.1", "127.0.0.1"]})
+    result = ping_once("fallback.test", timeout_ms=2000, resolve_mode="happy_eyeballs", dns_resolver=resolver)
+    assert result.is_success()
+
+
+def test_resolve_mode_happy_eyeballs_single_address():
+    """测试只有一个地址时直接使用"""
+    pinger = Pinger("localhost", resolve_mode="happy_eyeballs", ipv4=True)
+    assert pinger.ping_once().is_success()
+
+
+def test_resolve_mode_first_unchanged():
+    """测试 first 模式返回单个结果"""
+    result = ping_once("multi.test", dns_resolver=MULTI)
+    assert isinstance(result, PingResult.Pong)
+
+
+def test_resolve_mode_invalid():
+    """测试无效模式，以及不支持 all 模式的接口"""
+    with pytest.raises(ValueError):
+        ping_once("127.0.0.1", resolve_mode="random")  # type: ignore[call-overload]
+    with pytest.raises(ValueError):
+        Pinger("127.0.0.1", resolve_mode="all")  # type: ignore[arg-type]
+    with pytest.raises(ValueError):
+        PingStream("127.0.0.1", resolve_mode="all")  # type: ignore[arg-type]
+    with Monitor() as monitor, pytest.raises(ValueError):
+        monitor.add_target("127.0.0.1", resolve_mode="all")  # type: ignore[arg-type]
@@ -0,0 +1,96 @@
+"""
+流在 ping 进程退出后自动重启的测试
+"""
+
+import pytest
+from ping_rs import AsyncPingStream, PingResult, PingStream, create_ping_stream
+
+# DNS 预解析失败时 ping 进程会立即以 PingExited 结束
+INVALID_HOST = "nonexistent.invalid"
+
+
+def test_stream_without_restart_stops():
+    """测试默认不重启：退出后流结束"""
+    stream = PingStream(INVALID_HOST, interval_ms=200, dns_resolve_timeout_ms=1000)
+    result = next(stream)
+    assert isinstance(result, PingResult.PingExited)
+    assert not stream.is_active()
+    with pytest.raises(StopIteration):
+        next(stream)
+
+
+def test_stream_restart_on_exit():
+    """测试退出后先返回 PingExited，再重启直到达到 max_restarts"""
+    stream = PingStream(
+        INVALID_HOST,
+        interval_ms=200,
+        dns_resolve_timeout_ms=1000,
+        restart_on_exit=True,
+        restart_backoff_ms=100,
+        max_restarts=2,
+    )
+    results = list(stream)
+    # 首次启动 + 2 次重启
+    assert len(results) == 3
+    assert all(isinstance(r, PingResult.PingExited) for r in results)
+    assert not stream.is_active()
+
+
+def test_stream_restart_pending_is_active():
+    """测试等待重启期间流仍然活跃，非阻塞接收不会等待"""
+    stream = create_ping_stream(
+        INVALID_HOST,
+        interval_ms=200,
+        dns_resolve_timeout_ms=1000,
+        restart_on_exit=True,
+        restart_backoff_ms=5000,
+    )
+    assert isinstance(stream.recv(), PingResult.PingExited)
+    assert stream.is_active()
+    assert stream.try_recv() is None
+
+
+@pytest.mark.asyncio
+async def test_async_stream_restart_on_exit():
+    """测试异步流退出后先返回 PingExited，再重启直到达到 max_restarts"""
+    stream = AsyncPingStream(
+        INVALID_HOST,
+        interval_ms=200,
+        dns_resolve_timeout_ms=1000,
+        restart_on_exit=True,
+        restart_backoff_ms=100,
+        max_restarts=1,
+    )
+    results = [result async for result in stream]
+    assert len(results) == 2
+    assert all(isinstance(r, PingResult.PingExited) for r in results)
+
+
+@pytest.mark.asyncio
+async def test_async_stream_restart_emits_last_exit():
+    """测试达到 max_restarts 时仍先返回最后一次的 PingExited，下次迭代才结束"""
+    stream = AsyncPingStream(
+        INVALID_HOST,
+        interval_ms=200,
+        dns_resolve_timeout_ms=1000,
+        restart_on_exit=True,
+        restart_backoff_ms=100,
+        max_restarts=0,
+    )
+    assert isinstance(await stream.__anext__(), PingResult.PingExited)
+    with pytest.raises(StopAsyncIteration):
+        await stream.__anext__()
+
+
+@pytest.mark.asyncio
+async def test_async_stream_without_restart_stops():
+    """测试异步流默认在退出时结束迭代"""
+    stream = AsyncPingStream(INVALID_HOST, interval_ms=200, dns_resolve_timeout_ms=1000)
+    results = [result async for result in stream]
+    assert results == []
+
+
+def test_restart_invalid_backoff():
+    """测试无效的重启等待时间"""
+    with pytest.raises(ValueError):
+        PingStream("127.0.0.1", restart_on_exit=True, restart_backoff_ms=0)
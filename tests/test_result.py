@@ -0,0 +1,91 @@
+"""
+PingResult 相等性、哈希、排序和模式匹配测试
+"""
+
+import pytest
+from ping_rs import PingResult
+
+
+def test_structural_equality():
+    """测试结构相等"""
+    assert PingResult.Pong(1.0, "x") == PingResult.Pong(1.0, "x")
+    assert PingResult.Pong(1.0, "x") != PingResult.Pong(2.0, "x")
+    assert PingResult.Pong(1.0, "x") != PingResult.Pong(1.0, "y")
+    assert PingResult.Timeout("x") == PingResult.Timeout("x")
+    assert PingResult.Timeout("x") != PingResult.Unknown("x")
+    assert PingResult.PingExited(1, "err") == PingResult.PingExited(1, "err")
+    assert PingResult.PingExited(1, "err") != PingResult.PingExited(2, "err")
+    assert PingResult.Pong(1.0, "x") != "x"
+
+
+def test_nan_equality():
+    """测试 NaN 延迟按位模式比较"""
+    nan = float("nan")
+    assert PingResult.Pong(nan, "x") == PingResult.Pong(nan, "x")
+
+
+def test_hash_consistency():
+    """测试哈希与相等一致，可用于去重"""
+    results = [
+        PingResult.Pong(1.0, "x"),
+        PingResult.Pong(1.0, "x"),
+        PingResult.Timeout("t"),
+        PingResult.Timeout("t"),
+        PingResult.PingExited(1, "err"),
+    ]
+    assert hash(results[0]) == hash(results[1])
+    assert len(set(results)) == 3
+    assert {PingResult.Pong(1.0, "x"): "a"}[PingResult.Pong(1.0, "x")] == "a"
+
+
+def test_ordering():
+    """测试排序：先按变体，再按字段"""
+    results = [
+        PingResult.PingExited(1, "err"),
+        PingResult.Unknown("u"),
+        PingResult.Pong(3.0, "c"),
+        PingResult.Timeout("t"),
+        PingResult.Pong(1.0, "a"),
+    ]
+    assert sorted(results) == [
+        PingResult.Pong(1.0, "a"),
+        PingResult.Pong(3.0, "c"),
+        PingResult.Timeout("t"),
+        PingResult.Unknown("u"),
+        PingResult.PingExited(1, "err"),
+    ]
+    assert PingResult.Pong(1.0, "a") < PingResult.Pong(2.0, "a")
+    assert PingResult.Timeout("t") >= PingResult.Pong(100.0, "a")
+
+
+def test_ordering_with_other_types():
+    """测试与其他类型比较时抛出 TypeError"""
+    with pytest.raises(TypeError):
+        _ = PingResult.Pong(1.0, "x") < 1  # pyright: ignore[reportOperatorIssue]
+
+
+def test_match_args():
+    """测试变体构造函数与模式匹配"""
+    result = PingResult.Pong(duration_ms=1.5, line="x")
+    match result:
+        case PingResult.Pong(duration_ms, line):
+            assert duration_ms == 1.5
+            assert line == "x"
+        case _:
+            pytest.fail("Pong should match PingResult.Pong")
+
+    match PingResult.PingExited(2, "err"):
+        case PingResult.PingExited(exit_code, stderr):
+            assert exit_code == 2
+            assert stderr == "err"
+        case _:
+            pytest.fail("PingExited should match PingResult.PingExited")
+
+    assert PingResult.Pong.__match_args__ == ("duration_ms", "line")
+    assert PingResult.Timeout.__match_args__ == ("line",)
+    assert PingResult.Unknown.__match_args__ == ("line",)
+    assert PingResult.PingExited.__match_args__ == ("exit_code", "stderr")
+
+
+if __name__ == "__main__":
+    _ = pytest.main(["-xvs", __file__])
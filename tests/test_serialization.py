@@ -0,0 +1,70 @@
+"""
+PingResult 序列化测试（JSON、字典、pickle）
+"""
+
+import copy
+import json
+import pickle
+from concurrent.futures import ProcessPoolExecutor
+
+import pytest
+from ping_rs import PingResult
+
+SAMPLES = [
+    PingResult.Pong(1.5, "64 bytes from 127.0.0.1: icmp_seq=1 ttl=64 time=1.5 ms"),
+    PingResult.Timeout("Request timeout for icmp_seq 0"),
+    PingResult.Unknown("PING 127.0.0.1 (127.0.0.1): 56 data bytes"),
+    PingResult.PingExited(2, "ping: unknown host"),
+]
+
+
+def _roundtrip_in_subprocess(result: PingResult) -> PingResult:
+    return result
+
+
+@pytest.mark.parametrize("result", SAMPLES)
+def test_json_roundtrip(result: PingResult):
+    """测试 to_json / from_json 往返"""
+    data = result.to_json()
+    assert json.loads(data) == result.to_dict()
+
+    restored = PingResult.from_json(data)
+    assert restored.to_dict() == result.to_dict()
+    assert type(restored) is type(result)
+
+
+@pytest.mark.parametrize("result", SAMPLES)
+def test_dict_roundtrip(result: PingResult):
+    """测试 to_dict / from_dict 往返"""
+    restored = PingResult.from_dict(result.to_dict())
+    assert restored.to_dict() == result.to_dict()
+
+
+@pytest.mark.parametrize("result", SAMPLES)
+def test_pickle_roundtrip(result: PingResult):
+    """测试 pickle 和 copy"""
+    restored = pickle.loads(pickle.dumps(result))
+    assert restored.to_dict() == result.to_dict()
+    assert copy.copy(result).to_dict() == result.to_dict()
+    assert copy.deepcopy(result).to_dict() == result.to_dict()
+
+
+def test_multiprocessing_boundary():
+    """测试 PingResult 可以跨进程传递"""
+    with ProcessPoolExecutor(max_workers=1) as executor:
+        restored = list(executor.map(_roundtrip_in_subprocess, SAMPLES))
+    assert [r.to_dict() for r in restored] == [r.to_dict() for r in SAMPLES]
+
+
+def test_invalid_input():
+    """测试非法输入"""
+    with pytest.raises(ValueError):
+        _ = PingResult.from_json("not json")
+    with pytest.raises(ValueError):
+        _ = PingResult.from_json('{"type": "Bogus"}')
+    with pytest.raises(KeyError):
+        _ = PingResult.from_dict({"type": "Pong", "line": "x"})  # pyright: ignore[reportArgumentType]
+
+
+if __name__ == "__main__":
+    _ = pytest.main(["-xvs", __file__])
@@ -0,0 +1,75 @@
+"""
+多目标扫描与 CIDR / 地址范围展开测试
+"""
+
+import ipaddress
+
+import pytest
+from ping_rs import HostResult, PingResult, SweepResult, sweep, sweep_async
+
+
+def test_sweep_cidr():
+    """测试 CIDR 字符串展开（排除网络地址和广播地址）"""
+    result = sweep("127.0.0.0/30", timeout_ms=500)
+    assert isinstance(result, SweepResult)
+    assert [host.target for host in result.hosts] == ["127.0.0.1", "127.0.0.2"]
+    assert result.alive == ["127.0.0.1", "127.0.0.2"]
+    assert result.dead == []
+    assert len(result) == 2
+
+
+def test_sweep_network_object():
+    """测试 IPv4Network 对象"""
+    result = sweep(ipaddress.IPv4Network("127.0.0.4/31"), timeout_ms=500)
+    assert [host.target for host in result.hosts] == ["127.0.0.4", "127.0.0.5"]
+
+
+def test_sweep_range():
+    """测试地址范围，包括只写最后一段的简写"""
+    result = sweep("127.0.0.1-127.0.0.3", timeout_ms=500)
+    assert [host.target for host in result.hosts] == ["127.0.0.1", "127.0.0.2", "127.0.0.3"]
+    short = sweep("127.0.0.1-3", timeout_ms=500)
+    assert [host.target for host in short.hosts] == ["127.0.0.1", "127.0.0.2", "127.0.0.3"]
+
+
+def test_sweep_host_result():
+    """测试单个地址的结果"""
+    result = sweep(["127.0.0.1", "127.0.0.1"], count=2, timeout_ms=500)
+    # 重复的目标只探测一次
+    assert len(result) == 1
+    host = result.hosts[0]
+    assert isinstance(host, HostResult)
+    assert host.alive
+    assert host.rtt_ms is not None and host.rtt_ms >= 0
+    assert len(host.results) == 2
+    assert all(isinstance(r, PingResult.Pong) for r in host.results)
+
+
+def test_sweep_dead_host():
+    """测试无响应的地址（TEST-NET-1 文档地址）"""
+    result = sweep(["127.0.0.1", "192.0.2.1"], timeout_ms=500)
+    assert result.alive == ["127.0.0.1"]
+    assert result.dead == ["192.0.2.1"]
+    assert result.hosts[1].rtt_ms is None
+
+
+@pytest.mark.asyncio
+async def test_sweep_async():
+    """测试异步扫描"""
+    result = await sweep_async("127.0.0.0/30", timeout_ms=500)
+    assert result.alive == ["127.0.0.1", "127.0.0.2"]
+
+
+def test_sweep_invalid_targets():
+    """测试无效的网段和范围"""
+    with pytest.raises(ValueError):
+        sweep("10.0.0.0/33")
+    with pytest.raises(ValueError):
+        sweep("10.0.0.10-10.0.0.1")
+    with pytest.raises(ValueError):
+        sweep("10.0.0.1-::1")
+    # 超过展开上限
+    with pytest.raises(ValueError):
+        sweep("10.0.0.0/8")
+    with pytest.raises(ValueError):
+        sweep("127.0.0.1", concurrency=0)
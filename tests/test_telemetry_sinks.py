@@ -0,0 +1,212 @@
+"""
+InfluxDB 行协议与 StatsD 输出端测试
+"""
+
+import gc
+import math
+import socket
+import threading
+import time
+from collections.abc import Iterator
+from http.server import BaseHTTPRequestHandler, HTTPServer
+from typing import Any
+
+import pytest
+from ping_rs import InfluxSink, PingResult, PingStream, StatsdSink
+from ping_rs.core_schema import TargetType
+
+
+@pytest.fixture
+def udp_listener() -> Iterator[socket.socket]:
+    """本地 UDP 监听器"""
+    sock = socket.socket(socket.AF_INET, socket.SOCK_DGRAM)
+    sock.bind(("127.0.0.1", 0))
+    sock.settimeout(5)
+    yield sock
+    sock.close()
+
+
+def receive(sock: socket.socket) -> str:
+    return sock.recvfrom(65535)[0].decode()
+
+
+def test_influx_udp(udp_listener: socket.socket):
+    """测试经 UDP 发送行协议"""
+    port = udp_listener.getsockname()[1]
+    sink = InfluxSink(f"udp://127.0.0.1:{port}", measurement="icmp")
+    sink.write(PingResult.Pong(12.5, "a"), target="10.0.0.1")
+    sink.write(PingResult.PingExited(2, 'bad "arg"'), target="host name")
+    sink.flush()
+
+    pong = receive(udp_listener)
+    measurement_tags, fields, timestamp = pong.split(" ")
+    assert measurement_tags == "icmp,target=10.0.0.1"
+    assert 'type="Pong"' in fields
+    assert "rtt_ms=12.5" in fields
+    assert "success=true" in fields
+    assert int(timestamp) > 0
+
+    exited = receive(udp_listener)
+    assert exited.startswith("icmp,target=host\\ name ")
+    assert "exit_code=2i" in exited
+    assert 'stderr="bad \\"arg\\""' in exited
+
+
+def test_influx_udp_stream_tags(udp_listener: socket.socket, target: TargetType):
+    """测试挂载到流上时带有流标签"""
+    port = udp_listener.getsockname()[1]
+    sink = InfluxSink(f"udp://127.0.0.1:{port}")
+    stream = PingStream(target, interval_ms=200, max_count=1, ipv4=True)
+    stream.add_sink(sink)
+    assert len(list(stream)) == 1
+
+    line = receive(udp_listener)
+    assert line.startswith(f"ping,target={target},address_family=ipv4 ")
+
+
+class InfluxHandler(BaseHTTPRequestHandler):
+    def do_POST(self):
+        length = int(self.headers["Content-Length"])
+        self.server.requests.append((self.path, dict(self.headers), self.rfile.read(length).decode()))  # type: ignore[attr-defined]
+        self.send_response(204)
+        self.send_header("Content-Length", "0")
+        self.end_headers()
+
+    def log_message(self, *args: Any):
+        pass
+
+
+def test_influx_http():
+    """测试经 HTTP 批量写入"""
+    server = HTTPServer(("127.0.0.1", 0), InfluxHandler)
+    server.requests = []  # type: ignore[attr-defined]
+    thread = threading.Thread(target=server.serve_forever, daemon=True)
+    thread.start()
+    try:
+        url = f"http://127.0.0.1:{server.server_address[1]}/api/v2/write?bucket=ping"
+        with InfluxSink(url, token="secret", flush_interval_ms=60000) as sink:
+            sink.write(PingResult.Pong(1.0, "a"), target="a")
+            sink.write(PingResult.Timeout("b"), target="b")
+
+        path, headers, body = server.requests[0]  # type: ignore[attr-defined]
+        assert path == "/api/v2/write?bucket=ping"
+        assert headers["Authorization"] == "Token secret"
+        lines = body.split("\n")
+        assert len(lines) == 2
+        assert lines[0].startswith("ping,target=a ")
+        assert "success=false" in lines[1]
+    finally:
+        server.shutdown()
+        server.server_close()
+
+
+def test_influx_udp_skips_nan_rtt(udp_listener: socket.socket):
+    """测试 NaN 延迟不写入 rtt_ms 字段"""
+    port = udp_listener.getsockname()[1]
+    sink = InfluxSink(f"udp://127.0.0.1:{port}")
+    sink.write(PingResult.Pong(math.nan, "a"), target="a")
+
+    line = receive(udp_listener)
+    assert "rtt_ms" not in line
+    assert "success=true" in line
+
+
+class FlakyInfluxHandler(InfluxHandler):
+    def do_POST(self):
+        if self.server.failures > 0:  # type: ignore[attr-defined]
+            self.server.failures -= 1  # type: ignore[attr-defined]
+            self.send_response(500)
+            self.send_header("Content-Length", "0")
+            self.end_headers()
+            return
+        super().do_POST()
+
+
+def start_influx_server(handler: type[BaseHTTPRequestHandler], failures: int = 0) -> HTTPServer:
+    server = HTTPServer(("127.0.0.1", 0), handler)
+    server.requests = []  # type: ignore[attr-defined]
+    server.failures = failures  # type: ignore[attr-defined]
+    threading.Thread(target=server.serve_forever, daemon=True).start()
+    return server
+
+
+def test_influx_http_retries_failed_batch():
+    """测试发送失败的行保留在缓存中，下次发送时重试"""
+    server = start_influx_server(FlakyInfluxHandler, failures=1)
+    try:
+        sink = InfluxSink(f"http://127.0.0.1:{server.server_address[1]}", flush_interval_ms=60000)
+        sink.write(PingResult.Pong(1.0, "a"), target="a")
+        with pytest.raises(IOError):
+            sink.flush()
+
+        sink.write(PingResult.Pong(2.0, "b"), target="b")
+        sink.close()
+        _, _, body = server.requests[0]  # type: ignore[attr-defined]
+        lines = body.split("\n")
+        assert [line.split(" ")[0] for line in lines] == ["ping,target=a", "ping,target=b"]
+    finally:
+        server.shutdown()
+        server.server_close()
+
+
+def test_influx_http_flushes_on_drop():
+    """测试输出端被回收时发送剩余的行"""
+    server = start_influx_server(InfluxHandler)
+    try:
+        sink = InfluxSink(f"http://127.0.0.1:{server.server_address[1]}", flush_interval_ms=60000)
+        sink.write(PingResult.Pong(1.0, "a"), target="a")
+        del sink
+        gc.collect()
+
+        deadline = time.monotonic() + 5
+        while not server.requests and time.monotonic() < deadline:  # type: ignore[attr-defined]
+            time.sleep(0.05)
+        assert len(server.requests) == 1  # type: ignore[attr-defined]
+    finally:
+        server.shutdown()
+        server.server_close()
+
+
+def test_influx_invalid_url():
+    """测试无效地址"""
+    with pytest.raises(ValueError):
+        InfluxSink("tcp://127.0.0.1:8086")
+
+
+def test_statsd(udp_listener: socket.socket):
+    """测试 StatsD 计时和计数器"""
+    port = udp_listener.getsockname()[1]
+    sink = StatsdSink(port=port, prefix="net")
+    sink.write(PingResult.Pong(3.5, "a"), target="10.0.0.1")
+    sink.write(PingResult.Timeout("b"), target="10.0.0.1")
+    sink.flush()
+
+    assert receive(udp_listener).split("\n") == ["net.10_0_0_1.rtt:3.5|ms", "net.10_0_0_1.sent:1|c"]
+    assert receive(udp_listener).split("\n") == ["net.10_0_0_1.sent:1|c", "net.10_0_0_1.lost:1|c"]
+
+
+
+def test_statsd_skips_nan_rtt(udp_listener: socket.socket):
+    """测试 NaN 延迟只计数，不发送计时"""
+    port = udp_listener.getsockname()[1]
+    sink = StatsdSink(port=port, prefix="net")
+    sink.write(PingResult.Pong(math.nan, "a"), target="a")
+
+    assert receive(udp_listener).split("\n") == ["net.a.sent:1|c"]
+
+
+
+def test_otlp_exporter_exported_only_when_built():
+    """测试 OtlpJsonSink 只在以 otlp feature 构建时出现在 __all__ 中"""
+    import ping_rs
+
+    assert ("OtlpJsonSink" in ping_rs.__all__) == (ping_rs.OtlpJsonSink is not None)
+
+
+def test_statsd_tags(udp_listener: socket.socket):
+    """测试 DogStatsD 标签"""
+    port = udp_listener.getsockname()[1]
+    sink = StatsdSink(port=port, tags=True)
+    sink.write(PingResult.PingExited(1, ""), target="example.com")
+
+    assert receive(udp_listener) == "ping.exits:1|c|#target:example.com"
@@ -0,0 +1,143 @@
+"""
+TLS 握手探测测试
+"""
+
+import json
+import shutil
+import socket
+import ssl
+import subprocess
+import threading
+from pathlib import Path
+
+import pytest
+from ping_rs import PingResult, Recorder, TlsPinger, TlsPingResult
+
+pytestmark = pytest.mark.skipif(shutil.which("openssl") is None, reason="需要 openssl 命令生成自签名证书")
+
+
+@pytest.fixture(scope="module")
+def certificate(tmp_path_factory):
+    """生成 30 天后过期的自签名证书"""
+    directory = tmp_path_factory.mktemp("tls")
+    cert, key = directory / "cert.pem", directory / "key.pem"
+    subprocess.run(
+        [
+            "openssl", "req", "-x509", "-newkey", "rsa:2048", "-nodes", "-days", "30",
+            "-subj", "/CN=localhost", "-keyout", str(key), "-out", str(cert),
+        ],
+        check=True,
+        capture_output=True,
+    )
+    return cert, key
+
+
+@pytest.fixture
+def tls_server(certificate):
+    """本地 TLS 服务器，完成握手后关闭连接，返回监听端口"""
+    context = ssl.SSLContext(ssl.PROTOCOL_TLS_SERVER)
+    context.load_cert_chain(*certificate)
+    listener = socket.socket()
+    listener.bind(("127.0.0.1", 0))
+    listener.listen()
+    listener.settimeout(0.1)
+    stop = threading.Event()
+
+    def serve():
+        while not stop.is_set():
+            try:
+                conn, _ = listener.accept()
+            except socket.timeout:
+                continue
+            try:
+                with context.wrap_socket(conn, server_side=True) as tls:
+                    tls.recv(1)
+            except (ssl.SSLError, OSError):
+                pass
+
+    thread = threading.Thread(target=serve, daemon=True)
+    thread.start()
+    yield listener.getsockname()[1]
+    stop.set()
+    thread.join()
+    listener.close()
+
+
+def test_tls_handshake(tls_server):
+    """测试握手耗时、协商参数和证书过期时间"""
+    result = TlsPinger("127.0.0.1", tls_server, server_name="localhost", verify=False).ping_once()
+    assert isinstance(result, TlsPingResult)
+    assert result.is_success(), result.error
+    assert result.server_name == "localhost"
+    assert result.address == "127.0.0.1"
+    assert result.protocol_version in ("TLSv1.2", "TLSv1.3")
+    assert result.cipher_suite
+    assert result.connect_ms is not None and result.handshake_ms is not None
+    assert result.total_ms == pytest.approx(result.connect_ms + result.handshake_ms)
+    assert 29 < result.cert_expires_in_days <= 30
+    assert result.cert_not_after > 0
+    assert "protocol_version='TLSv1." in repr(result)
+
+
+def test_tls_to_ping_result(tls_server):
+    """测试转换为 PingResult"""
+    result = TlsPinger("localhost", tls_server, verify=False, ipv4=True).ping_once().to_ping_result()
+    assert isinstance(result, PingResult.Pong)
+    assert "handshake with localhost" in result.line
+
+
+def test_tls_result_to_sink(tls_server, tmp_path: Path):
+    """测试 TlsPingResult 直接写入输出端"""
+    path = tmp_path / "tls.jsonl"
+    with Recorder(path) as recorder:
+        recorder.write(TlsPinger("127.0.0.1", tls_server, verify=False).ping_once(), target="127.0.0.1")
+    record = json.loads(path.read_text())
+    assert record["type"] == "Pong"
+    assert record["target"] == "127.0.0.1"
+
+
+def test_tls_verify_failure(tls_server):
+    """测试自签名证书校验失败"""
+    result = TlsPinger("127.0.0.1", tls_server, server_name="localhost").ping_once()
+    assert not result.is_success()
+    assert result.error
+    assert result.connect_ms is not None
+    assert result.handshake_ms is None
+    assert result.total_ms is None
+    assert result.to_ping_result().is_exited()
+
+
+def test_tls_timeout():
+    """测试只接受连接但不握手的服务器"""
+    listener = socket.socket()
+    listener.bind(("127.0.0.1", 0))
+    listener.listen()
+    try:
+        result = TlsPinger("127.0.0.1", listener.getsockname()[1], timeout_ms=300).ping_once()
+        assert result.timed_out
+        assert result.to_ping_result().is_timeout()
+    finally:
+        listener.close()
+
+
+def test_tls_ping_multiple(tls_server):
+    """测试多次握手"""
+    results = TlsPinger("127.0.0.1", tls_server, interval_ms=50, verify=False).ping_multiple(3)
+    assert len(results) == 3
+    assert all(r.is_success() for r in results)
+
+
+@pytest.mark.asyncio
+async def test_tls_async(tls_server):
+    """测试异步握手"""
+    pinger = TlsPinger("127.0.0.1", tls_server, interval_ms=50, verify=False)
+    assert (await pinger.ping_once_async()).is_success()
+    assert len(await pinger.ping_multiple_async(count=2)) == 2
+
+
+def test_tls_invalid_args():
+    """测试无效参数"""
+    with pytest.raises(ValueError):
+        TlsPinger("127.0.0.1", timeout_ms=0)
+    with pytest.raises(ValueError):
+        TlsPinger("127.0.0.1", interval_ms=-1)
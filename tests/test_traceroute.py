@@ -0,0 +1,44 @@
+"""
+路由追踪测试
+"""
+
+import pytest
+from ping_rs import TracerouteHop, traceroute, traceroute_async
+
+
+def test_traceroute_loopback():
+    """测试追踪本机地址：第一跳即到达目标"""
+    hops = traceroute("127.0.0.1", max_hops=5, probes_per_hop=3, timeout_ms=500)
+    assert len(hops) == 1
+    hop = hops[0]
+    assert isinstance(hop, TracerouteHop)
+    assert hop.hop == 1
+    assert hop.address == "127.0.0.1"
+    assert hop.reached
+    assert len(hop.rtts_ms) + hop.timeouts == 3
+    assert all(rtt >= 0 for rtt in hop.rtts_ms)
+    assert "TracerouteHop(hop=1" in repr(hop)
+
+
+@pytest.mark.asyncio
+async def test_traceroute_async():
+    """测试异步路由追踪"""
+    hops = await traceroute_async("localhost", max_hops=5, probes_per_hop=1, timeout_ms=500, ipv4=True)
+    assert hops[-1].reached
+    assert hops[-1].address == "127.0.0.1"
+
+
+def test_traceroute_invalid_args():
+    """测试无效参数"""
+    with pytest.raises(ValueError):
+        traceroute("127.0.0.1", max_hops=0)
+    with pytest.raises(ValueError):
+        traceroute("127.0.0.1", probes_per_hop=0)
+    with pytest.raises(ValueError):
+        traceroute("127.0.0.1", timeout_ms=0)
+
+
+def test_traceroute_unresolvable():
+    """测试无法解析的主机名"""
+    with pytest.raises(RuntimeError):
+        traceroute("nonexistent.invalid", timeout_ms=500)
@@ -0,0 +1,131 @@
+"""
+UDP 探测测试
+"""
+
+import socket
+import threading
+
+import pytest
+from ping_rs import PingResult, UdpPinger, UdpPingStream
+
+
+@pytest.fixture
+def echo_server():
+    """本地 UDP 回显服务器，返回监听端口"""
+    sock = socket.socket(socket.AF_INET, socket.SOCK_DGRAM)
+    sock.bind(("127.0.0.1", 0))
+    sock.settimeout(0.1)
+    stop = threading.Event()
+
+    def serve():
+        while not stop.is_set():
+            try:
+                data, addr = sock.recvfrom(65535)
+            except socket.timeout:
+                continue
+            sock.sendto(data, addr)
+
+    thread = threading.Thread(target=serve, daemon=True)
+    thread.start()
+    yield sock.getsockname()[1]
+    stop.set()
+    thread.join()
+    sock.close()
+
+
+@pytest.fixture
+def closed_port():
+    """一个没有监听者的本地 UDP 端口"""
+    sock = socket.socket(socket.AF_INET, socket.SOCK_DGRAM)
+    sock.bind(("127.0.0.1", 0))
+    port = sock.getsockname()[1]
+    sock.close()
+    return port
+
+
+def test_udp_pong(echo_server):
+    """测试回显服务器返回 Pong"""
+    pinger = UdpPinger("127.0.0.1", echo_server, timeout_ms=500)
+    result = pinger.ping_once()
+    assert result.is_success()
+    assert result.duration_ms is not None and result.duration_ms >= 0
+    assert "bytes from 127.0.0.1" in result.line
+
+
+def test_udp_port_unreachable(closed_port):
+    """测试关闭的端口返回 PortUnreachable"""
+    result = UdpPinger("127.0.0.1", closed_port, timeout_ms=500).ping_once()
+    assert isinstance(result, PingResult.PortUnreachable)
+    assert result.is_port_unreachable()
+    assert not result.is_success()
+    assert result.type_name == "PortUnreachable"
+    assert result.type_code == 4
+
+
+def test_udp_timeout():
+    """测试无响应的地址返回 Timeout"""
+    result = UdpPinger("192.0.2.1", 9, timeout_ms=300).ping_once()
+    assert result.is_timeout()
+
+
+def test_udp_ping_multiple(echo_server):
+    """测试多次探测"""
+    results = UdpPinger("127.0.0.1", echo_server, interval_ms=50, timeout_ms=500).ping_multiple(3)
+    assert len(results) == 3
+    assert all(r.is_success() for r in results)
+
+
+@pytest.mark.asyncio
+async def test_udp_ping_async(echo_server):
+    """测试异步探测"""
+    pinger = UdpPinger("localhost", echo_server, interval_ms=50, timeout_ms=500, ipv4=True)
+    assert (await pinger.ping_once_async()).is_success()
+    results = await pinger.ping_multiple_async(count=2)
+    assert [r.is_success() for r in results] == [True, True]
+
+
+def test_udp_stream_sync(echo_server):
+    """测试同步迭代流"""
+    stream = UdpPingStream("127.0.0.1", echo_server, interval_ms=50, timeout_ms=500, max_count=3)
+    results = list(stream)
+    assert len(results) == 3
+    assert all(r.is_success() for r in results)
+    assert not stream.is_active()
+
+
+@pytest.mark.asyncio
+async def test_udp_stream_async(closed_port):
+    """测试异步迭代流"""
+    stream = UdpPingStream("127.0.0.1", closed_port, interval_ms=50, timeout_ms=500, max_count=2)
+    results = [result async for result in stream]
+    assert len(results) == 2
+    assert all(r.is_port_unreachable() for r in results)
+
+
+def test_udp_unresolvable_host():
+    """测试无法解析的主机返回 PingExited"""
+    result = UdpPinger("nonexistent.invalid", 53, timeout_ms=500).ping_once()
+    assert result.is_exited()
+
+
+def test_port_unreachable_round_trip():
+    """测试 PortUnreachable 的序列化、比较和模式匹配"""
+    result = PingResult.PortUnreachable(1.5, "line")
+    assert result.to_dict() == {"type": "PortUnreachable", "duration_ms": 1.5, "line": "line"}
+    assert PingResult.from_dict(result.to_dict()) == result
+    assert PingResult.PingExited(1, "err") < result
+    match result:
+        case PingResult.PortUnreachable(duration_ms, line):
+            assert (duration_ms, line) == (1.5, "line")
+        case _:
+            pytest.fail("pattern did not match")
+
+
+def test_udp_invalid_args():
+    """测试无效参数"""
+    with pytest.raises(ValueError):
+        UdpPinger("127.0.0.1", 53, timeout_ms=0)
+    with pytest.raises(ValueError):
+        UdpPingStream("127.0.0.1", 53, interval_ms=-1)
+    with pytest.raises(OverflowError):
+        UdpPinger("127.0.0.1", 70000)